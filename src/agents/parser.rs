@@ -65,6 +65,7 @@ mod tests {
 name: security
 description: Focuses on security vulnerabilities
 model: claude-sonnet-4-20250514
+provider: hosted
 tags: [security, auth, injection]
 ---
 
@@ -82,6 +83,7 @@ Focus on injection vulnerabilities and auth issues.
             agent.profile.model,
             Some("claude-sonnet-4-20250514".to_string())
         );
+        assert_eq!(agent.profile.provider.as_deref(), Some("hosted"));
         assert_eq!(agent.profile.tags, vec!["security", "auth", "injection"]);
         assert!(agent.system_prompt.starts_with("You are a senior"));
     }
@@ -97,6 +99,7 @@ Do things."#;
         let agent = parse_agent_definition(content).unwrap();
         assert_eq!(agent.profile.name, "test");
         assert!(agent.profile.model.is_none());
+        assert!(agent.profile.provider.is_none());
         assert!(agent.profile.tags.is_empty());
         assert!(agent.profile.tools.is_empty());
     }
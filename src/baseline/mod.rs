@@ -0,0 +1,318 @@
+//! Baseline files for suppressing known findings.
+//!
+//! # Bounded Context: Finding Suppression
+//!
+//! A baseline (`.nitpik-baseline.json`) records the findings of one run
+//! so later reviews only report what is new — useful when adopting
+//! nitpik on a codebase with many pre-existing issues. Not to be
+//! confused with [`BaselineContext`](crate::models::BaselineContext),
+//! the project context sent to the LLM.
+//!
+//! Entries are matched by file, a hash of the flagged source lines, and
+//! title similarity. Line numbers are recorded for reference only, so an
+//! entry keeps matching after unrelated edits shift its code.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::models::finding::Finding;
+use crate::orchestrator::dedup::titles_similar;
+
+/// Current baseline file format version.
+const BASELINE_VERSION: u32 = 1;
+
+/// Errors from reading or writing a baseline file.
+#[derive(Error, Debug)]
+pub enum BaselineError {
+    #[error("failed to read baseline {path}: {source}")]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse baseline {path}: {source}")]
+    ParseFile {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("unsupported baseline version {0} (expected {BASELINE_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("failed to write baseline {path}: {source}")]
+    WriteFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// A known finding recorded in the baseline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// The file path relative to the repo root.
+    pub file: String,
+    /// Line the finding pointed at when recorded (informational).
+    pub line: u32,
+    /// The agent that produced the finding.
+    pub agent: String,
+    /// The finding's title.
+    pub title: String,
+    /// Hash of the whitespace-normalized lines the finding covered.
+    pub content_hash: String,
+}
+
+/// The contents of a baseline file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// File format version.
+    pub version: u32,
+    /// Known findings, sorted by file and line.
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// Findings left after applying a baseline, with suppression counts.
+#[derive(Debug)]
+pub struct Suppression {
+    /// Findings not covered by the baseline.
+    pub findings: Vec<Finding>,
+    /// Number of findings removed because the baseline knows them.
+    pub suppressed: usize,
+    /// Baseline entries for reviewed files that matched no finding.
+    pub stale: usize,
+}
+
+impl Baseline {
+    /// Record every finding, hashing its lines from `file_contents`.
+    ///
+    /// `file_contents` holds complete files keyed by repo-relative path;
+    /// findings in files missing from it hash as empty content.
+    pub fn from_findings(findings: &[Finding], file_contents: &IndexMap<String, String>) -> Self {
+        let mut entries: Vec<BaselineEntry> = findings
+            .iter()
+            .map(|f| BaselineEntry {
+                file: f.file.clone(),
+                line: f.line,
+                agent: f.agent.clone(),
+                title: f.title.clone(),
+                content_hash: content_hash(f, file_contents),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+        entries.dedup();
+
+        Self {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    /// Load a baseline file.
+    pub async fn load(path: &Path) -> Result<Self, BaselineError> {
+        let content =
+            tokio::fs::read_to_string(path)
+                .await
+                .map_err(|source| BaselineError::ReadFile {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+        let baseline: Self =
+            serde_json::from_str(&content).map_err(|source| BaselineError::ParseFile {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(BaselineError::UnsupportedVersion(baseline.version));
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline as pretty-printed JSON.
+    pub async fn save(&self, path: &Path) -> Result<(), BaselineError> {
+        let mut json = serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string());
+        json.push('\n');
+        tokio::fs::write(path, json)
+            .await
+            .map_err(|source| BaselineError::WriteFile {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    /// Remove findings the baseline already knows about.
+    ///
+    /// A finding matches an entry with the same file and content hash
+    /// whose title is similar. Entries are only counted as stale when
+    /// their file is in `reviewed_files` — a diff review that doesn't
+    /// touch a file says nothing about that file's known findings.
+    pub fn suppress(
+        &self,
+        findings: Vec<Finding>,
+        file_contents: &IndexMap<String, String>,
+        reviewed_files: &HashSet<&str>,
+    ) -> Suppression {
+        let mut matched = vec![false; self.entries.len()];
+        let mut kept = Vec::with_capacity(findings.len());
+        let mut suppressed = 0;
+
+        for f in findings {
+            let hash = content_hash(&f, file_contents);
+            let hit = self.entries.iter().position(|e| {
+                e.file == f.file && e.content_hash == hash && titles_match(&e.title, &f.title)
+            });
+            match hit {
+                Some(i) => {
+                    matched[i] = true;
+                    suppressed += 1;
+                }
+                None => kept.push(f),
+            }
+        }
+
+        let stale = self
+            .entries
+            .iter()
+            .zip(&matched)
+            .filter(|(e, hit)| !**hit && reviewed_files.contains(e.file.as_str()))
+            .count();
+
+        Suppression {
+            findings: kept,
+            suppressed,
+            stale,
+        }
+    }
+}
+
+/// Returns `true` if two titles describe the same issue.
+pub(crate) fn titles_match(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim()) || titles_similar(a, b)
+}
+
+/// Hash the whitespace-normalized lines a finding covers.
+pub(crate) fn content_hash(f: &Finding, file_contents: &IndexMap<String, String>) -> String {
+    let end = f.end_line.unwrap_or(f.line).max(f.line) as usize;
+    let start = f.line.max(1) as usize;
+    let lines = file_contents
+        .get(&f.file)
+        .map(|content| {
+            content
+                .lines()
+                .skip(start - 1)
+                .take(end + 1 - start)
+                .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+    hex::encode(Sha256::digest(lines.as_bytes()))[..16].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::Severity;
+
+    fn finding(line: u32, title: &str) -> Finding {
+        Finding {
+            file: "src/app.rs".into(),
+            line,
+            end_line: None,
+            severity: Severity::Warning,
+            title: title.into(),
+            message: "Details".into(),
+            suggestion: None,
+            agent: "backend".into(),
+            fix: None,
+            served_by: None,
+        }
+    }
+
+    fn contents(content: &str) -> IndexMap<String, String> {
+        IndexMap::from([("src/app.rs".to_string(), content.to_string())])
+    }
+
+    fn reviewed() -> HashSet<&'static str> {
+        HashSet::from(["src/app.rs"])
+    }
+
+    const BEFORE: &str = "fn a() {}\nlet x = y.unwrap();\nfn b() {}\n";
+    const AFTER: &str = "// new\n// lines\nfn a() {}\n  let x =  y.unwrap();\nfn b() {}\n";
+
+    #[test]
+    fn suppresses_known_finding_after_line_shift() {
+        let baseline =
+            Baseline::from_findings(&[finding(2, "Unchecked unwrap call")], &contents(BEFORE));
+        let result = baseline.suppress(
+            vec![finding(4, "Unchecked unwrap"), finding(5, "Other issue")],
+            &contents(AFTER),
+            &reviewed(),
+        );
+        assert_eq!(result.suppressed, 1);
+        assert_eq!(result.stale, 0);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.findings[0].title, "Other issue");
+    }
+
+    #[test]
+    fn changed_content_or_title_is_not_suppressed() {
+        let baseline =
+            Baseline::from_findings(&[finding(2, "Unchecked unwrap")], &contents(BEFORE));
+
+        let edited = "fn a() {}\nlet x = y.expect(\"x\");\nfn b() {}\n";
+        let result = baseline.suppress(
+            vec![finding(2, "Unchecked unwrap")],
+            &contents(edited),
+            &reviewed(),
+        );
+        assert_eq!(result.suppressed, 0);
+        assert_eq!(result.stale, 1);
+
+        let result = baseline.suppress(
+            vec![finding(2, "SQL injection risk")],
+            &contents(BEFORE),
+            &reviewed(),
+        );
+        assert_eq!(result.suppressed, 0);
+    }
+
+    #[test]
+    fn stale_only_counts_reviewed_files() {
+        let baseline =
+            Baseline::from_findings(&[finding(2, "Unchecked unwrap")], &contents(BEFORE));
+        let result = baseline.suppress(vec![], &contents(BEFORE), &HashSet::new());
+        assert_eq!(result.stale, 0);
+        let result = baseline.suppress(vec![], &contents(BEFORE), &reviewed());
+        assert_eq!(result.stale, 1);
+    }
+
+    #[test]
+    fn entries_are_sorted_and_deduplicated() {
+        let baseline = Baseline::from_findings(
+            &[finding(3, "B"), finding(1, "A"), finding(3, "B")],
+            &contents(BEFORE),
+        );
+        let lines: Vec<u32> = baseline.entries.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![1, 3]);
+    }
+
+    #[tokio::test]
+    async fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(crate::constants::BASELINE_FILENAME);
+        let baseline =
+            Baseline::from_findings(&[finding(2, "Unchecked unwrap")], &contents(BEFORE));
+        baseline.save(&path).await.unwrap();
+        assert_eq!(Baseline::load(&path).await.unwrap(), baseline);
+
+        std::fs::write(&path, r#"{"version": 99, "entries": []}"#).unwrap();
+        assert!(matches!(
+            Baseline::load(&path).await,
+            Err(BaselineError::UnsupportedVersion(99))
+        ));
+    }
+}
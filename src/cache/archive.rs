@@ -0,0 +1,295 @@
+//! Export and import of one review scope's cache as a `.tar.gz`.
+//!
+//! Lets a pipeline hand the cache for a branch from one run to the next
+//! as a build artifact. An archive holds the sidecars recorded for the
+//! scope plus the entries they point at, as flat `<name>.json` and
+//! `<name>.meta` files.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use thiserror::Error;
+
+use super::store::Sidecar;
+
+/// Errors from exporting or importing a cache archive.
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("failed to write cache archive {path}: {source}")]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to read cache archive {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("cache archive contains '{0}', which is not a cache entry or sidecar")]
+    UnexpectedEntry(String),
+
+    #[error("cache directory could not be determined")]
+    NoDirectory,
+
+    #[error("cache archive task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+}
+
+/// What an export or import transferred.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    /// Cached findings (`.json` files).
+    pub entries: usize,
+    /// Sidecars (`.meta` files).
+    pub sidecars: usize,
+}
+
+impl ArchiveSummary {
+    fn count(&mut self, name: &str) {
+        if name.ends_with(".meta") {
+            self.sidecars += 1;
+        } else {
+            self.entries += 1;
+        }
+    }
+}
+
+/// Write the sidecars recorded for `scope` in `cache_dir`, and the
+/// entries they point at, to a `.tar.gz` at `output`.
+pub async fn export_scope(
+    cache_dir: &Path,
+    scope: &str,
+    output: &Path,
+) -> Result<ArchiveSummary, ArchiveError> {
+    let cache_dir = cache_dir.to_path_buf();
+    let scope = scope.to_string();
+    let output = output.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let write_err = |source| ArchiveError::Write {
+            path: output.clone(),
+            source,
+        };
+        let names = scope_files(&cache_dir, &scope).map_err(|source| ArchiveError::Read {
+            path: cache_dir.clone(),
+            source,
+        })?;
+
+        let file = File::create(&output).map_err(write_err)?;
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        let mut summary = ArchiveSummary::default();
+        for name in &names {
+            builder
+                .append_path_with_name(cache_dir.join(name), name)
+                .map_err(write_err)?;
+            summary.count(name);
+        }
+        builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map_err(write_err)?;
+        Ok(summary)
+    })
+    .await?
+}
+
+/// Unpack a cache archive from `input` into `cache_dir`.
+///
+/// Entries are content-addressed, so existing ones are identical and
+/// simply rewritten; sidecars replace the local ones for their scope.
+pub async fn import(cache_dir: &Path, input: &Path) -> Result<ArchiveSummary, ArchiveError> {
+    let cache_dir = cache_dir.to_path_buf();
+    let input = input.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let read_err = |source| ArchiveError::Read {
+            path: input.clone(),
+            source,
+        };
+        let write_err = |source| ArchiveError::Write {
+            path: cache_dir.clone(),
+            source,
+        };
+        let file = File::open(&input).map_err(read_err)?;
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+        std::fs::create_dir_all(&cache_dir).map_err(write_err)?;
+
+        let mut summary = ArchiveSummary::default();
+        for entry in archive.entries().map_err(read_err)? {
+            let mut entry = entry.map_err(read_err)?;
+            let path = entry.path().map_err(read_err)?.into_owned();
+            let name = cache_file_name(&path)
+                .filter(|_| entry.header().entry_type().is_file())
+                .ok_or_else(|| ArchiveError::UnexpectedEntry(path.display().to_string()))?;
+
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content).map_err(read_err)?;
+            std::fs::write(cache_dir.join(&name), content).map_err(write_err)?;
+            summary.count(&name);
+        }
+        Ok(summary)
+    })
+    .await?
+}
+
+/// Names of the sidecars for `scope` and the entries they point at.
+fn scope_files(cache_dir: &Path, scope: &str) -> std::io::Result<BTreeSet<String>> {
+    let mut names = BTreeSet::new();
+    if !cache_dir.exists() {
+        return Ok(names);
+    }
+    for dir_entry in std::fs::read_dir(cache_dir)? {
+        let path = dir_entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("meta") {
+            continue;
+        }
+        // Legacy plain-text sidecars don't record their scope.
+        let Some(sidecar) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| Sidecar::parse(&content))
+        else {
+            continue;
+        };
+        if sidecar.scope != scope {
+            continue;
+        }
+        let entry = format!("{}.json", sidecar.cache_key);
+        if cache_dir.join(&entry).is_file() {
+            names.insert(entry);
+        }
+        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            names.insert(name.to_string());
+        }
+    }
+    Ok(names)
+}
+
+/// The file name an archive path unpacks to, if it is a plain
+/// `.json` or `.meta` file with no directory components.
+fn cache_file_name(path: &Path) -> Option<String> {
+    let mut components = path.components();
+    let (Some(Component::Normal(name)), None) = (components.next(), components.next()) else {
+        return None;
+    };
+    let name = name.to_str()?;
+    (name.ends_with(".json") || name.ends_with(".meta")).then(|| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::store::FileStore;
+    use crate::models::finding::{Finding, Severity};
+
+    fn findings(title: &str) -> Vec<Finding> {
+        vec![Finding {
+            file: "a.rs".into(),
+            line: 1,
+            end_line: None,
+            severity: Severity::Warning,
+            title: title.into(),
+            message: "Details".into(),
+            suggestion: None,
+            agent: "backend".into(),
+            fix: None,
+            served_by: None,
+        }]
+    }
+
+    #[tokio::test]
+    async fn export_and_import_one_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = FileStore::new_with_dir(dir.path().join("source"));
+        source.put("key-main", &findings("On main")).await;
+        source
+            .put_sidecar("a.rs", "backend", "model", "key-main", "main")
+            .await;
+        source.put("key-feature", &findings("On feature")).await;
+        source
+            .put_sidecar("a.rs", "backend", "model", "key-feature", "feature")
+            .await;
+
+        let archive = dir.path().join("cache.tar.gz");
+        let exported = export_scope(&dir.path().join("source"), "feature", &archive)
+            .await
+            .unwrap();
+        assert_eq!(
+            exported,
+            ArchiveSummary {
+                entries: 1,
+                sidecars: 1
+            }
+        );
+
+        let target_dir = dir.path().join("target");
+        let imported = import(&target_dir, &archive).await.unwrap();
+        assert_eq!(imported, exported);
+
+        let target = FileStore::new_with_dir(target_dir);
+        assert!(target.get("key-main").await.is_none());
+        let prior = target
+            .get_previous("a.rs", "backend", "model", "key-new", "feature")
+            .await
+            .unwrap();
+        assert_eq!(prior[0].title, "On feature");
+    }
+
+    #[tokio::test]
+    async fn export_of_unknown_scope_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("cache.tar.gz");
+        let summary = export_scope(&dir.path().join("missing"), "main", &archive)
+            .await
+            .unwrap();
+        assert_eq!(summary, ArchiveSummary::default());
+        assert_eq!(
+            import(&dir.path().join("target"), &archive).await.unwrap(),
+            ArchiveSummary::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn import_rejects_paths_outside_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = dir.path().join("evil.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&archive).unwrap(),
+            Compression::fast(),
+        ));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(2);
+        header.set_mode(0o644);
+        // `append_data` refuses `..`, so write the name into the header directly.
+        header.as_gnu_mut().unwrap().name[..10].copy_from_slice(b"../x.json\0");
+        header.set_cksum();
+        builder.append(&header, &b"[]"[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let err = import(&dir.path().join("cache"), &archive)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ArchiveError::UnexpectedEntry(_)));
+        assert!(!dir.path().join("x.json").exists());
+    }
+
+    #[test]
+    fn cache_file_names_are_flat() {
+        assert_eq!(
+            cache_file_name(Path::new("abc.json")),
+            Some("abc.json".into())
+        );
+        assert_eq!(
+            cache_file_name(Path::new("abc.meta")),
+            Some("abc.meta".into())
+        );
+        assert!(cache_file_name(Path::new("batches/x.json")).is_none());
+        assert!(cache_file_name(Path::new("../abc.json")).is_none());
+        assert!(cache_file_name(Path::new("/abc.json")).is_none());
+        assert!(cache_file_name(Path::new("notes.txt")).is_none());
+    }
+}
@@ -0,0 +1,186 @@
+//! Human-readable cache limits: ages like `30d` and sizes like `500MB`.
+//!
+//! Both types parse from strings so they work unchanged as TOML values
+//! in `[cache]` and as CLI flag values.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// A duration written as a number and a unit: `s`, `m`, `h`, `d` or `w`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Age(Duration);
+
+impl Age {
+    pub fn duration(self) -> Duration {
+        self.0
+    }
+}
+
+impl FromStr for Age {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, unit) = split_number(s)?;
+        let secs_per_unit = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            "w" => 7 * 24 * 60 * 60,
+            _ => {
+                return Err(format!(
+                    "invalid age '{s}': expected a number followed by s, m, h, d or w (e.g. 30d)"
+                ));
+            }
+        };
+        value
+            .checked_mul(secs_per_unit)
+            .map(|secs| Self(Duration::from_secs(secs)))
+            .ok_or_else(|| format!("invalid age '{s}': too large"))
+    }
+}
+
+impl fmt::Display for Age {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let (value, unit) = [
+            (7 * 24 * 60 * 60, "w"),
+            (24 * 60 * 60, "d"),
+            (60 * 60, "h"),
+            (60, "m"),
+        ]
+        .into_iter()
+        .find(|(n, _)| secs > 0 && secs % n == 0)
+        .map_or((secs, "s"), |(n, unit)| (secs / n, unit));
+        write!(f, "{value}{unit}")
+    }
+}
+
+impl TryFrom<String> for Age {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Age> for String {
+    fn from(age: Age) -> Self {
+        age.to_string()
+    }
+}
+
+/// A size in bytes, written with an optional binary unit: `B`, `KB`,
+/// `MB` or `GB` (`KiB`, `MiB` and `GiB` are accepted too).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, unit) = split_number(s)?;
+        let multiplier = match unit.to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" | "KIB" => 1 << 10,
+            "M" | "MB" | "MIB" => 1 << 20,
+            "G" | "GB" | "GIB" => 1 << 30,
+            _ => {
+                return Err(format!(
+                    "invalid size '{s}': expected a number optionally followed by KB, MB or GB (e.g. 500MB)"
+                ));
+            }
+        };
+        value
+            .checked_mul(multiplier)
+            .map(Self)
+            .ok_or_else(|| format!("invalid size '{s}': too large"))
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (value, unit) = [(1 << 30, "GB"), (1 << 20, "MB"), (1 << 10, "KB")]
+            .into_iter()
+            .find(|(n, _)| self.0 > 0 && self.0 % n == 0)
+            .map_or((self.0, "B"), |(n, unit)| (self.0 / n, unit));
+        write!(f, "{value}{unit}")
+    }
+}
+
+impl TryFrom<String> for ByteSize {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ByteSize> for String {
+    fn from(size: ByteSize) -> Self {
+        size.to_string()
+    }
+}
+
+/// Split `"30d"` into `(30, "d")`, allowing whitespace between the two.
+fn split_number(s: &str) -> Result<(u64, &str), String> {
+    let s = s.trim();
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let value = s[..digits]
+        .parse()
+        .map_err(|_| format!("invalid value '{s}': expected a leading number"))?;
+    Ok((value, s[digits..].trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!(
+            "30d".parse::<Age>().unwrap().duration().as_secs(),
+            2_592_000
+        );
+        assert_eq!("12 h".parse::<Age>().unwrap().duration().as_secs(), 43_200);
+        assert_eq!("2w".parse::<Age>().unwrap().to_string(), "2w");
+        assert_eq!("90m".parse::<Age>().unwrap().to_string(), "90m");
+        assert!("30".parse::<Age>().is_err());
+        assert!("d".parse::<Age>().is_err());
+        assert!("30y".parse::<Age>().is_err());
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!("500MB".parse::<ByteSize>().unwrap().bytes(), 500 << 20);
+        assert_eq!("1 GiB".parse::<ByteSize>().unwrap().bytes(), 1 << 30);
+        assert_eq!("4096".parse::<ByteSize>().unwrap().to_string(), "4KB");
+        assert_eq!("10kb".parse::<ByteSize>().unwrap().bytes(), 10 << 10);
+        assert!("lots".parse::<ByteSize>().is_err());
+        assert!("5TB".parse::<ByteSize>().is_err());
+    }
+
+    #[test]
+    fn deserializes_from_toml_strings() {
+        #[derive(Deserialize)]
+        struct Limits {
+            ttl: Age,
+            max_size: ByteSize,
+        }
+        let limits: Limits = toml::from_str("ttl = \"7d\"\nmax_size = \"1GB\"").unwrap();
+        assert_eq!(limits.ttl.to_string(), "1w");
+        assert_eq!(limits.max_size.bytes(), 1 << 30);
+        assert!(toml::from_str::<Limits>("ttl = \"soon\"\nmax_size = \"1GB\"").is_err());
+    }
+}
@@ -10,29 +10,170 @@
 //! A sidecar `.meta` file per file×agent×model triple tracks the
 //! most recent cache key so that prior findings can be retrieved
 //! after a content change invalidates the cache.
+//!
+//! The cache can be bounded by an entry TTL and a maximum size;
+//! [`CacheEngine::prune`] enforces both, evicting least recently used
+//! entries first.
+//!
+//! Storage sits behind the [`CacheBackend`] trait. The local
+//! [`store::FileStore`] is always used; a shared backend such as
+//! [`remote::RemoteStore`] can be layered behind it so CI runners
+//! reuse each other's results. Keys are content hashes, so sharing
+//! entries between machines is safe.
+//!
+//! Keys are built from [`KeyComponents`] — the diff, file content,
+//! agent and model rather than the whole prompt — and the components
+//! are recorded in the sidecar so [`CacheEngine::explain`] can say why
+//! a task missed.
 
+pub mod archive;
+pub mod limits;
+pub mod remote;
+pub(crate) mod sigv4;
 pub mod store;
 
+use std::fmt;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
 use crate::models::finding::Finding;
 
-/// Compute a cache key from file content, agent config, and model name.
+/// Version of the cache key layout. Bumping it invalidates every entry,
+/// so change it whenever the components or their meaning change.
+pub const CACHE_SCHEMA_VERSION: u32 = 2;
+
+/// The named inputs a cache key is built from, each stored as a hash.
 ///
-/// Uses xxHash3-128 for speed — ~10× faster than SHA-256 on the 50KB+
-/// prompts that typically feed into this. The collision risk is acceptable
+/// Keys are derived from these semantic components rather than the full
+/// rendered prompt, so context that doesn't affect a file's review
+/// (such as a new commit elsewhere) leaves its key untouched. Recording
+/// the components in the sidecar lets [`CacheEngine::explain`] report
+/// which of them changed since the last review.
+///
+/// Values are hashed with xxHash3-128 for speed — ~10× faster than
+/// SHA-256 on large diffs and files. The collision risk is acceptable
 /// for content-addressable cache keys (not security-critical).
-pub fn cache_key(file_content: &str, agent_name: &str, model: &str) -> String {
-    let mut data = Vec::with_capacity(file_content.len() + agent_name.len() + model.len());
-    data.extend_from_slice(file_content.as_bytes());
-    data.extend_from_slice(agent_name.as_bytes());
-    data.extend_from_slice(model.as_bytes());
-    let hash = xxhash_rust::xxh3::xxh3_128(&data);
-    format!("{hash:032x}")
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KeyComponents(IndexMap<String, String>);
+
+impl KeyComponents {
+    /// Start a key at the current [`CACHE_SCHEMA_VERSION`].
+    pub fn new() -> Self {
+        Self(IndexMap::new()).with("schema", &CACHE_SCHEMA_VERSION.to_string())
+    }
+
+    /// Add a component, replacing any earlier value with the same name.
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.0.insert(name.to_string(), hash(value.as_bytes()));
+        self
+    }
+
+    /// The cache key: a hash over every component name and value hash.
+    pub fn key(&self) -> String {
+        let data: String = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{name}={value}\n"))
+            .collect();
+        hash(data.as_bytes())
+    }
+
+    /// Names of the components that differ from `previous`, including
+    /// ones only present on one side.
+    pub fn changed_since(&self, previous: &KeyComponents) -> Vec<String> {
+        let mut changed: Vec<String> = self
+            .0
+            .iter()
+            .filter(|(name, value)| previous.0.get(*name) != Some(*value))
+            .map(|(name, _)| name.clone())
+            .collect();
+        changed.extend(
+            previous
+                .0
+                .keys()
+                .filter(|name| !self.0.contains_key(*name))
+                .cloned(),
+        );
+        changed
+    }
+}
+
+impl Default for KeyComponents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn hash(data: &[u8]) -> String {
+    format!("{:032x}", xxhash_rust::xxh3::xxh3_128(data))
+}
+
+/// Why a task would or wouldn't be served from the cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheExplanation {
+    /// The key is cached; the task would not call the LLM.
+    Hit,
+    /// Caching is turned off for this run.
+    Disabled,
+    /// The tuple has never been reviewed in this scope.
+    FirstReview,
+    /// These key components changed since the last review.
+    Changed(Vec<String>),
+    /// The key is unchanged but its entry was evicted or cleared.
+    Evicted,
+    /// The last review predates recorded key components.
+    Unknown,
+}
+
+impl fmt::Display for CacheExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hit => write!(f, "hit"),
+            Self::Disabled => write!(f, "miss: caching is disabled"),
+            Self::FirstReview => write!(f, "miss: first review in this scope"),
+            Self::Changed(names) => write!(f, "miss: changed {}", names.join(", ")),
+            Self::Evicted => write!(f, "miss: entry was evicted or cleared"),
+            Self::Unknown => write!(f, "miss: previous key predates component tracking"),
+        }
+    }
+}
+
+/// Storage for cached findings and the sidecars that track the latest
+/// cache key per file×agent×model×scope tuple.
+///
+/// Implementations are best-effort: failures read as misses and writes
+/// are dropped, since a cache must never fail a review.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Look up cached findings by key.
+    async fn get(&self, key: &str) -> Option<Vec<Finding>>;
+
+    /// Store findings by key.
+    async fn put(&self, key: &str, findings: &[Finding]);
+
+    /// Read the sidecar for a tuple.
+    async fn read_sidecar(
+        &self,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+    ) -> Option<store::Sidecar>;
+
+    /// Write (or overwrite) the sidecar for the tuple it describes.
+    async fn write_sidecar(&self, sidecar: &store::Sidecar);
 }
 
 /// The cache engine for review results.
 pub struct CacheEngine {
     enabled: bool,
     store: store::FileStore,
+    /// Shared backend consulted on local misses and written through.
+    shared: Option<Box<dyn CacheBackend>>,
 }
 
 impl CacheEngine {
@@ -41,23 +182,47 @@ impl CacheEngine {
         Self {
             enabled,
             store: store::FileStore::new(),
+            shared: None,
         }
     }
 
     /// Create a cache engine with a specific cache directory (useful for testing).
-    pub fn new_with_dir(cache_dir: std::path::PathBuf) -> Self {
-        Self {
-            enabled: true,
-            store: store::FileStore::new_with_dir(cache_dir),
-        }
+    pub fn new_with_dir(cache_dir: PathBuf) -> Self {
+        Self::new(true).with_dir(cache_dir)
+    }
+
+    /// Keep the local cache in `cache_dir` instead of the default
+    /// directory under the global config dir.
+    pub fn with_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.store = store::FileStore::new_with_dir(cache_dir);
+        self
+    }
+
+    /// Share results through `backend`: local misses are looked up
+    /// there, and every write goes to both.
+    pub fn with_shared(mut self, backend: Box<dyn CacheBackend>) -> Self {
+        self.shared = Some(backend);
+        self
+    }
+
+    /// Whether results are read from and written to the cache.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
     }
 
     /// Look up cached findings.
+    ///
+    /// Hits from the shared backend are copied into the local cache.
     pub async fn get(&self, key: &str) -> Option<Vec<Finding>> {
         if !self.enabled {
             return None;
         }
-        self.store.get(key).await
+        if let Some(findings) = self.store.get(key).await {
+            return Some(findings);
+        }
+        let findings = self.shared.as_ref()?.get(key).await?;
+        self.store.put(key, &findings).await;
+        Some(findings)
     }
 
     /// Store findings in the cache.
@@ -66,6 +231,9 @@ impl CacheEngine {
             return;
         }
         self.store.put(key, findings).await;
+        if let Some(shared) = &self.shared {
+            shared.put(key, findings).await;
+        }
     }
 
     /// Write the sidecar that maps a file×agent×model×scope tuple to its
@@ -78,19 +246,103 @@ impl CacheEngine {
         cache_key: &str,
         review_scope: &str,
     ) {
+        let sidecar = store::Sidecar::new(file_path, agent_name, model, cache_key, review_scope);
+        self.write_sidecar(&sidecar).await;
+    }
+
+    /// Like [`put_sidecar`](Self::put_sidecar), but also records the
+    /// components the key was built from so later misses can be
+    /// explained.
+    pub async fn record_key(
+        &self,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+        components: &KeyComponents,
+    ) {
+        let sidecar = store::Sidecar {
+            components: Some(components.clone()),
+            ..store::Sidecar::new(
+                file_path,
+                agent_name,
+                model,
+                &components.key(),
+                review_scope,
+            )
+        };
+        self.write_sidecar(&sidecar).await;
+    }
+
+    async fn write_sidecar(&self, sidecar: &store::Sidecar) {
         if !self.enabled {
             return;
         }
+        self.store.write_sidecar(sidecar).await;
+        if let Some(shared) = &self.shared {
+            shared.write_sidecar(sidecar).await;
+        }
+    }
+
+    /// Read the latest sidecar for a tuple, preferring the shared one
+    /// since another runner may have reviewed the branch more recently.
+    async fn read_sidecar(
+        &self,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+    ) -> Option<store::Sidecar> {
+        if let Some(shared) = &self.shared
+            && let Some(sidecar) = shared
+                .read_sidecar(file_path, agent_name, model, review_scope)
+                .await
+        {
+            return Some(sidecar);
+        }
         self.store
-            .put_sidecar(file_path, agent_name, model, cache_key, review_scope)
-            .await;
+            .read_sidecar(file_path, agent_name, model, review_scope)
+            .await
+    }
+
+    /// Explain whether a task with key `current` would be served from
+    /// the cache and, if not, what changed since the tuple's last review.
+    pub async fn explain(
+        &self,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+        current: &KeyComponents,
+    ) -> CacheExplanation {
+        if !self.enabled {
+            return CacheExplanation::Disabled;
+        }
+        if self.get(&current.key()).await.is_some() {
+            return CacheExplanation::Hit;
+        }
+        let Some(previous) = self
+            .read_sidecar(file_path, agent_name, model, review_scope)
+            .await
+        else {
+            return CacheExplanation::FirstReview;
+        };
+        if previous.cache_key == current.key() {
+            return CacheExplanation::Evicted;
+        }
+        match previous.components {
+            Some(components) => CacheExplanation::Changed(current.changed_since(&components)),
+            None => CacheExplanation::Unknown,
+        }
     }
 
     /// Retrieve findings from the *previous* review of a file×agent×model×scope
     /// tuple, if the cache key has changed (content invalidation).
     ///
     /// Returns `None` when caching is disabled, on first run, or when
-    /// the cache key hasn't changed (pure hit).
+    /// the cache key hasn't changed (pure hit). The shared sidecar wins
+    /// over the local one, since another runner may have reviewed the
+    /// branch more recently.
     pub async fn get_previous(
         &self,
         file_path: &str,
@@ -102,15 +354,14 @@ impl CacheEngine {
         if !self.enabled {
             return None;
         }
-        self.store
-            .get_previous(
-                file_path,
-                agent_name,
-                model,
-                current_cache_key,
-                review_scope,
-            )
-            .await
+        let previous_key = self
+            .read_sidecar(file_path, agent_name, model, review_scope)
+            .await?
+            .cache_key;
+        if previous_key == current_cache_key || previous_key.is_empty() {
+            return None;
+        }
+        self.get(&previous_key).await
     }
 
     /// Remove stale `.meta` sidecar files older than the given duration.
@@ -123,6 +374,24 @@ impl CacheEngine {
         self.store.cleanup_stale_sidecars(max_age).await
     }
 
+    /// Evict entries unused for longer than `ttl`, then least recently
+    /// used entries until the cache fits in `max_size`.
+    ///
+    /// Like [`clear`](Self::clear) and [`stats`](Self::stats), this only
+    /// covers the local cache; a shared backend manages its own lifecycle.
+    pub async fn prune(
+        &self,
+        ttl: Option<limits::Age>,
+        max_size: Option<limits::ByteSize>,
+    ) -> Result<store::CacheStats, std::io::Error> {
+        self.store
+            .prune(
+                ttl.map(limits::Age::duration),
+                max_size.map(limits::ByteSize::bytes),
+            )
+            .await
+    }
+
     /// Remove all cached entries.
     pub async fn clear(&self) -> Result<store::CacheStats, std::io::Error> {
         self.store.clear().await
@@ -133,8 +402,28 @@ impl CacheEngine {
         self.store.stats().await
     }
 
-    /// Return the cache directory path.
-    pub fn path(&self) -> Option<&std::path::PathBuf> {
+    /// Export the local cache for one review scope to a `.tar.gz`.
+    pub async fn export_scope(
+        &self,
+        scope: &str,
+        output: &std::path::Path,
+    ) -> Result<archive::ArchiveSummary, archive::ArchiveError> {
+        let dir = self.path().ok_or(archive::ArchiveError::NoDirectory)?;
+        archive::export_scope(dir, scope, output).await
+    }
+
+    /// Import an archive written by [`export_scope`](Self::export_scope)
+    /// into the local cache.
+    pub async fn import(
+        &self,
+        input: &std::path::Path,
+    ) -> Result<archive::ArchiveSummary, archive::ArchiveError> {
+        let dir = self.path().ok_or(archive::ArchiveError::NoDirectory)?;
+        archive::import(dir, input).await
+    }
+
+    /// Return the local cache directory path.
+    pub fn path(&self) -> Option<&PathBuf> {
         self.store.path()
     }
 }
@@ -143,25 +432,60 @@ impl CacheEngine {
 mod tests {
     use super::*;
 
+    fn components(diff: &str, agent: &str) -> KeyComponents {
+        KeyComponents::new()
+            .with("diff", diff)
+            .with("agent", agent)
+            .with("model", "model")
+    }
+
     #[test]
     fn cache_key_deterministic() {
-        let k1 = cache_key("content", "agent", "model");
-        let k2 = cache_key("content", "agent", "model");
-        assert_eq!(k1, k2);
+        assert_eq!(
+            components("diff", "agent").key(),
+            components("diff", "agent").key()
+        );
     }
 
     #[test]
     fn cache_key_varies_with_content() {
-        let k1 = cache_key("content1", "agent", "model");
-        let k2 = cache_key("content2", "agent", "model");
-        assert_ne!(k1, k2);
+        assert_ne!(
+            components("diff1", "agent").key(),
+            components("diff2", "agent").key()
+        );
     }
 
     #[test]
     fn cache_key_varies_with_agent() {
-        let k1 = cache_key("content", "agent1", "model");
-        let k2 = cache_key("content", "agent2", "model");
-        assert_ne!(k1, k2);
+        assert_ne!(
+            components("diff", "agent1").key(),
+            components("diff", "agent2").key()
+        );
+    }
+
+    #[test]
+    fn cache_key_includes_component_names() {
+        let a = KeyComponents::new().with("diff", "x");
+        let b = KeyComponents::new().with("file", "x");
+        assert_ne!(a.key(), b.key());
+    }
+
+    #[test]
+    fn changed_since_names_differing_components() {
+        let before = components("diff", "agent").with("commit_log", "log");
+        let after = components("diff2", "agent");
+        assert_eq!(after.changed_since(&before), vec!["diff", "commit_log"]);
+        assert!(after.changed_since(&after).is_empty());
+    }
+
+    #[test]
+    fn components_roundtrip_through_sidecar_json() {
+        let sidecar = store::Sidecar {
+            components: Some(components("diff", "agent")),
+            ..store::Sidecar::new("f.rs", "agent", "model", "key", "main")
+        };
+        let json = serde_json::to_string(&sidecar).unwrap();
+        assert_eq!(store::Sidecar::parse(&json), Some(sidecar));
     }
 
     #[tokio::test]
@@ -195,4 +519,100 @@ mod tests {
             0
         );
     }
+
+    // ── Shared backend tests ─────────────────────────────────────────
+
+    fn sample_findings() -> Vec<Finding> {
+        vec![Finding {
+            file: "f.rs".into(),
+            line: 1,
+            end_line: None,
+            severity: crate::models::finding::Severity::Info,
+            title: "Shared".into(),
+            message: "Reviewed elsewhere".into(),
+            suggestion: None,
+            agent: "backend".into(),
+            fix: None,
+            served_by: None,
+        }]
+    }
+
+    /// Two engines with separate local caches sharing one backend, as
+    /// two CI runners would.
+    fn runners(dir: &std::path::Path) -> (CacheEngine, CacheEngine) {
+        let shared = || Box::new(store::FileStore::new_with_dir(dir.join("shared")));
+        (
+            CacheEngine::new_with_dir(dir.join("a")).with_shared(shared()),
+            CacheEngine::new_with_dir(dir.join("b")).with_shared(shared()),
+        )
+    }
+
+    #[tokio::test]
+    async fn shared_backend_serves_other_runners() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = runners(dir.path());
+
+        a.put("key", &sample_findings()).await;
+        assert_eq!(b.get("key").await.unwrap()[0].title, "Shared");
+        // The hit was copied into runner B's local cache.
+        let local_b = store::FileStore::new_with_dir(dir.path().join("b"));
+        assert!(local_b.get("key").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn shared_sidecars_provide_prior_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = runners(dir.path());
+
+        a.put("key-1", &sample_findings()).await;
+        a.put_sidecar("f.rs", "backend", "model", "key-1", "main")
+            .await;
+        let prior = b
+            .get_previous("f.rs", "backend", "model", "key-2", "main")
+            .await;
+        assert_eq!(prior.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn explain_reports_why_a_task_missed() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = CacheEngine::new_with_dir(dir.path().to_path_buf());
+        let explain = |c: KeyComponents| {
+            let engine = &engine;
+            async move { engine.explain("f.rs", "backend", "model", "main", &c).await }
+        };
+
+        let first = components("diff", "backend");
+        assert_eq!(explain(first.clone()).await, CacheExplanation::FirstReview);
+
+        engine.put(&first.key(), &sample_findings()).await;
+        engine
+            .record_key("f.rs", "backend", "model", "main", &first)
+            .await;
+        assert_eq!(explain(first.clone()).await, CacheExplanation::Hit);
+        assert_eq!(
+            explain(components("diff2", "backend")).await,
+            CacheExplanation::Changed(vec!["diff".to_string()])
+        );
+
+        engine.clear().await.unwrap();
+        engine
+            .record_key("f.rs", "backend", "model", "main", &first)
+            .await;
+        assert_eq!(explain(first).await, CacheExplanation::Evicted);
+
+        engine
+            .put_sidecar("f.rs", "backend", "model", "legacy", "main")
+            .await;
+        assert_eq!(
+            explain(components("diff", "backend")).await,
+            CacheExplanation::Unknown
+        );
+        assert_eq!(
+            CacheEngine::new(false)
+                .explain("f.rs", "backend", "model", "main", &KeyComponents::new())
+                .await,
+            CacheExplanation::Disabled
+        );
+    }
 }
@@ -0,0 +1,359 @@
+//! Shared cache backend over HTTP.
+//!
+//! Stores each entry as an object named `<cache-key>.json` and each
+//! sidecar as `<lookup-key>.meta` under a base URL, using plain `GET`
+//! and `PUT`. This works with any key-value HTTP server (an nginx
+//! WebDAV location, a CI cache service) and, with
+//! [`RemoteAuth::S3`], with S3-compatible object storage such as AWS
+//! S3 or MinIO in path style (`https://host/bucket/prefix/`).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use async_trait::async_trait;
+use reqwest::{Method, StatusCode, Url};
+use thiserror::Error;
+
+use super::CacheBackend;
+use super::sigv4;
+use super::store::{Sidecar, lookup_key};
+use crate::models::finding::Finding;
+
+pub use super::sigv4::Credentials;
+
+/// Errors setting up a remote cache.
+#[derive(Error, Debug)]
+pub enum RemoteCacheError {
+    #[error("invalid remote cache URL '{url}': {reason}")]
+    InvalidUrl { url: String, reason: String },
+
+    #[error("failed to create HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+}
+
+/// How requests to the remote cache authenticate.
+#[derive(Debug, Clone)]
+pub enum RemoteAuth {
+    /// No authentication, e.g. a server on a private network.
+    None,
+    /// `Authorization: Bearer <token>`.
+    Bearer(String),
+    /// AWS Signature Version 4.
+    S3(Credentials),
+}
+
+/// Cache backend that reads and writes objects over HTTP.
+pub struct RemoteStore {
+    client: reqwest::Client,
+    /// Base URL; always ends with `/` so object names join below it.
+    base_url: Url,
+    auth: RemoteAuth,
+    /// Set once a failure has been reported, to warn only once per run.
+    warned: AtomicBool,
+}
+
+impl RemoteStore {
+    /// Create a store for objects under `url`.
+    pub fn new(url: &str, auth: RemoteAuth) -> Result<Self, RemoteCacheError> {
+        let invalid = |reason: String| RemoteCacheError::InvalidUrl {
+            url: url.to_string(),
+            reason,
+        };
+        let mut base_url = Url::parse(url).map_err(|e| invalid(e.to_string()))?;
+        if !matches!(base_url.scheme(), "http" | "https") {
+            return Err(invalid("expected an http:// or https:// URL".to_string()));
+        }
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        Ok(Self {
+            client: crate::http::build_client()?,
+            base_url,
+            auth,
+            warned: AtomicBool::new(false),
+        })
+    }
+
+    /// Fetch an object; `None` when it doesn't exist or can't be read.
+    async fn read(&self, name: &str) -> Option<Vec<u8>> {
+        let response = self.send(Method::GET, name, Vec::new()).await?;
+        match response.status() {
+            StatusCode::OK => match response.bytes().await {
+                Ok(body) => Some(body.to_vec()),
+                Err(e) => {
+                    self.warn(&e.to_string());
+                    None
+                }
+            },
+            // S3 answers 403 instead of 404 without list permission.
+            StatusCode::NOT_FOUND | StatusCode::FORBIDDEN => None,
+            status => {
+                self.warn(&format!("GET {name} returned {status}"));
+                None
+            }
+        }
+    }
+
+    /// Store an object, replacing any existing one.
+    async fn write(&self, name: &str, body: Vec<u8>) {
+        let Some(response) = self.send(Method::PUT, name, body).await else {
+            return;
+        };
+        if !response.status().is_success() {
+            self.warn(&format!("PUT {name} returned {}", response.status()));
+        }
+    }
+
+    async fn send(&self, method: Method, name: &str, body: Vec<u8>) -> Option<reqwest::Response> {
+        let url = self.base_url.join(name).ok()?;
+        let mut request = self.client.request(method.clone(), url.clone());
+        match &self.auth {
+            RemoteAuth::None => {}
+            RemoteAuth::Bearer(token) => request = request.bearer_auth(token),
+            RemoteAuth::S3(credentials) => {
+                for (name, value) in s3_headers(credentials, &method, &url, &body) {
+                    request = request.header(name, value);
+                }
+            }
+        }
+        match request.body(body).send().await {
+            Ok(response) => Some(response),
+            Err(e) => {
+                self.warn(&e.to_string());
+                None
+            }
+        }
+    }
+
+    fn warn(&self, message: &str) {
+        if !self.warned.swap(true, Ordering::Relaxed) {
+            eprintln!(
+                "Warning: remote cache unavailable, continuing with the local cache: {message}"
+            );
+        }
+    }
+}
+
+/// Headers that sign a request with AWS Signature Version 4.
+fn s3_headers(
+    credentials: &Credentials,
+    method: &Method,
+    url: &Url,
+    body: &[u8],
+) -> Vec<(&'static str, String)> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+    let amz_date = sigv4::amz_date(now);
+    let payload_hash = sigv4::payload_hash(body);
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+
+    let mut headers = vec![
+        ("host", host),
+        ("x-amz-content-sha256", payload_hash.clone()),
+        ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    let signed: Vec<(&str, &str)> = headers.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    let authorization = sigv4::authorization(
+        credentials,
+        method.as_str(),
+        url.path(),
+        &signed,
+        &payload_hash,
+        &amz_date,
+    );
+    // reqwest derives `Host` from the URL itself.
+    headers.retain(|(name, _)| *name != "host");
+    headers.push(("authorization", authorization));
+    headers
+}
+
+#[async_trait]
+impl CacheBackend for RemoteStore {
+    async fn get(&self, key: &str) -> Option<Vec<Finding>> {
+        let body = self.read(&format!("{key}.json")).await?;
+        serde_json::from_slice(&body).ok()
+    }
+
+    async fn put(&self, key: &str, findings: &[Finding]) {
+        let Ok(body) = serde_json::to_vec(findings) else {
+            return;
+        };
+        self.write(&format!("{key}.json"), body).await;
+    }
+
+    async fn read_sidecar(
+        &self,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+    ) -> Option<Sidecar> {
+        let name = format!(
+            "{}.meta",
+            lookup_key(file_path, agent_name, model, review_scope)
+        );
+        let body = self.read(&name).await?;
+        Some(Sidecar::read(
+            &String::from_utf8_lossy(&body),
+            file_path,
+            agent_name,
+            model,
+            review_scope,
+        ))
+    }
+
+    async fn write_sidecar(&self, sidecar: &Sidecar) {
+        let Ok(body) = serde_json::to_vec(sidecar) else {
+            return;
+        };
+        let name = format!(
+            "{}.meta",
+            lookup_key(
+                &sidecar.file,
+                &sidecar.agent,
+                &sidecar.model,
+                &sidecar.scope
+            )
+        );
+        self.write(&name, body).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock::{MockServer, Route};
+    use crate::models::finding::Severity;
+
+    fn sample_findings() -> Vec<Finding> {
+        vec![Finding {
+            file: "src/lib.rs".into(),
+            line: 3,
+            end_line: None,
+            severity: Severity::Warning,
+            title: "Shared".into(),
+            message: "From another runner".into(),
+            suggestion: None,
+            agent: "backend".into(),
+            fix: None,
+            served_by: None,
+        }]
+    }
+
+    #[test]
+    fn rejects_invalid_urls() {
+        assert!(RemoteStore::new("not a url", RemoteAuth::None).is_err());
+        assert!(RemoteStore::new("ftp://cache.example.com/", RemoteAuth::None).is_err());
+        assert!(RemoteStore::new("https://cache.example.com/nitpik", RemoteAuth::None).is_ok());
+    }
+
+    #[tokio::test]
+    async fn reads_and_writes_objects_under_base_path() {
+        let body = serde_json::to_string(&sample_findings()).unwrap();
+        let server = MockServer::start(vec![
+            Route::new("GET", "/cache/hit.json", 200, &body),
+            Route::new("PUT", "/cache/new.json", 200, ""),
+        ])
+        .await;
+        let store = RemoteStore::new(
+            &format!("{}/cache", server.url),
+            RemoteAuth::Bearer("secret".into()),
+        )
+        .unwrap();
+
+        assert_eq!(store.get("hit").await.unwrap()[0].title, "Shared");
+        assert!(store.get("miss").await.is_none());
+        store.put("new", &sample_findings()).await;
+
+        let requests = server.requests();
+        let put = requests.iter().find(|r| r.method == "PUT").unwrap();
+        assert_eq!(put.path, "/cache/new.json");
+        assert_eq!(put.header("authorization"), Some("Bearer secret"));
+        assert_eq!(put.json()[0]["title"], "Shared");
+    }
+
+    #[tokio::test]
+    async fn sidecars_round_trip_through_lookup_keys() {
+        let lookup = lookup_key("a.rs", "backend", "model", "main");
+        let sidecar =
+            serde_json::to_string(&Sidecar::new("a.rs", "backend", "model", "key-1", "main"))
+                .unwrap();
+        let server = MockServer::start(vec![
+            Route::new("GET", &format!("/{lookup}.meta"), 200, &sidecar),
+            Route::new("PUT", &format!("/{lookup}.meta"), 200, ""),
+        ])
+        .await;
+        let store = RemoteStore::new(&server.url, RemoteAuth::None).unwrap();
+
+        assert_eq!(
+            store
+                .read_sidecar("a.rs", "backend", "model", "main")
+                .await
+                .unwrap()
+                .cache_key,
+            "key-1"
+        );
+        assert!(
+            store
+                .read_sidecar("a.rs", "backend", "model", "other")
+                .await
+                .is_none()
+        );
+
+        store
+            .write_sidecar(&Sidecar::new("a.rs", "backend", "model", "key-2", "main"))
+            .await;
+        let put = server
+            .requests()
+            .into_iter()
+            .find(|r| r.method == "PUT")
+            .unwrap();
+        assert_eq!(put.json()["cache_key"], "key-2");
+        assert_eq!(put.json()["scope"], "main");
+    }
+
+    #[tokio::test]
+    async fn s3_requests_are_signed() {
+        let server = MockServer::start(vec![Route::new("PUT", "/bucket/k.json", 200, "")]).await;
+        let store = RemoteStore::new(
+            &format!("{}/bucket", server.url),
+            RemoteAuth::S3(Credentials {
+                access_key_id: "AKID".into(),
+                secret_access_key: "secret".into(),
+                session_token: Some("session".into()),
+                region: "eu-north-1".into(),
+            }),
+        )
+        .unwrap();
+        store.put("k", &sample_findings()).await;
+
+        let request = &server.requests()[0];
+        let authorization = request.header("authorization").unwrap();
+        assert!(authorization.starts_with("AWS4-HMAC-SHA256 Credential=AKID/"));
+        assert!(authorization.contains("/eu-north-1/s3/aws4_request"));
+        assert!(
+            authorization.contains(
+                "SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token"
+            )
+        );
+        assert_eq!(
+            request.header("x-amz-content-sha256"),
+            Some(sigv4::payload_hash(request.body.as_bytes()).as_str())
+        );
+        assert_eq!(request.header("x-amz-security-token"), Some("session"));
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_reads_as_miss() {
+        let store = RemoteStore::new("http://127.0.0.1:9/", RemoteAuth::None).unwrap();
+        assert!(store.get("key").await.is_none());
+        store.put("key", &sample_findings()).await;
+    }
+}
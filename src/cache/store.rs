@@ -4,13 +4,76 @@
 //! Each entry also writes a sidecar `.meta` file keyed by
 //! `(file_path, agent_name, model)` so that prior findings can be
 //! retrieved after a cache key changes (content invalidation).
+//!
+//! Reading an entry bumps its modification time, which eviction uses
+//! as the last-access time: real access times are unreliable on the
+//! `noatime`/`relatime` mounts common on CI runners.
 
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+use super::{CacheBackend, KeyComponents};
 use crate::models::finding::Finding;
 
+/// Contents of a sidecar `.meta` file: the latest review of a
+/// file×agent×model×scope tuple.
+///
+/// Older sidecars hold just the cache key as plain text; they still
+/// serve prior findings but count as unknown in the stats breakdown.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sidecar {
+    pub cache_key: String,
+    pub file: String,
+    pub agent: String,
+    pub model: String,
+    pub scope: String,
+    /// What the cache key was built from, to explain later misses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<KeyComponents>,
+}
+
+impl Sidecar {
+    pub fn new(
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        cache_key: &str,
+        review_scope: &str,
+    ) -> Self {
+        Self {
+            cache_key: cache_key.to_string(),
+            file: file_path.to_string(),
+            agent: agent_name.to_string(),
+            model: model.to_string(),
+            scope: review_scope.to_string(),
+            components: None,
+        }
+    }
+
+    pub(crate) fn parse(content: &str) -> Option<Self> {
+        serde_json::from_str(content).ok()
+    }
+
+    /// Read a sidecar for a tuple in either format.
+    pub(crate) fn read(
+        content: &str,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+    ) -> Self {
+        Self::parse(content).unwrap_or_else(|| {
+            Self::new(file_path, agent_name, model, content.trim(), review_scope)
+        })
+    }
+}
+
 /// Filesystem-based cache store.
 pub struct FileStore {
     cache_dir: Option<PathBuf>,
@@ -43,7 +106,9 @@ impl FileStore {
         let path = self.key_path(key)?;
 
         let content = tokio::fs::read_to_string(&path).await.ok()?;
-        serde_json::from_str(&content).ok()
+        let findings = serde_json::from_str(&content).ok()?;
+        touch(&path).await;
+        Some(findings)
     }
 
     /// Store findings by key.
@@ -77,46 +142,129 @@ impl FileStore {
     }
 
     /// Compute statistics about the cache.
+    ///
+    /// Entries are attributed to a scope and model through the sidecars
+    /// that point at them; an entry shared by several scopes counts
+    /// toward each, and entries no sidecar describes count as unknown.
     pub async fn stats(&self) -> Result<CacheStats, std::io::Error> {
+        let mut stats = CacheStats::default();
+        let files = self.files().await?;
+
+        let entry_sizes: HashMap<&str, u64> = files
+            .iter()
+            .filter(|f| f.kind == FileKind::Entry)
+            .map(|f| (f.stem(), f.bytes))
+            .collect();
+        let mut described = HashSet::new();
+        let mut by_scope = HashSet::new();
+        let mut by_model = HashSet::new();
+        for file in &files {
+            stats.total_bytes += file.bytes;
+            if file.kind == FileKind::Entry {
+                stats.entries += 1;
+                continue;
+            }
+            // Sidecars aren't counted as entries, but their size is
+            // included in the total.
+            let Ok(content) = tokio::fs::read_to_string(&file.path).await else {
+                continue;
+            };
+            let Some(meta) = Sidecar::parse(&content) else {
+                continue;
+            };
+            let Some(&bytes) = entry_sizes.get(meta.cache_key.as_str()) else {
+                continue;
+            };
+            described.insert(meta.cache_key.clone());
+            if by_scope.insert((meta.scope.clone(), meta.cache_key.clone())) {
+                stats.by_scope.entry(meta.scope).or_default().add(bytes);
+            }
+            if by_model.insert((meta.model.clone(), meta.cache_key)) {
+                stats.by_model.entry(meta.model).or_default().add(bytes);
+            }
+        }
+
+        for (key, bytes) in entry_sizes {
+            if !described.contains(key) {
+                for breakdown in [&mut stats.by_scope, &mut stats.by_model] {
+                    breakdown
+                        .entry(UNKNOWN_GROUP.to_string())
+                        .or_default()
+                        .add(bytes);
+                }
+            }
+        }
+        for breakdown in [&mut stats.by_scope, &mut stats.by_model] {
+            breakdown.sort_by(|_, a, _, b| b.bytes.cmp(&a.bytes));
+        }
+        Ok(stats)
+    }
+
+    /// Evict entries and sidecars not used within `max_age`, then the
+    /// least recently used ones until the cache fits in `max_bytes`.
+    ///
+    /// Returns the number of entries removed and the bytes freed.
+    pub async fn prune(
+        &self,
+        max_age: Option<Duration>,
+        max_bytes: Option<u64>,
+    ) -> Result<CacheStats, std::io::Error> {
+        let mut files = self.files().await?;
+        files.sort_by_key(|f| f.used);
+        let mut total: u64 = files.iter().map(|f| f.bytes).sum();
+        let now = SystemTime::now();
+        let mut removed = CacheStats::default();
+
+        for file in files {
+            let expired = max_age
+                .is_some_and(|age| now.duration_since(file.used).is_ok_and(|since| since > age));
+            let over_size = max_bytes.is_some_and(|max| total > max);
+            if !expired && !over_size {
+                continue;
+            }
+            if tokio::fs::remove_file(&file.path).await.is_ok() {
+                total -= file.bytes;
+                removed.total_bytes += file.bytes;
+                if file.kind == FileKind::Entry {
+                    removed.entries += 1;
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// List the entries and sidecars in the cache directory.
+    async fn files(&self) -> Result<Vec<CacheFile>, std::io::Error> {
         let Some(ref dir) = self.cache_dir else {
-            return Ok(CacheStats {
-                entries: 0,
-                total_bytes: 0,
-            });
+            return Ok(Vec::new());
         };
-
         if !tokio::fs::try_exists(dir).await.unwrap_or(false) {
-            return Ok(CacheStats {
-                entries: 0,
-                total_bytes: 0,
-            });
+            return Ok(Vec::new());
         }
 
-        let mut entries: usize = 0;
-        let mut total_bytes: u64 = 0;
-
+        let mut files = Vec::new();
         let mut read_dir = tokio::fs::read_dir(dir).await?;
         while let Some(entry) = read_dir.next_entry().await? {
             let path = entry.path();
-            let ext = path.extension().and_then(|e| e.to_str());
-            match ext {
-                Some("json") => {
-                    entries += 1;
-                    total_bytes += entry.metadata().await.map(|m| m.len()).unwrap_or(0);
-                }
-                Some("meta") => {
-                    // Sidecar files are not counted as cache entries
-                    // but their size is included in the total.
-                    total_bytes += entry.metadata().await.map(|m| m.len()).unwrap_or(0);
-                }
-                _ => {}
+            let kind = match path.extension().and_then(|e| e.to_str()) {
+                Some("json") => FileKind::Entry,
+                Some("meta") => FileKind::Sidecar,
+                _ => continue,
+            };
+            let Ok(metadata) = entry.metadata().await else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
             }
+            files.push(CacheFile {
+                path,
+                kind,
+                bytes: metadata.len(),
+                used: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
         }
-
-        Ok(CacheStats {
-            entries,
-            total_bytes,
-        })
+        Ok(files)
     }
 
     /// Return the cache directory path.
@@ -138,14 +286,28 @@ impl FileStore {
         current_cache_key: &str,
         review_scope: &str,
     ) -> Option<Vec<Finding>> {
-        let sidecar = self.sidecar_path(file_path, agent_name, model, review_scope)?;
-        let previous_key = tokio::fs::read_to_string(&sidecar).await.ok()?;
-        let previous_key = previous_key.trim();
+        let previous_key = self
+            .sidecar_key(file_path, agent_name, model, review_scope)
+            .await?;
         if previous_key == current_cache_key || previous_key.is_empty() {
             return None;
         }
         // Read the old cache entry
-        self.get(previous_key).await
+        self.get(&previous_key).await
+    }
+
+    /// Read the cache key the sidecar for a file×agent×model×scope tuple
+    /// points at.
+    pub async fn sidecar_key(
+        &self,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+    ) -> Option<String> {
+        self.read_sidecar(file_path, agent_name, model, review_scope)
+            .await
+            .map(|sidecar| sidecar.cache_key)
     }
 
     /// Write (or overwrite) the sidecar that maps a file×agent×model
@@ -158,13 +320,8 @@ impl FileStore {
         cache_key: &str,
         review_scope: &str,
     ) {
-        let Some(sidecar) = self.sidecar_path(file_path, agent_name, model, review_scope) else {
-            return;
-        };
-        if let Some(parent) = sidecar.parent() {
-            let _ = tokio::fs::create_dir_all(parent).await;
-        }
-        let _ = tokio::fs::write(&sidecar, cache_key).await;
+        let sidecar = Sidecar::new(file_path, agent_name, model, cache_key, review_scope);
+        self.write_sidecar(&sidecar).await;
     }
 
     /// Get the file path for a cache key.
@@ -248,31 +405,141 @@ pub fn lookup_key(file_path: &str, agent_name: &str, model: &str, review_scope:
     hex::encode(hasher.finalize())
 }
 
+#[async_trait]
+impl CacheBackend for FileStore {
+    async fn get(&self, key: &str) -> Option<Vec<Finding>> {
+        FileStore::get(self, key).await
+    }
+
+    async fn put(&self, key: &str, findings: &[Finding]) {
+        FileStore::put(self, key, findings).await;
+    }
+
+    async fn read_sidecar(
+        &self,
+        file_path: &str,
+        agent_name: &str,
+        model: &str,
+        review_scope: &str,
+    ) -> Option<Sidecar> {
+        let path = self.sidecar_path(file_path, agent_name, model, review_scope)?;
+        let content = tokio::fs::read_to_string(&path).await.ok()?;
+        Some(Sidecar::read(
+            &content,
+            file_path,
+            agent_name,
+            model,
+            review_scope,
+        ))
+    }
+
+    async fn write_sidecar(&self, sidecar: &Sidecar) {
+        let Some(path) = self.sidecar_path(
+            &sidecar.file,
+            &sidecar.agent,
+            &sidecar.model,
+            &sidecar.scope,
+        ) else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = tokio::fs::create_dir_all(parent).await;
+        }
+        let Ok(content) = serde_json::to_string(sidecar) else {
+            return;
+        };
+        let _ = tokio::fs::write(&path, content).await;
+    }
+}
+
+/// Group for entries no sidecar describes in the stats breakdown.
+pub const UNKNOWN_GROUP: &str = "(unknown)";
+
 /// Statistics about the cache.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CacheStats {
     /// Number of cached entries.
     pub entries: usize,
     /// Total size in bytes.
     pub total_bytes: u64,
+    /// Entries per review scope (branch), largest first.
+    pub by_scope: IndexMap<String, CacheUsage>,
+    /// Entries per model, largest first.
+    pub by_model: IndexMap<String, CacheUsage>,
 }
 
 impl CacheStats {
     /// Format total_bytes as a human-readable string.
     pub fn human_size(&self) -> String {
-        const KB: u64 = 1024;
-        const MB: u64 = 1024 * KB;
-
-        if self.total_bytes >= MB {
-            format!("{:.1} MiB", self.total_bytes as f64 / MB as f64)
-        } else if self.total_bytes >= KB {
-            format!("{:.1} KiB", self.total_bytes as f64 / KB as f64)
-        } else {
-            format!("{} B", self.total_bytes)
-        }
+        human_size(self.total_bytes)
+    }
+}
+
+/// Entries and bytes attributed to one scope or model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+impl CacheUsage {
+    fn add(&mut self, bytes: u64) {
+        self.entries += 1;
+        self.bytes += bytes;
     }
 }
 
+/// Format a byte count as a human-readable string.
+pub fn human_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+
+    if bytes >= MB {
+        format!("{:.1} MiB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KiB", bytes as f64 / KB as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Entry,
+    Sidecar,
+}
+
+/// A file in the cache directory.
+struct CacheFile {
+    path: PathBuf,
+    kind: FileKind,
+    bytes: u64,
+    /// Last access, as recorded in the modification time.
+    used: SystemTime,
+}
+
+impl CacheFile {
+    /// The file name without its extension: the cache key for entries.
+    fn stem(&self) -> &str {
+        self.path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+    }
+}
+
+/// Bump a file's modification time to mark it as recently used.
+async fn touch(path: &Path) {
+    let path = path.to_path_buf();
+    let _ = tokio::task::spawn_blocking(move || {
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(SystemTime::now())
+    })
+    .await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,6 +561,8 @@ mod tests {
             message: "Details".into(),
             suggestion: None,
             agent: "backend".into(),
+            fix: None,
+            served_by: None,
         }]
     }
 
@@ -384,6 +653,7 @@ mod tests {
         let stats = CacheStats {
             entries: 1,
             total_bytes: 500,
+            ..Default::default()
         };
         assert_eq!(stats.human_size(), "500 B");
     }
@@ -393,6 +663,7 @@ mod tests {
         let stats = CacheStats {
             entries: 1,
             total_bytes: 2048,
+            ..Default::default()
         };
         assert_eq!(stats.human_size(), "2.0 KiB");
     }
@@ -402,6 +673,7 @@ mod tests {
         let stats = CacheStats {
             entries: 1,
             total_bytes: 2 * 1024 * 1024,
+            ..Default::default()
         };
         assert_eq!(stats.human_size(), "2.0 MiB");
     }
@@ -560,6 +832,8 @@ mod tests {
             message: "Only on feature-b".into(),
             suggestion: None,
             agent: "backend".into(),
+            fix: None,
+            served_by: None,
         }];
         store.put("key-b1", &findings_b).await;
         store
@@ -654,4 +928,116 @@ mod tests {
             0
         );
     }
+
+    // ── Eviction and breakdown tests ─────────────────────────────────
+
+    fn backdate(path: &std::path::Path, days: u64) {
+        let time = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+        filetime::set_file_mtime(path, filetime::FileTime::from_system_time(time)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn prune_removes_entries_older_than_max_age() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        store.put("old", &sample_findings()).await;
+        store.put("new", &sample_findings()).await;
+        store
+            .put_sidecar("old.rs", "backend", "model", "old", "main")
+            .await;
+        backdate(&store.key_path("old").unwrap(), 40);
+        backdate(
+            &store
+                .sidecar_path("old.rs", "backend", "model", "main")
+                .unwrap(),
+            40,
+        );
+        std::fs::create_dir(dir.path().join("batches")).unwrap();
+
+        let removed = store
+            .prune(Some(Duration::from_secs(30 * 24 * 60 * 60)), None)
+            .await
+            .unwrap();
+        assert_eq!(removed.entries, 1);
+        assert!(removed.total_bytes > 0);
+        assert!(store.get("old").await.is_none());
+        assert!(store.get("new").await.is_some());
+        assert!(dir.path().join("batches").exists());
+    }
+
+    #[tokio::test]
+    async fn prune_evicts_least_recently_used_to_fit_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        for key in ["a", "b", "c"] {
+            store.put(key, &sample_findings()).await;
+        }
+        let entry_size = std::fs::metadata(store.key_path("a").unwrap())
+            .unwrap()
+            .len();
+        backdate(&store.key_path("a").unwrap(), 3);
+        backdate(&store.key_path("b").unwrap(), 2);
+        backdate(&store.key_path("c").unwrap(), 1);
+        // Reading "a" makes it the most recently used entry.
+        assert!(store.get("a").await.is_some());
+
+        let removed = store.prune(None, Some(entry_size * 2)).await.unwrap();
+        assert_eq!(removed.entries, 1);
+        assert!(store.get("b").await.is_none());
+        assert!(store.get("a").await.is_some());
+        assert!(store.get("c").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn prune_without_limits_keeps_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        store.put("key1", &sample_findings()).await;
+        let removed = store.prune(None, None).await.unwrap();
+        assert_eq!(removed.entries, 0);
+        assert!(store.get("key1").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn get_previous_reads_legacy_plain_sidecars() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        store.put("key-v1", &sample_findings()).await;
+        let sidecar = store
+            .sidecar_path("file.rs", "backend", "model", "main")
+            .unwrap();
+        std::fs::write(&sidecar, "key-v1").unwrap();
+
+        let prior = store
+            .get_previous("file.rs", "backend", "model", "key-v2", "main")
+            .await;
+        assert_eq!(prior.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stats_break_down_by_scope_and_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = make_store(dir.path());
+        for key in ["k1", "k2", "k3", "orphan"] {
+            store.put(key, &sample_findings()).await;
+        }
+        store
+            .put_sidecar("a.rs", "backend", "sonnet", "k1", "main")
+            .await;
+        store
+            .put_sidecar("b.rs", "backend", "sonnet", "k2", "main")
+            .await;
+        store
+            .put_sidecar("a.rs", "backend", "gpt", "k3", "feature")
+            .await;
+
+        let stats = store.stats().await.unwrap();
+        assert_eq!(stats.entries, 4);
+        assert_eq!(stats.by_scope["main"].entries, 2);
+        assert_eq!(stats.by_scope["feature"].entries, 1);
+        assert_eq!(stats.by_scope[UNKNOWN_GROUP].entries, 1);
+        assert_eq!(stats.by_model["sonnet"].entries, 2);
+        assert_eq!(stats.by_model["gpt"].entries, 1);
+        assert_eq!(stats.by_scope.keys().next().unwrap(), "main");
+    }
 }
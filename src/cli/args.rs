@@ -3,7 +3,8 @@
 use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
-use nitpik::models::{DEFAULT_PROFILE, Severity};
+use nitpik::cost::BudgetAction;
+use nitpik::models::{DEFAULT_PROFILE, Forge, GitRange, Severity};
 
 /// AI-powered code review CLI.
 #[derive(Parser, Debug)]
@@ -33,12 +34,27 @@ pub enum Command {
     /// Validate a custom agent profile definition.
     Validate(ValidateArgs),
 
+    /// Apply structured fixes from a saved JSON report.
+    Fix(FixArgs),
+
+    /// Manage the baseline of known findings.
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineAction,
+    },
+
     /// Manage the result cache.
     Cache {
         #[command(subcommand)]
         action: CacheAction,
     },
 
+    /// Browse recorded review runs and trends.
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+
     /// Manage the commercial license key.
     License {
         #[command(subcommand)]
@@ -67,15 +83,147 @@ pub struct ValidateArgs {
     pub file: PathBuf,
 }
 
+/// Arguments for the `fix` subcommand.
+///
+/// Without `--write` or `--patch` this is a dry run that previews each
+/// fix and the resulting diff.
+#[derive(Parser, Debug)]
+pub struct FixArgs {
+    /// JSON report written by `nitpik review --format json`.
+    pub report: PathBuf,
+
+    /// Path to the repository the report was produced for.
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Only apply these findings (1-based positions in the report, comma-separated).
+    #[arg(long, value_delimiter = ',')]
+    pub only: Vec<usize>,
+
+    /// Apply the fixes to the files in place.
+    #[arg(long, default_value_t = false, conflicts_with = "patch")]
+    pub write: bool,
+
+    /// Write the fixes as a unified diff to this file ("-" for stdout).
+    #[arg(long, value_name = "FILE")]
+    pub patch: Option<PathBuf>,
+}
+
+/// Baseline management subcommands.
+#[derive(clap::Subcommand, Debug)]
+pub enum BaselineAction {
+    /// Run a review and record its findings as the baseline.
+    Create(Box<BaselineCreateArgs>),
+}
+
+/// Arguments for `baseline create`.
+#[derive(Parser, Debug)]
+pub struct BaselineCreateArgs {
+    /// Where to write the baseline (default: .nitpik-baseline.json in the repo root).
+    #[arg(long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub review: ReviewArgs,
+}
+
 /// Cache management subcommands.
 #[derive(clap::Subcommand, Debug)]
 pub enum CacheAction {
     /// Remove all cached review results.
     Clear,
-    /// Show cache statistics (entry count and size).
+    /// Show cache statistics (entry count, size, and usage per scope and model).
     Stats,
     /// Print the cache directory path.
     Path,
+    /// Evict old entries, then least recently used ones over the size limit.
+    Prune(CachePruneArgs),
+    /// Bundle one branch's cached results into a `.tar.gz`, e.g. to hand
+    /// them to the next pipeline run as an artifact.
+    Export(CacheExportArgs),
+    /// Restore cached results from an archive written by `cache export`.
+    Import(CacheImportArgs),
+    /// Plan a review like `nitpik review` and show, per task, whether it
+    /// would hit the cache and which key components changed if not.
+    Explain(Box<CacheExplainArgs>),
+}
+
+/// Arguments for `cache explain`.
+#[derive(Parser, Debug)]
+pub struct CacheExplainArgs {
+    #[command(flatten)]
+    pub review: ReviewArgs,
+}
+
+/// Arguments for `cache export`.
+#[derive(Parser, Debug)]
+pub struct CacheExportArgs {
+    /// Review scope (branch) to export (default: the current branch).
+    #[arg(long)]
+    pub scope: Option<String>,
+
+    /// Archive to write.
+    #[arg(short, long)]
+    pub output: PathBuf,
+}
+
+/// Arguments for `cache import`.
+#[derive(Parser, Debug)]
+pub struct CacheImportArgs {
+    /// Archive written by `cache export`.
+    pub input: PathBuf,
+}
+
+/// Arguments for `cache prune`.
+#[derive(Parser, Debug)]
+pub struct CachePruneArgs {
+    /// Remove entries not used within this age, e.g. `30d`, `12h`
+    /// (default: `[cache] ttl`).
+    #[arg(long, value_name = "AGE")]
+    pub older_than: Option<nitpik::cache::limits::Age>,
+
+    /// Evict least recently used entries until the cache fits in this
+    /// size, e.g. `500MB` (default: `[cache] max_size`).
+    #[arg(long, value_name = "SIZE")]
+    pub max_size: Option<nitpik::cache::limits::ByteSize>,
+}
+
+/// Review history subcommands.
+#[derive(clap::Subcommand, Debug)]
+pub enum HistoryAction {
+    /// List recorded runs, most recent first.
+    List(HistoryListArgs),
+    /// Compare two runs: new, fixed and persisting findings.
+    Diff(HistoryDiffArgs),
+    /// Show finding counts per severity and per agent across runs.
+    Trends(HistoryListArgs),
+}
+
+/// Arguments for `history list` and `history trends`.
+#[derive(Parser, Debug)]
+pub struct HistoryListArgs {
+    /// Path to the repository (default: current directory).
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
+
+    /// Number of most recent runs to show.
+    #[arg(long, default_value_t = nitpik::constants::HISTORY_DEFAULT_LIMIT)]
+    pub limit: usize,
+}
+
+/// Arguments for `history diff`.
+#[derive(Parser, Debug)]
+pub struct HistoryDiffArgs {
+    /// ID (or ID prefix) of the older run (default: the second most recent).
+    pub old: Option<String>,
+
+    /// ID (or ID prefix) of the newer run (default: the most recent).
+    #[arg(requires = "old")]
+    pub new: Option<String>,
+
+    /// Path to the repository (default: current directory).
+    #[arg(long, default_value = ".")]
+    pub path: PathBuf,
 }
 
 /// License management subcommands.
@@ -125,6 +273,41 @@ pub struct ReviewArgs {
     #[arg(long)]
     pub scan: Option<PathBuf>,
 
+    /// Review only the changes staged in the index (git diff --cached).
+    #[arg(long, default_value_t = false)]
+    pub staged: bool,
+
+    /// Review only the unstaged changes in the working tree (git diff).
+    #[arg(long, default_value_t = false)]
+    pub working_tree: bool,
+
+    /// Review the changes introduced by a single commit.
+    #[arg(long, value_name = "SHA")]
+    pub commit: Option<String>,
+
+    /// Review a revision range: A..B diffs A against B, A...B diffs B
+    /// against its merge base with A.
+    #[arg(long, value_name = "RANGE")]
+    pub range: Option<GitRange>,
+
+    /// Review a pull request by number, fetched from the --forge API.
+    #[arg(
+        long,
+        value_name = "NUMBER",
+        requires = "forge",
+        conflicts_with = "agent"
+    )]
+    pub pr: Option<u64>,
+
+    /// Forge hosting the --pr pull request: github, gitlab, forgejo or bitbucket.
+    #[arg(long, requires = "pr")]
+    pub forge: Option<Forge>,
+
+    /// Read full file contents from this git revision instead of the
+    /// working tree, e.g. the commit a --diff-file was generated from.
+    #[arg(long, value_name = "REV")]
+    pub content_rev: Option<String>,
+
     // --- Profile ---
     /// Comma-separated profiles: built-in names, file paths, or "auto".
     /// Built-in: frontend, backend, architect, security, general
@@ -153,6 +336,16 @@ pub struct ReviewArgs {
     #[arg(long, default_value_t = false, conflicts_with = "fail_on")]
     pub no_fail: bool,
 
+    // --- Baseline ---
+    /// Baseline file of known findings to suppress (default: .nitpik-baseline.json
+    /// in the repo root, if present).
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Report all findings, ignoring any baseline file.
+    #[arg(long, default_value_t = false, conflicts_with = "baseline")]
+    pub no_baseline: bool,
+
     // --- Agentic ---
     /// Enable agentic context gathering (tools for LLM).
     #[arg(long, default_value_t = false)]
@@ -209,6 +402,41 @@ pub struct ReviewArgs {
     #[arg(long)]
     pub max_prior_findings: Option<usize>,
 
+    /// Record this run in the review history, even if `[history]` is
+    /// not enabled in the config.
+    #[arg(long, default_value_t = false)]
+    pub history: bool,
+
+    // --- Cost ---
+    /// Print a per-file, per-agent token and cost estimate and exit
+    /// without calling the LLM.
+    #[arg(long, default_value_t = false)]
+    pub estimate: bool,
+
+    /// Maximum estimated cost of the run in USD.
+    #[arg(long, value_name = "USD")]
+    pub max_cost: Option<f64>,
+
+    /// What to do when the estimate exceeds --max-cost.
+    #[arg(long, default_value = "abort", requires = "max_cost")]
+    pub budget_action: BudgetAction,
+
+    // --- Batch ---
+    /// Submit all review prompts as one batch job and wait for it
+    /// (Anthropic and OpenAI only). Batches cost about half as much but
+    /// can take hours to finish.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["agent", "no_cache", "max_cost", "estimate", "resume_batch"]
+    )]
+    pub batch: bool,
+
+    /// Collect the results of an earlier --batch run and finish the review.
+    /// Pass the same inputs as the run that submitted it.
+    #[arg(long, value_name = "ID", conflicts_with_all = ["agent", "no_cache", "estimate"])]
+    pub resume_batch: Option<String>,
+
     /// Suppress all non-essential output (banner, progress, informational messages).
     /// Only findings and errors are shown.
     #[arg(long, short = 'q', default_value_t = false)]
@@ -223,6 +451,22 @@ pub struct ReviewArgs {
     #[arg(long, default_value_t = false)]
     pub no_commit_context: bool,
 
+    /// Read the pull request title and description from this file instead
+    /// of the CI environment or the --pr pull request.
+    #[arg(long, value_name = "FILE")]
+    pub pr_description_file: Option<PathBuf>,
+
+    // --- Filters ---
+    /// Only review files matching these globs (comma-separated).
+    /// Example: --include 'src/**,lib/**'
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub include: Vec<String>,
+
+    /// Skip files matching these globs, in addition to `review.exclude`
+    /// (comma-separated).
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
     /// Comma-separated list of project documentation files to exclude by name.
     /// Example: --exclude-doc AGENTS.md,CONVENTIONS.md
     #[arg(long, value_name = "FILENAME", value_delimiter = ',')]
@@ -245,6 +489,7 @@ pub enum OutputFormat {
     Bitbucket,
     Checkstyle,
     Forgejo,
+    Sarif,
 }
 
 impl OutputFormat {
@@ -263,29 +508,67 @@ impl OutputFormat {
                 nitpik::output::checkstyle::CheckstyleFormatter.format(findings)
             }
             OutputFormat::Forgejo => nitpik::output::forgejo::ForgejoFormatter.format(findings),
+            OutputFormat::Sarif => nitpik::output::sarif::SarifFormatter.format(findings),
+        }
+    }
+
+    /// Render findings for stdout, including suppression counts and token
+    /// usage where the format has a summary to put them in.
+    pub fn render_report(
+        &self,
+        findings: &[nitpik::models::finding::Finding],
+        suppressed: nitpik::models::finding::SuppressedCounts,
+        usage: Option<&nitpik::cost::UsageReport>,
+    ) -> String {
+        match self {
+            OutputFormat::Json => {
+                nitpik::output::json::JsonFormatter.format_report(findings, suppressed, usage)
+            }
+            _ => self.render(findings),
         }
     }
 
     /// Publish findings to external APIs for formats that support it.
     ///
-    /// Bitbucket publishes when `BITBUCKET_WORKSPACE` is set.
+    /// GitHub publishes a pull request review when `GITHUB_TOKEN` is set
+    /// and the workflow was triggered by a pull request event.
+    /// GitLab opens merge request discussions when `GITLAB_TOKEN` is set in
+    /// a merge request pipeline; `diffs` are used to position them.
+    /// Bitbucket publishes when `BITBUCKET_WORKSPACE` is set; `failed` is
+    /// the run's pass/fail result and becomes the Code Insights report result.
     /// Forgejo publishes when `CI_FORGE_URL` is set.
     /// Other formats are no-ops.
     pub async fn publish(
         &self,
         findings: &[nitpik::models::finding::Finding],
-        fail_on: Option<nitpik::models::finding::Severity>,
+        diffs: &[nitpik::models::FileDiff<'_>],
+        failed: bool,
         env: &nitpik::env::Env,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         use nitpik::output::OutputPublisher;
         match self {
+            OutputFormat::Github
+                if env.is_set("GITHUB_TOKEN")
+                    && nitpik::output::github::is_pull_request_event(env) =>
+            {
+                nitpik::output::github::GithubPublisher::new(env, diffs)
+                    .publish(findings)
+                    .await
+            }
+            OutputFormat::Gitlab
+                if env.is_set("GITLAB_TOKEN") && env.is_set("CI_MERGE_REQUEST_IID") =>
+            {
+                nitpik::output::gitlab::GitlabPublisher::new(env, diffs)
+                    .publish(findings)
+                    .await
+            }
             OutputFormat::Bitbucket if env.is_set("BITBUCKET_WORKSPACE") => {
-                nitpik::output::bitbucket::BitbucketPublisher::new(fail_on, env)
+                nitpik::output::bitbucket::BitbucketPublisher::new(failed, env)
                     .publish(findings)
                     .await
             }
             OutputFormat::Forgejo if env.is_set("CI_FORGE_URL") => {
-                nitpik::output::forgejo::ForgejoPublisher::new(env)
+                nitpik::output::forgejo::ForgejoPublisher::new(env, diffs)
                     .publish(findings)
                     .await
             }
@@ -302,20 +585,21 @@ impl ReviewArgs {
             self.diff_stdin,
             self.diff_base.is_some(),
             self.scan.is_some(),
+            self.staged,
+            self.working_tree,
+            self.commit.is_some(),
+            self.range.is_some(),
+            self.pr.is_some(),
         ];
         let count = sources.iter().filter(|&&x| x).count();
+        let flags = "--diff-file, --diff-stdin, --diff-base, --scan, --staged, \
+                     --working-tree, --commit, --range, or --pr";
 
         if count == 0 {
-            return Err(
-                "one input source is required: --diff-file, --diff-stdin, --diff-base, or --scan"
-                    .to_string(),
-            );
+            return Err(format!("one input source is required: {flags}"));
         }
         if count > 1 {
-            return Err(
-                "only one input source allowed: --diff-file, --diff-stdin, --diff-base, or --scan"
-                    .to_string(),
-            );
+            return Err(format!("only one input source allowed: {flags}"));
         }
 
         if let Some(ref path) = self.diff_file {
@@ -326,10 +610,33 @@ impl ReviewArgs {
             Ok(InputMode::GitBase(base.clone()))
         } else if let Some(ref path) = self.scan {
             Ok(InputMode::DirectPath(path.clone()))
+        } else if self.staged {
+            Ok(InputMode::Staged)
+        } else if self.working_tree {
+            Ok(InputMode::WorkingTree)
+        } else if let Some(ref commit) = self.commit {
+            Ok(InputMode::Commit(commit.clone()))
+        } else if let Some(ref range) = self.range {
+            Ok(InputMode::Range(range.clone()))
+        } else if let (Some(number), Some(forge)) = (self.pr, self.forge) {
+            Ok(InputMode::PullRequest { number, forge })
         } else {
             unreachable!()
         }
     }
+
+    /// Whether to review agentically, given `[review.agentic] enabled`.
+    ///
+    /// Batch runs are single-turn and cache their results under
+    /// non-agentic keys, so `--batch` and `--resume-batch` always review
+    /// without tools; otherwise `--resume-batch` would miss every key
+    /// and review each file live a second time. `--pr` reviews don't use
+    /// tools either: they would explore the local checkout, not the
+    /// pull request.
+    pub fn use_agent(&self, configured: bool) -> bool {
+        let batch = self.batch || self.resume_batch.is_some();
+        (self.agent || configured) && !batch && self.pr.is_none()
+    }
 }
 
 // InputMode is defined in models/ and re-exported here for convenience.
@@ -360,12 +667,21 @@ mod tests {
             diff_stdin,
             diff_base: diff_base.map(String::from),
             scan: scan.map(PathBuf::from),
+            staged: false,
+            working_tree: false,
+            commit: None,
+            range: None,
+            pr: None,
+            forge: None,
+            content_rev: None,
             profile: vec!["backend".to_string()],
             profile_dir: None,
             tag: vec![],
             format: OutputFormat::Terminal,
             fail_on: None,
             no_fail: false,
+            baseline: None,
+            no_baseline: false,
             agent: false,
             max_turns: 10,
             max_tool_calls: 10,
@@ -378,9 +694,18 @@ mod tests {
             no_cache: false,
             no_prior_context: false,
             max_prior_findings: None,
+            history: false,
+            estimate: false,
+            max_cost: None,
+            budget_action: BudgetAction::Abort,
+            batch: false,
+            resume_batch: None,
             quiet: false,
             no_project_docs: false,
             no_commit_context: false,
+            pr_description_file: None,
+            include: vec![],
+            exclude: vec![],
             exclude_doc: vec![],
             #[cfg(debug_assertions)]
             debug_prompt: false,
@@ -435,6 +760,79 @@ mod tests {
         assert!(matches!(mode, InputMode::Stdin));
     }
 
+    #[test]
+    fn validate_git_selection_inputs() {
+        let mut args = make_args(None, None, None);
+        args.staged = true;
+        assert!(matches!(args.validate_input().unwrap(), InputMode::Staged));
+
+        let mut args = make_args(None, None, None);
+        args.range = Some("main...HEAD".parse().unwrap());
+        let InputMode::Range(range) = args.validate_input().unwrap() else {
+            panic!("expected a range");
+        };
+        assert!(range.merge_base);
+
+        let mut args = make_args(None, Some("main"), None);
+        args.commit = Some("abc1234".to_string());
+        assert!(
+            args.validate_input()
+                .unwrap_err()
+                .contains("only one input source allowed")
+        );
+        assert!(Cli::try_parse_from(["nitpik", "review", "--range", "main"]).is_err());
+    }
+
+    #[test]
+    fn validate_pull_request_input() {
+        let cli =
+            Cli::try_parse_from(["nitpik", "review", "--pr", "42", "--forge", "gitlab"]).unwrap();
+        let Command::Review(args) = cli.command else {
+            panic!("expected Review command");
+        };
+        assert!(matches!(
+            args.validate_input().unwrap(),
+            InputMode::PullRequest {
+                number: 42,
+                forge: Forge::Gitlab
+            }
+        ));
+        assert!(Cli::try_parse_from(["nitpik", "review", "--pr", "42"]).is_err());
+        assert!(Cli::try_parse_from(["nitpik", "review", "--forge", "github"]).is_err());
+        assert!(
+            Cli::try_parse_from(["nitpik", "review", "--pr", "1", "--forge", "gitea"]).is_err()
+        );
+    }
+
+    #[test]
+    fn batch_and_pr_runs_are_never_agentic() {
+        let parse = |args: &[&str]| {
+            let cli = Cli::try_parse_from([&["nitpik", "review", "--staged"], args].concat());
+            let Command::Review(args) = cli.unwrap().command else {
+                panic!("expected Review command");
+            };
+            args
+        };
+        assert!(parse(&["--agent"]).use_agent(false));
+        assert!(parse(&[]).use_agent(true));
+        assert!(!parse(&["--batch"]).use_agent(true));
+        assert!(!parse(&["--resume-batch", "msgbatch_1"]).use_agent(true));
+        let cli = Cli::try_parse_from(["nitpik", "review", "--pr", "7", "--forge", "github"]);
+        let Command::Review(args) = cli.unwrap().command else {
+            panic!("expected Review command");
+        };
+        assert!(!args.use_agent(true));
+        assert!(
+            Cli::try_parse_from([
+                "nitpik", "review", "--pr", "7", "--forge", "github", "--agent"
+            ])
+            .is_err()
+        );
+        assert!(
+            Cli::try_parse_from(["nitpik", "review", "--staged", "--batch", "--agent"]).is_err()
+        );
+    }
+
     #[test]
     fn validate_stdin_conflicts_with_diff_file() {
         let args = make_args_full(Some("diff.patch"), true, None, None);
@@ -469,6 +867,8 @@ mod tests {
             message: "This is a test finding".to_string(),
             suggestion: Some("Fix it".to_string()),
             agent: "backend".to_string(),
+            fix: None,
+            served_by: None,
         }
     }
 
@@ -528,6 +928,16 @@ mod tests {
         let _ = OutputFormat::Bitbucket.render(&empty);
         let _ = OutputFormat::Checkstyle.render(&empty);
         let _ = OutputFormat::Forgejo.render(&empty);
+        let _ = OutputFormat::Sarif.render(&empty);
+    }
+
+    #[test]
+    fn output_format_render_sarif() {
+        let findings = vec![sample_finding()];
+        let output = OutputFormat::Sarif.render(&findings);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["version"], "2.1.0");
+        assert_eq!(parsed["runs"][0]["results"].as_array().unwrap().len(), 1);
     }
 
     #[test]
@@ -570,4 +980,157 @@ mod tests {
             _ => panic!("expected Review command"),
         }
     }
+
+    #[test]
+    fn fix_args_parse() {
+        let cli = Cli::try_parse_from(["nitpik", "fix", "report.json", "--only", "2,5", "--write"])
+            .unwrap();
+        match cli.command {
+            Command::Fix(args) => {
+                assert_eq!(args.report, PathBuf::from("report.json"));
+                assert_eq!(args.only, vec![2, 5]);
+                assert!(args.write);
+                assert!(args.patch.is_none());
+            }
+            _ => panic!("expected Fix command"),
+        }
+    }
+
+    #[test]
+    fn fix_write_conflicts_with_patch() {
+        let result =
+            Cli::try_parse_from(["nitpik", "fix", "r.json", "--write", "--patch", "out.diff"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn baseline_create_accepts_review_flags() {
+        let cli = Cli::try_parse_from([
+            "nitpik",
+            "baseline",
+            "create",
+            "--scan",
+            "src",
+            "--output",
+            "known.json",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Baseline {
+                action: BaselineAction::Create(args),
+            } => {
+                assert_eq!(args.output, Some(PathBuf::from("known.json")));
+                assert_eq!(args.review.scan, Some(PathBuf::from("src")));
+            }
+            _ => panic!("expected Baseline command"),
+        }
+    }
+
+    #[test]
+    fn history_diff_defaults_to_latest_runs() {
+        let cli = Cli::try_parse_from(["nitpik", "history", "diff"]).unwrap();
+        match cli.command {
+            Command::History {
+                action: HistoryAction::Diff(args),
+            } => {
+                assert!(args.old.is_none());
+                assert!(args.new.is_none());
+                assert_eq!(args.path, PathBuf::from("."));
+            }
+            _ => panic!("expected History command"),
+        }
+
+        let cli = Cli::try_parse_from(["nitpik", "history", "trends", "--limit", "5"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::History {
+                action: HistoryAction::Trends(HistoryListArgs { limit: 5, .. })
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_cache_prune() {
+        let cli = Cli::try_parse_from(["nitpik", "cache", "prune", "--older-than", "30d"]).unwrap();
+        let Command::Cache {
+            action: CacheAction::Prune(args),
+        } = cli.command
+        else {
+            panic!("expected cache prune");
+        };
+        assert_eq!(
+            args.older_than.unwrap().duration().as_secs(),
+            30 * 24 * 60 * 60
+        );
+        assert!(args.max_size.is_none());
+
+        assert!(Cli::try_parse_from(["nitpik", "cache", "prune", "--older-than", "soon"]).is_err());
+    }
+
+    #[test]
+    fn parse_cache_export_and_import() {
+        let cli = Cli::try_parse_from([
+            "nitpik",
+            "cache",
+            "export",
+            "--scope",
+            "feature/login",
+            "-o",
+            "cache.tar.gz",
+        ])
+        .unwrap();
+        let Command::Cache {
+            action: CacheAction::Export(args),
+        } = cli.command
+        else {
+            panic!("expected cache export");
+        };
+        assert_eq!(args.scope.as_deref(), Some("feature/login"));
+        assert_eq!(args.output, PathBuf::from("cache.tar.gz"));
+        assert!(Cli::try_parse_from(["nitpik", "cache", "export"]).is_err());
+
+        let cli = Cli::try_parse_from(["nitpik", "cache", "import", "cache.tar.gz"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Cache {
+                action: CacheAction::Import(CacheImportArgs { .. })
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_cache_explain_takes_review_args() {
+        let cli = Cli::try_parse_from([
+            "nitpik",
+            "cache",
+            "explain",
+            "--diff-base",
+            "main",
+            "--profile",
+            "backend",
+        ])
+        .unwrap();
+        let Command::Cache {
+            action: CacheAction::Explain(args),
+        } = cli.command
+        else {
+            panic!("expected cache explain");
+        };
+        assert_eq!(args.review.diff_base.as_deref(), Some("main"));
+        assert_eq!(args.review.profile, vec!["backend"]);
+    }
+
+    #[test]
+    fn no_baseline_conflicts_with_baseline() {
+        let result = Cli::try_parse_from([
+            "nitpik",
+            "review",
+            "--diff-base",
+            "main",
+            "--baseline",
+            "b.json",
+            "--no-baseline",
+        ]);
+        assert!(result.is_err());
+    }
 }
@@ -7,13 +7,18 @@
 //! 4. `~/.config/nitpik/config.toml` (global defaults)
 //! 5. Built-in defaults
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+use crate::cache::limits::{Age, ByteSize};
 use crate::env::Env;
+use crate::models::agent::AgentProfile;
 use crate::models::finding::Severity;
-use crate::models::{DEFAULT_PROFILE, ProviderName};
+use crate::models::{DEFAULT_PROFILE, DEFAULT_PROVIDER, ProviderName};
+
+use super::overrides::PathOverride;
 
 /// Errors during config loading.
 #[derive(Error, Debug)]
@@ -29,6 +34,18 @@ pub enum ConfigError {
         path: PathBuf,
         source: toml::de::Error,
     },
+
+    #[error(
+        "profile '{profile}' uses unknown provider '{provider}'; define it as [providers.{provider}]"
+    )]
+    UnknownProvider { profile: String, provider: String },
+
+    #[error("invalid {key} in {path}: {reason}")]
+    InvalidValue {
+        path: PathBuf,
+        key: String,
+        reason: String,
+    },
 }
 
 /// Top-level configuration.
@@ -37,10 +54,18 @@ pub enum ConfigError {
 pub struct Config {
     pub review: ReviewConfig,
     pub provider: ProviderConfig,
+    /// Named providers that profiles can select by name
+    /// (`[providers.<name>]`).
+    pub providers: IndexMap<String, ProviderConfig>,
     pub secrets: SecretsConfig,
     pub threats: ThreatConfig,
     pub license: LicenseConfig,
     pub telemetry: TelemetryConfig,
+    pub history: HistoryConfig,
+    pub cache: CacheConfig,
+    /// Per-model prices overriding the built-in table, keyed by model
+    /// name or name prefix.
+    pub pricing: IndexMap<String, ModelPrice>,
 }
 
 /// Review-related configuration.
@@ -51,6 +76,14 @@ pub struct ReviewConfig {
     pub fail_on: Option<Severity>,
     pub agentic: AgenticConfig,
     pub context: ContextConfig,
+    /// Globs for files never sent for review. Replaces the built-in
+    /// defaults (lockfiles, minified bundles, snapshots, `vendor/`).
+    pub exclude: Vec<String>,
+    /// Per-path adjustments, applied in order (`[[review.overrides]]`).
+    pub overrides: Vec<PathOverride>,
+    /// Named provider for each profile, overriding the profile's own
+    /// `provider` (`[review.profile_providers]`).
+    pub profile_providers: IndexMap<String, String>,
 }
 
 impl Default for ReviewConfig {
@@ -60,6 +93,12 @@ impl Default for ReviewConfig {
             fail_on: None,
             agentic: AgenticConfig::default(),
             context: ContextConfig::default(),
+            exclude: crate::constants::DEFAULT_EXCLUDES
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            overrides: Vec::new(),
+            profile_providers: IndexMap::new(),
         }
     }
 }
@@ -84,11 +123,13 @@ impl Default for AgenticConfig {
 }
 
 /// Context assembly configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ContextConfig {
     pub max_file_lines: usize,
     pub surrounding_lines: usize,
+    /// Maximum diff lines sent to a reviewer in one chunk.
+    pub chunk_lines: usize,
 }
 
 impl Default for ContextConfig {
@@ -96,6 +137,7 @@ impl Default for ContextConfig {
         Self {
             max_file_lines: 1000,
             surrounding_lines: 100,
+            chunk_lines: crate::constants::DEFAULT_CHUNK_LINES,
         }
     }
 }
@@ -108,6 +150,10 @@ pub struct ProviderConfig {
     pub model: Option<String>,
     pub base_url: Option<String>,
     pub api_key: Option<String>,
+    /// Providers tried in order when this one stays unavailable
+    /// (`[[provider.fallbacks]]`). Fallbacks of fallbacks are ignored.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<ProviderConfig>,
 }
 
 impl ProviderConfig {
@@ -129,6 +175,7 @@ impl std::fmt::Debug for ProviderConfig {
             .field("model", &self.model)
             .field("base_url", &self.base_url)
             .field("api_key", &self.api_key.as_ref().map(|_| "[REDACTED]"))
+            .field("fallbacks", &self.fallbacks)
             .finish()
     }
 }
@@ -140,10 +187,23 @@ impl Default for ProviderConfig {
             model: None,
             base_url: None,
             api_key: None,
+            fallbacks: Vec::new(),
         }
     }
 }
 
+/// Price of a model in USD per million tokens.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    /// Price of input tokens read from the provider's prompt cache.
+    /// Defaults to the built-in discount for the model, if any, or else
+    /// the full input price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input: Option<f64>,
+}
+
 /// Secret scanning configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -194,6 +254,80 @@ impl Default for TelemetryConfig {
     }
 }
 
+/// Review history configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Record every review run in the local history.
+    pub enabled: bool,
+    /// Directory for this repository's history, relative to the repo
+    /// root (default: a per-repo directory under the global config dir).
+    pub dir: Option<String>,
+}
+
+/// Result cache limits.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Maximum total cache size (e.g. `"500MB"`); least recently used
+    /// entries are evicted beyond it.
+    pub max_size: Option<ByteSize>,
+    /// Evict entries not used for this long (e.g. `"30d"`).
+    pub ttl: Option<Age>,
+    /// Local cache directory, relative to the repo root (default:
+    /// `~/.config/nitpik/cache`). Point it at a CI-cached workspace
+    /// directory to keep results between jobs.
+    pub dir: Option<String>,
+    /// Shared cache that runners read from on local misses and write
+    /// through to (`[cache.remote]`).
+    pub remote: Option<RemoteCacheConfig>,
+    /// Shared prompt context whose changes invalidate cached results
+    /// (default: none, so a new commit or doc edit keeps every file's
+    /// cache).
+    pub invalidate_on: Option<Vec<CacheInvalidation>>,
+}
+
+/// Shared prompt context that can be made part of every cache key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheInvalidation {
+    /// Project documentation such as `CONTRIBUTING.md`.
+    ProjectDocs,
+    /// The commit messages of the reviewed range.
+    CommitLog,
+    /// The title and description of the reviewed pull request.
+    PrDescription,
+}
+
+/// A shared cache reached over HTTP.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteCacheConfig {
+    /// Base URL objects are stored under, e.g.
+    /// `https://s3.eu-north-1.amazonaws.com/my-bucket/nitpik/`.
+    pub url: String,
+    /// Protocol spoken by the server.
+    #[serde(default)]
+    pub kind: RemoteCacheKind,
+    /// Bearer token for `http` servers.
+    pub token: Option<String>,
+    /// Region to sign `s3` requests for (default: `us-east-1`).
+    pub region: Option<String>,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+    pub session_token: Option<String>,
+}
+
+/// Protocol of a remote cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteCacheKind {
+    /// Plain `GET`/`PUT` of objects, optionally with a bearer token.
+    #[default]
+    Http,
+    /// S3-compatible object storage with AWS Signature Version 4.
+    S3,
+}
+
 impl Config {
     /// Load configuration with proper layering.
     ///
@@ -225,16 +359,76 @@ impl Config {
         Ok(config)
     }
 
+    /// Name of the provider that reviews with `profile`.
+    ///
+    /// A `[review.profile_providers]` entry wins over the profile's own
+    /// `provider`; without either, [`DEFAULT_PROVIDER`] selects the
+    /// top-level `[provider]`.
+    pub fn provider_ref<'a>(&'a self, profile: &'a AgentProfile) -> &'a str {
+        self.review
+            .profile_providers
+            .get(&profile.name)
+            .or(profile.provider.as_ref())
+            .map_or(DEFAULT_PROVIDER, String::as_str)
+    }
+
+    /// Settings for the provider named `name`, as returned by
+    /// [`provider_ref`](Self::provider_ref).
+    pub fn named_provider(&self, name: &str) -> Option<&ProviderConfig> {
+        if name == DEFAULT_PROVIDER {
+            Some(&self.provider)
+        } else {
+            self.providers.get(name)
+        }
+    }
+
+    /// Check that every profile selects a configured provider.
+    pub fn check_provider_refs<'a>(
+        &self,
+        profiles: impl IntoIterator<Item = &'a AgentProfile>,
+    ) -> Result<(), ConfigError> {
+        for profile in profiles {
+            let name = self.provider_ref(profile);
+            if self.named_provider(name).is_none() {
+                return Err(ConfigError::UnknownProvider {
+                    profile: profile.name.clone(),
+                    provider: name.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Load a config from a specific file.
     fn load_file(path: &Path) -> Result<Self, ConfigError> {
         let content = std::fs::read_to_string(path).map_err(|e| ConfigError::ReadFile {
             path: path.to_path_buf(),
             source: e,
         })?;
-        toml::from_str(&content).map_err(|e| ConfigError::ParseFile {
+        let config: Self = toml::from_str(&content).map_err(|e| ConfigError::ParseFile {
             path: path.to_path_buf(),
             source: e,
-        })
+        })?;
+        config.validate(path)?;
+        Ok(config)
+    }
+
+    /// Reject values that parse but can't be used.
+    fn validate(&self, path: &Path) -> Result<(), ConfigError> {
+        let invalid = |key: String| ConfigError::InvalidValue {
+            path: path.to_path_buf(),
+            key,
+            reason: "must be at least 1".to_string(),
+        };
+        if self.review.context.chunk_lines == 0 {
+            return Err(invalid("review.context.chunk_lines".to_string()));
+        }
+        for (i, o) in self.review.overrides.iter().enumerate() {
+            if o.chunk_lines == Some(0) {
+                return Err(invalid(format!("review.overrides[{i}].chunk_lines")));
+            }
+        }
+        Ok(())
     }
 
     /// Get the global config file path.
@@ -294,6 +488,17 @@ impl Config {
             other.review.context.surrounding_lines,
             dc.surrounding_lines
         );
+        merge_if_changed!(
+            self.review.context.chunk_lines,
+            other.review.context.chunk_lines,
+            dc.chunk_lines
+        );
+        merge_if_changed!(self.review.exclude, other.review.exclude, dr.exclude);
+        // Overrides accumulate so repo blocks apply after global ones
+        self.review.overrides.extend(other.review.overrides);
+        self.review
+            .profile_providers
+            .extend(other.review.profile_providers);
 
         // Provider settings
         let dp = ProviderConfig::default();
@@ -301,6 +506,11 @@ impl Config {
         merge_if_some!(self.provider.model, other.provider.model);
         merge_if_some!(self.provider.base_url, other.provider.base_url);
         merge_if_some!(self.provider.api_key, other.provider.api_key);
+        if !other.provider.fallbacks.is_empty() {
+            self.provider.fallbacks = other.provider.fallbacks;
+        }
+        // Named providers replace same-named entries whole
+        self.providers.extend(other.providers);
 
         // Secret settings
         if other.secrets.enabled {
@@ -329,6 +539,22 @@ impl Config {
         if !other.telemetry.enabled {
             self.telemetry.enabled = false;
         }
+
+        // History settings
+        if other.history.enabled {
+            self.history.enabled = true;
+        }
+        merge_if_some!(self.history.dir, other.history.dir);
+
+        // Cache limits
+        merge_if_some!(self.cache.max_size, other.cache.max_size);
+        merge_if_some!(self.cache.ttl, other.cache.ttl);
+        merge_if_some!(self.cache.dir, other.cache.dir);
+        merge_if_some!(self.cache.remote, other.cache.remote);
+        merge_if_some!(self.cache.invalidate_on, other.cache.invalidate_on);
+
+        // Pricing entries merge per model
+        self.pricing.extend(other.pricing);
     }
 
     /// Apply environment variable overrides.
@@ -358,6 +584,49 @@ impl Config {
         if api_key.is_some() {
             self.provider.api_key = api_key;
         }
+        // Fallbacks and named providers only read their provider-specific
+        // variable; NITPIK_API_KEY belongs to the primary provider.
+        let key_from_env = |provider: &mut ProviderConfig| {
+            if provider.api_key.is_none() {
+                provider.api_key = env.var(provider.name.api_key_env_var()).ok();
+            }
+        };
+        self.provider.fallbacks.iter_mut().for_each(key_from_env);
+        for named in self.providers.values_mut() {
+            key_from_env(named);
+            named.fallbacks.iter_mut().for_each(key_from_env);
+        }
+
+        // Cache location and shared cache
+        if let Ok(val) = env.var(crate::constants::ENV_CACHE_DIR) {
+            self.cache.dir = Some(val);
+        }
+        if let Ok(val) = env.var(crate::constants::ENV_CACHE_URL) {
+            self.cache.remote.get_or_insert_with(Default::default).url = val;
+        }
+        if let Some(remote) = &mut self.cache.remote {
+            let fill = |field: &mut Option<String>, var: &str| {
+                if field.is_none() {
+                    *field = env.var(var).ok();
+                }
+            };
+            fill(&mut remote.token, crate::constants::ENV_CACHE_TOKEN);
+            if remote.kind == RemoteCacheKind::S3 {
+                fill(&mut remote.region, crate::constants::ENV_AWS_REGION);
+                fill(
+                    &mut remote.access_key_id,
+                    crate::constants::ENV_AWS_ACCESS_KEY_ID,
+                );
+                fill(
+                    &mut remote.secret_access_key,
+                    crate::constants::ENV_AWS_SECRET_ACCESS_KEY,
+                );
+                fill(
+                    &mut remote.session_token,
+                    crate::constants::ENV_AWS_SESSION_TOKEN,
+                );
+            }
+        }
 
         // License key
         if let Ok(val) = env.var(crate::constants::ENV_LICENSE_KEY) {
@@ -390,9 +659,118 @@ mod tests {
         assert_eq!(config.provider.resolved_model(), "claude-sonnet-4-20250514");
         assert_eq!(config.review.default_profiles, vec!["general"]);
         assert_eq!(config.review.agentic.max_turns, 10);
+        assert!(config.review.exclude.contains(&"**/Cargo.lock".to_string()));
         assert!(!config.secrets.enabled);
     }
 
+    #[test]
+    fn exclude_replaces_defaults() {
+        let mut base = Config::default();
+        let other: Config = toml::from_str("[review]\nexclude = []\n").unwrap();
+        base.merge(other);
+        assert!(base.review.exclude.is_empty());
+    }
+
+    #[test]
+    fn parse_and_merge_cache_limits() {
+        let mut base: Config =
+            toml::from_str("[cache]\nmax_size = \"500MB\"\nttl = \"30d\"\n").unwrap();
+        let other: Config = toml::from_str("[cache]\nttl = \"7d\"\n").unwrap();
+        base.merge(other);
+        assert_eq!(base.cache.max_size.unwrap().bytes(), 500 << 20);
+        assert_eq!(base.cache.ttl.unwrap().to_string(), "1w");
+        assert!(toml::from_str::<Config>("[cache]\nttl = \"forever\"\n").is_err());
+    }
+
+    #[test]
+    fn parse_cache_invalidation_policy() {
+        let config: Config =
+            toml::from_str("[cache]\ninvalidate_on = [\"project_docs\", \"commit_log\"]\n")
+                .unwrap();
+        assert_eq!(
+            config.cache.invalidate_on.unwrap(),
+            vec![CacheInvalidation::ProjectDocs, CacheInvalidation::CommitLog]
+        );
+        let config: Config =
+            toml::from_str("[cache]\ninvalidate_on = [\"pr_description\"]\n").unwrap();
+        assert_eq!(
+            config.cache.invalidate_on.unwrap(),
+            vec![CacheInvalidation::PrDescription]
+        );
+        assert!(toml::from_str::<Config>("[cache]\ninvalidate_on = [\"readme\"]\n").is_err());
+    }
+
+    #[test]
+    fn parse_remote_cache_with_env_credentials() {
+        let mut config: Config = toml::from_str(
+            r#"
+[cache]
+dir = ".nitpik-cache"
+
+[cache.remote]
+url = "http://minio:9000/ci-cache/nitpik"
+kind = "s3"
+region = "eu-north-1"
+"#,
+        )
+        .unwrap();
+        config.apply_env_vars(&Env::mock([
+            ("AWS_ACCESS_KEY_ID", "AKID"),
+            ("AWS_SECRET_ACCESS_KEY", "secret"),
+            ("AWS_REGION", "us-west-2"),
+        ]));
+        assert_eq!(config.cache.dir.as_deref(), Some(".nitpik-cache"));
+        let remote = config.cache.remote.unwrap();
+        assert_eq!(remote.kind, RemoteCacheKind::S3);
+        assert_eq!(remote.region.as_deref(), Some("eu-north-1"));
+        assert_eq!(remote.access_key_id.as_deref(), Some("AKID"));
+        assert_eq!(remote.secret_access_key.as_deref(), Some("secret"));
+        assert!(remote.token.is_none());
+    }
+
+    #[test]
+    fn apply_env_vars_cache_url_and_token() {
+        let mut config = Config::default();
+        config.apply_env_vars(&Env::mock([
+            ("NITPIK_CACHE_URL", "https://cache.example.com/nitpik"),
+            ("NITPIK_CACHE_TOKEN", "tok"),
+            ("NITPIK_CACHE_DIR", "/ci/cache"),
+        ]));
+        let remote = config.cache.remote.unwrap();
+        assert_eq!(remote.url, "https://cache.example.com/nitpik");
+        assert_eq!(remote.kind, RemoteCacheKind::Http);
+        assert_eq!(remote.token.as_deref(), Some("tok"));
+        assert_eq!(config.cache.dir.as_deref(), Some("/ci/cache"));
+    }
+
+    #[test]
+    fn parse_and_merge_pricing() {
+        let mut base: Config = toml::from_str(
+            r#"
+[pricing."gpt-4o"]
+input = 2.5
+output = 10.0
+
+[pricing."my-local-model"]
+input = 0.0
+output = 0.0
+"#,
+        )
+        .unwrap();
+        let other: Config =
+            toml::from_str("[pricing.\"gpt-4o\"]\ninput = 2.0\noutput = 8.0\n").unwrap();
+        base.merge(other);
+        assert_eq!(base.pricing.len(), 2);
+        assert_eq!(
+            base.pricing["gpt-4o"],
+            ModelPrice {
+                input: 2.0,
+                output: 8.0,
+                cached_input: None,
+            }
+        );
+    }
+
     #[test]
     fn parse_toml_config() {
         let toml_str = r#"
@@ -433,6 +811,7 @@ enabled = true
         other.review.agentic.max_tool_calls = 3;
         other.review.context.max_file_lines = 500;
         other.review.context.surrounding_lines = 50;
+        other.review.context.chunk_lines = 200;
         other.provider.base_url = Some("https://custom.api".to_string());
         other.provider.api_key = Some("sk-test".to_string());
         other.secrets.enabled = true;
@@ -449,6 +828,7 @@ enabled = true
         assert_eq!(base.review.agentic.max_tool_calls, 3);
         assert_eq!(base.review.context.max_file_lines, 500);
         assert_eq!(base.review.context.surrounding_lines, 50);
+        assert_eq!(base.review.context.chunk_lines, 200);
         assert_eq!(
             base.provider.base_url,
             Some("https://custom.api".to_string())
@@ -478,6 +858,28 @@ enabled = true
         assert_eq!(base.review.fail_on, Some(Severity::Warning));
     }
 
+    #[test]
+    fn merge_appends_overrides() {
+        let mut base = Config::default();
+        base.review.overrides.push(PathOverride {
+            paths: vec!["vendor/**".to_string()],
+            ignore: true,
+            ..Default::default()
+        });
+        let mut other = Config::default();
+        other.review.overrides.push(PathOverride {
+            paths: vec!["web/**".to_string()],
+            profiles: vec!["frontend".to_string()],
+            ..Default::default()
+        });
+
+        base.merge(other);
+
+        assert_eq!(base.review.overrides.len(), 2);
+        assert_eq!(base.review.overrides[0].paths, vec!["vendor/**"]);
+        assert_eq!(base.review.overrides[1].paths, vec!["web/**"]);
+    }
+
     #[test]
     fn load_file_reads_toml() {
         let dir = tempfile::tempdir().unwrap();
@@ -508,6 +910,24 @@ model = "gpt-4o"
         assert!(result.unwrap_err().to_string().contains("parse"));
     }
 
+    #[test]
+    fn load_file_rejects_zero_chunk_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("zero.toml");
+
+        std::fs::write(&path, "[review.context]\nchunk_lines = 0\n").unwrap();
+        let err = Config::load_file(&path).unwrap_err().to_string();
+        assert!(err.contains("review.context.chunk_lines"), "{err}");
+
+        std::fs::write(
+            &path,
+            "[[review.overrides]]\npaths = [\"a/**\"]\n\n[[review.overrides]]\npaths = [\"b/**\"]\nchunk_lines = 0\n",
+        )
+        .unwrap();
+        let err = Config::load_file(&path).unwrap_err().to_string();
+        assert!(err.contains("review.overrides[1].chunk_lines"), "{err}");
+    }
+
     #[test]
     fn load_file_not_found() {
         let result = Config::load_file(Path::new("/tmp/nitpik_not_exist_config.toml"));
@@ -601,6 +1021,117 @@ model = "gpt-4o"
         );
     }
 
+    #[test]
+    fn parse_provider_fallbacks_with_env_keys() {
+        let toml_str = r#"
+[provider]
+name = "anthropic"
+
+[[provider.fallbacks]]
+name = "openai"
+model = "gpt-4.1"
+
+[[provider.fallbacks]]
+name = "ollama"
+base_url = "http://localhost:11434"
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        let env = Env::mock([
+            ("NITPIK_API_KEY", "sk-primary"),
+            ("OPENAI_API_KEY", "sk-openai"),
+        ]);
+        config.apply_env_vars(&env);
+
+        let fallbacks = &config.provider.fallbacks;
+        assert_eq!(fallbacks.len(), 2);
+        assert_eq!(fallbacks[0].name, ProviderName::OpenAI);
+        assert_eq!(fallbacks[0].resolved_model(), "gpt-4.1");
+        assert_eq!(fallbacks[0].api_key.as_deref(), Some("sk-openai"));
+        assert_eq!(fallbacks[1].api_key, None);
+        assert_eq!(config.provider.api_key.as_deref(), Some("sk-primary"));
+    }
+
+    #[test]
+    fn parse_named_providers_and_profile_refs() {
+        let toml_str = r#"
+[review.profile_providers]
+frontend = "local"
+
+[providers.local]
+name = "ollama"
+model = "llama3"
+
+[providers.fast]
+name = "openai"
+model = "gpt-4o-mini"
+"#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        let env = Env::mock([
+            ("NITPIK_API_KEY", "sk-primary"),
+            ("OPENAI_API_KEY", "sk-openai"),
+        ]);
+        config.apply_env_vars(&env);
+
+        assert_eq!(config.providers["local"].api_key, None);
+        assert_eq!(
+            config.providers["fast"].api_key.as_deref(),
+            Some("sk-openai")
+        );
+
+        let profile = |name: &str, provider: Option<&str>| {
+            let mut agent = crate::agents::builtin::get_builtin("backend").unwrap();
+            agent.profile.name = name.to_string();
+            agent.profile.provider = provider.map(str::to_string);
+            agent.profile
+        };
+        // Config mapping wins over frontmatter
+        let frontend = profile("frontend", Some("fast"));
+        assert_eq!(config.provider_ref(&frontend), "local");
+        let backend = profile("backend", Some("fast"));
+        assert_eq!(config.provider_ref(&backend), "fast");
+        let security = profile("security", None);
+        assert_eq!(config.provider_ref(&security), DEFAULT_PROVIDER);
+        assert_eq!(
+            config.named_provider(DEFAULT_PROVIDER).unwrap().name,
+            ProviderName::Anthropic
+        );
+
+        assert!(
+            config
+                .check_provider_refs([&frontend, &backend, &security])
+                .is_ok()
+        );
+        let err = config
+            .check_provider_refs([&profile("docs", Some("missing"))])
+            .unwrap_err();
+        assert!(err.to_string().contains("[providers.missing]"));
+    }
+
+    #[test]
+    fn merge_replaces_named_providers_by_name() {
+        let mut base = Config::default();
+        base.providers.insert(
+            "local".to_string(),
+            ProviderConfig {
+                name: ProviderName::Ollama,
+                ..ProviderConfig::default()
+            },
+        );
+        let mut other = Config::default();
+        other.providers.insert(
+            "fast".to_string(),
+            ProviderConfig {
+                name: ProviderName::OpenAI,
+                ..ProviderConfig::default()
+            },
+        );
+        base.merge(other);
+        assert_eq!(
+            base.providers.keys().collect::<Vec<_>>(),
+            vec!["local", "fast"]
+        );
+    }
+
     #[test]
     fn resolved_model_uses_provider_default_when_no_model_set() {
         let mut config = Config::default();
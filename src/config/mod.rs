@@ -8,8 +8,11 @@
 //! LLM providers or diff parsing.
 //!
 //! Handles `.nitpik.toml` loading, environment variable resolution,
-//! and CLI flag merging with proper priority ordering.
+//! and CLI flag merging with proper priority ordering. Per-path
+//! `[[review.overrides]]` resolution lives in [`overrides`].
 
 pub mod loader;
+pub mod overrides;
 
 pub use loader::{Config, ProviderConfig};
+pub use overrides::{PathOverride, PathSettings};
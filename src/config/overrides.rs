@@ -0,0 +1,223 @@
+//! Per-path review overrides (`[[review.overrides]]`).
+//!
+//! Each override selects files by glob and adjusts how they are reviewed:
+//! which profiles run, whether the file is skipped, the fail-on threshold,
+//! and the context limits. When several overrides match a file they are
+//! applied in declaration order, so later blocks win.
+
+use serde::{Deserialize, Serialize};
+
+use crate::glob;
+use crate::models::finding::Severity;
+
+use super::loader::{ContextConfig, ReviewConfig};
+
+/// A single `[[review.overrides]]` block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathOverride {
+    /// Globs matched against repo-relative file paths.
+    pub paths: Vec<String>,
+    /// Profiles that review matching files instead of the default selection.
+    pub profiles: Vec<String>,
+    /// Skip matching files entirely.
+    pub ignore: bool,
+    /// Fail-on threshold for findings in matching files.
+    pub fail_on: Option<Severity>,
+    /// Overrides `review.context.max_file_lines` for matching files.
+    pub max_file_lines: Option<usize>,
+    /// Overrides `review.context.surrounding_lines` for matching files.
+    pub surrounding_lines: Option<usize>,
+    /// Overrides `review.context.chunk_lines` for matching files.
+    pub chunk_lines: Option<usize>,
+}
+
+impl PathOverride {
+    /// Whether this override applies to `path`.
+    pub fn matches(&self, path: &str) -> bool {
+        glob::matches_any(path, &self.paths)
+    }
+}
+
+/// Review settings resolved for a single file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathSettings<'a> {
+    /// Profiles selected by an override, or `None` for the default selection.
+    pub profiles: Option<&'a [String]>,
+    /// Whether the file is excluded from review.
+    pub ignore: bool,
+    /// Fail-on threshold set by an override, if any.
+    pub fail_on: Option<Severity>,
+    /// Effective context limits.
+    pub context: ContextConfig,
+}
+
+impl ReviewConfig {
+    /// Resolve the settings that apply to `path`, folding in every
+    /// matching override on top of the global review settings.
+    pub fn settings_for(&self, path: &str) -> PathSettings<'_> {
+        let mut settings = PathSettings {
+            profiles: None,
+            ignore: false,
+            fail_on: None,
+            context: self.context.clone(),
+        };
+        for o in self.overrides.iter().filter(|o| o.matches(path)) {
+            if !o.profiles.is_empty() {
+                settings.profiles = Some(&o.profiles);
+            }
+            if o.ignore {
+                settings.ignore = true;
+            }
+            if o.fail_on.is_some() {
+                settings.fail_on = o.fail_on;
+            }
+            if let Some(lines) = o.max_file_lines {
+                settings.context.max_file_lines = lines;
+            }
+            if let Some(lines) = o.surrounding_lines {
+                settings.context.surrounding_lines = lines;
+            }
+            if let Some(lines) = o.chunk_lines {
+                settings.context.chunk_lines = lines;
+            }
+        }
+        settings
+    }
+
+    /// Whether `path` is excluded from review by an override.
+    pub fn is_ignored(&self, path: &str) -> bool {
+        self.settings_for(path).ignore
+    }
+
+    /// All profile names referenced by overrides, in declaration order
+    /// and without duplicates.
+    pub fn override_profiles(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self.overrides.iter().flat_map(|o| &o.profiles) {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn config_with(toml_str: &str) -> Config {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn parse_overrides() {
+        let config = config_with(
+            r#"
+[[review.overrides]]
+paths = ["web/**"]
+profiles = ["frontend"]
+fail_on = "warning"
+
+[[review.overrides]]
+paths = ["vendor/**", "**/*.pb.go"]
+ignore = true
+"#,
+        );
+        let overrides = &config.review.overrides;
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides[0].profiles, vec!["frontend"]);
+        assert_eq!(overrides[0].fail_on, Some(Severity::Warning));
+        assert!(overrides[1].ignore);
+        assert_eq!(overrides[1].paths.len(), 2);
+    }
+
+    #[test]
+    fn unmatched_path_uses_global_settings() {
+        let config = config_with(
+            r#"
+[review.context]
+max_file_lines = 400
+
+[[review.overrides]]
+paths = ["web/**"]
+profiles = ["frontend"]
+"#,
+        );
+        let settings = config.review.settings_for("src/main.rs");
+        assert_eq!(settings.profiles, None);
+        assert!(!settings.ignore);
+        assert_eq!(settings.fail_on, None);
+        assert_eq!(settings.context.max_file_lines, 400);
+    }
+
+    #[test]
+    fn later_overrides_win() {
+        let config = config_with(
+            r#"
+[[review.overrides]]
+paths = ["auth/**"]
+profiles = ["security"]
+fail_on = "info"
+max_file_lines = 5000
+chunk_lines = 200
+
+[[review.overrides]]
+paths = ["auth/legacy/**"]
+profiles = ["backend", "security"]
+fail_on = "error"
+"#,
+        );
+        let settings = config.review.settings_for("auth/legacy/login.py");
+        assert_eq!(
+            settings.profiles,
+            Some(&["backend".to_string(), "security".to_string()][..])
+        );
+        assert_eq!(settings.fail_on, Some(Severity::Error));
+        assert_eq!(settings.context.max_file_lines, 5000);
+        assert_eq!(settings.context.surrounding_lines, 100);
+        assert_eq!(settings.context.chunk_lines, 200);
+
+        let settings = config.review.settings_for("auth/session.py");
+        assert_eq!(settings.profiles, Some(&["security".to_string()][..]));
+        assert_eq!(settings.fail_on, Some(Severity::Info));
+    }
+
+    #[test]
+    fn ignore_is_sticky() {
+        let config = config_with(
+            r#"
+[[review.overrides]]
+paths = ["gen/**"]
+ignore = true
+
+[[review.overrides]]
+paths = ["**/*.rs"]
+fail_on = "warning"
+"#,
+        );
+        assert!(config.review.is_ignored("gen/schema.rs"));
+        assert!(!config.review.is_ignored("src/schema.rs"));
+    }
+
+    #[test]
+    fn override_profiles_are_deduplicated() {
+        let config = config_with(
+            r#"
+[[review.overrides]]
+paths = ["web/**"]
+profiles = ["frontend", "security"]
+
+[[review.overrides]]
+paths = ["auth/**"]
+profiles = ["security"]
+"#,
+        );
+        assert_eq!(
+            config.review.override_profiles(),
+            vec!["frontend", "security"]
+        );
+    }
+}
@@ -38,6 +38,9 @@ pub const APP_NAME: &str = "nitpik";
 /// Local config filename (e.g. `.nitpik.toml` in repo root).
 pub const CONFIG_FILENAME: &str = ".nitpik.toml";
 
+/// Baseline filename for suppressing known findings (in repo root).
+pub const BASELINE_FILENAME: &str = ".nitpik-baseline.json";
+
 /// Directory name under `~/.config/` for global config and cache.
 pub const CONFIG_DIR: &str = "nitpik";
 
@@ -92,6 +95,15 @@ pub const ENV_BASE_URL: &str = "NITPIK_BASE_URL";
 pub const ENV_LICENSE_KEY: &str = "NITPIK_LICENSE_KEY";
 pub const ENV_TELEMETRY: &str = "NITPIK_TELEMETRY";
 pub const ENV_DEBUG: &str = "NITPIK_DEBUG";
+pub const ENV_CACHE_DIR: &str = "NITPIK_CACHE_DIR";
+pub const ENV_CACHE_URL: &str = "NITPIK_CACHE_URL";
+pub const ENV_CACHE_TOKEN: &str = "NITPIK_CACHE_TOKEN";
+
+/// Standard AWS credential variables, read for an `s3` remote cache.
+pub const ENV_AWS_ACCESS_KEY_ID: &str = "AWS_ACCESS_KEY_ID";
+pub const ENV_AWS_SECRET_ACCESS_KEY: &str = "AWS_SECRET_ACCESS_KEY";
+pub const ENV_AWS_SESSION_TOKEN: &str = "AWS_SESSION_TOKEN";
+pub const ENV_AWS_REGION: &str = "AWS_REGION";
 
 /// Environment variables that custom command subprocesses inherit by default.
 ///
@@ -137,6 +149,10 @@ pub const INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_secs(
 /// Maximum backoff delay between retries.
 pub const MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60);
 
+/// Consecutive rate-limit or overload errors after which a task moves on
+/// to the next provider in its fallback chain instead of backing off.
+pub const FALLBACK_AFTER_OVERLOADS: u32 = 2;
+
 // ── HTTP constants ──────────────────────────────────────────────────
 
 /// Default total request timeout for outgoing HTTP calls.
@@ -145,19 +161,84 @@ pub const HTTP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10)
 /// Default TCP connect timeout for outgoing HTTP calls.
 pub const HTTP_CONNECT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
+// ── Batch API ───────────────────────────────────────────────────────
+
+/// How often to check whether a submitted batch job has finished.
+pub const BATCH_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Request timeout for batch submission and result downloads, which
+/// carry every prompt or result of a run in one body.
+pub const BATCH_TRANSFER_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Request timeout for a live review call sent outside rig-core, long
+/// enough for a full-length response.
+pub const REVIEW_CALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(900);
+
+/// Subdirectory of the cache directory holding pending batch records.
+pub const BATCHES_DIR: &str = "batches";
+
+// ── Review history ──────────────────────────────────────────────────
+
+/// Subdirectory of the global config directory holding review history,
+/// with one directory per repository.
+pub const HISTORY_DIR: &str = "history";
+
+/// Number of runs `nitpik history list` and `trends` show by default.
+pub const HISTORY_DEFAULT_LIMIT: usize = 20;
+
+// ── Remote cache ────────────────────────────────────────────────────
+
+/// Region S3 requests are signed for when none is configured.
+pub const S3_DEFAULT_REGION: &str = "us-east-1";
+
+// ── Path filtering ──────────────────────────────────────────────────
+
+/// Paths excluded from review unless `review.exclude` is set in config:
+/// lockfiles, minified bundles, snapshots, and vendored dependencies.
+pub const DEFAULT_EXCLUDES: &[&str] = &[
+    "**/Cargo.lock",
+    "**/package-lock.json",
+    "**/npm-shrinkwrap.json",
+    "**/yarn.lock",
+    "**/pnpm-lock.yaml",
+    "**/bun.lockb",
+    "**/composer.lock",
+    "**/Gemfile.lock",
+    "**/poetry.lock",
+    "**/uv.lock",
+    "**/Pipfile.lock",
+    "**/go.sum",
+    "**/mix.lock",
+    "**/Podfile.lock",
+    "**/*.min.js",
+    "**/*.min.css",
+    "**/*.snap",
+    "**/vendor/**",
+];
+
+/// Git attributes file consulted for generated-file markers (in repo root).
+pub const GITATTRIBUTES_FILENAME: &str = ".gitattributes";
+
 // ── Resource limits ─────────────────────────────────────────────────
 
 /// Maximum lines per chunk when splitting large diffs (context window guard).
 pub const DEFAULT_CHUNK_LINES: usize = 500;
 
+/// Output tokens assumed per review task when estimating cost.
+pub const ESTIMATED_OUTPUT_TOKENS: u64 = 1_000;
+
 /// Maximum file size (bytes) for project documentation files.
 pub const MAX_DOC_SIZE: u64 = 256 * 1024;
 
-/// Context lines around hunks when excerpting large files.
-pub const LARGE_FILE_CONTEXT_LINES: usize = 50;
+/// Maximum length (characters) of the pull request description shown
+/// to reviewers.
+pub const MAX_PR_DESCRIPTION_CHARS: usize = 8_000;
 
 /// Maximum compiled regex DFA size for gitleaks rules (50 MB).
 pub const REGEX_SIZE_LIMIT: usize = 50 * 1024 * 1024;
 
 /// Agent name for threat scanner findings.
 pub const THREAT_SCANNER_AGENT: &str = "threat-scanner";
+
+/// Agent name for secret scanner findings.
+pub const SECRET_SCANNER_AGENT: &str = "secret-scanner";
@@ -4,17 +4,101 @@ use std::path::Path;
 
 use indexmap::IndexMap;
 
+use crate::config::loader::ReviewConfig;
+use crate::diff::{git, parser};
+use crate::models::InputMode;
 use crate::models::diff::FileDiff;
 
+/// Where the post-change contents of reviewed files are read from.
+///
+/// The file content shown to the LLM must match the new side of the
+/// diff, or line numbers drift: a staged-only review reads the index,
+/// and commit or range reviews read the tree of the reviewed revision.
+/// Content that still disagrees with the diff is replaced by the
+/// post-image rebuilt from the hunks (see [`load_file_contents`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentSource {
+    /// Files on disk in the working tree.
+    WorkingTree,
+    /// Blobs staged in the git index.
+    Index,
+    /// Blobs in the tree of a git revision.
+    Revision(String),
+    /// Blobs named by a diff's `index` lines (path → object ID), read
+    /// from the working tree when the object isn't in the repository.
+    Blobs(IndexMap<String, String>),
+    /// Contents fetched ahead of time (path → content), e.g. a pull
+    /// request's head files from the forge API.
+    Fetched(IndexMap<String, String>),
+}
+
+impl ContentSource {
+    /// The source holding the new side of `input`'s diff.
+    pub fn for_input(input: &InputMode) -> Self {
+        match input {
+            InputMode::Staged => Self::Index,
+            InputMode::Commit(commit) => Self::Revision(commit.clone()),
+            InputMode::Range(range) => Self::Revision(range.head.clone()),
+            InputMode::DiffFile(_)
+            | InputMode::Stdin
+            | InputMode::GitBase(_)
+            | InputMode::DirectPath(_)
+            | InputMode::WorkingTree => Self::WorkingTree,
+            // The checkout may be unrelated; see `PullRequest::fetch_contents`
+            InputMode::PullRequest { .. } => Self::Fetched(IndexMap::new()),
+        }
+    }
+
+    /// The source for a pre-computed diff: the blobs its `index` lines
+    /// name, or the working tree if it has none.
+    pub fn for_raw_diff(diff: &str) -> Self {
+        let blobs = parser::post_image_blobs(diff);
+        if blobs.is_empty() {
+            Self::WorkingTree
+        } else {
+            Self::Blobs(blobs)
+        }
+    }
+
+    /// Read a repo-relative file, or `None` if it doesn't exist in this
+    /// source or can't be read as text.
+    pub async fn read(&self, repo_root: &Path, path: &str) -> Option<String> {
+        let found = match self {
+            Self::WorkingTree => None,
+            Self::Index => git::show_file(repo_root, "", path).await.ok(),
+            Self::Revision(rev) => git::show_file(repo_root, rev, path).await.ok(),
+            Self::Blobs(blobs) => match blobs.get(path) {
+                Some(id) => git::show_object(repo_root, id).await.ok(),
+                None => None,
+            },
+            Self::Fetched(files) => files.get(path).cloned(),
+        };
+        match (self, found) {
+            (_, Some(content)) => Some(content),
+            (Self::WorkingTree | Self::Blobs(_), None) => {
+                tokio::fs::read_to_string(repo_root.join(path)).await.ok()
+            }
+            (Self::Index | Self::Revision(_) | Self::Fetched(_), None) => None,
+        }
+    }
+}
+
 /// Load full file contents for all changed files.
 ///
-/// For files under `max_lines`, the entire file is loaded.
-/// For larger files, extracts the hunk regions plus surrounding context
-/// lines so the LLM still has type/function context around the changes.
+/// For files under `max_file_lines`, the entire file is loaded.
+/// For larger files, extracts the hunk regions plus `surrounding_lines`
+/// of context so the LLM still has type/function context around the
+/// changes. Both limits are resolved per file, honoring path overrides.
+///
+/// Contents are read from `source`. When a file can't be read there, or
+/// its lines disagree with the diff (a warning is printed), the
+/// post-image is rebuilt from the hunks instead, with the lines between
+/// hunks marked as omitted.
 pub async fn load_file_contents(
     repo_root: &Path,
     diffs: &[FileDiff<'_>],
-    max_lines: usize,
+    source: &ContentSource,
+    review: &ReviewConfig,
 ) -> IndexMap<String, String> {
     let mut contents = IndexMap::new();
 
@@ -23,36 +107,98 @@ pub async fn load_file_contents(
             continue;
         }
 
-        let file_path = repo_root.join(diff.path());
-        if !file_path.exists() {
-            continue;
-        }
-
-        match tokio::fs::read_to_string(&file_path).await {
-            Ok(content) => {
-                let line_count = content.lines().count();
-                if line_count <= max_lines {
-                    contents.insert(diff.path().to_string(), content);
-                } else {
-                    // Large file: extract hunk regions + surrounding context
-                    let excerpt = extract_hunk_context(
-                        &content,
-                        diff,
-                        crate::constants::LARGE_FILE_CONTEXT_LINES,
-                    );
-                    contents.insert(diff.path().to_string(), excerpt);
-                }
+        let content = source.read(repo_root, diff.path()).await.filter(|content| {
+            let matches = matches_diff(content, diff);
+            if !matches {
+                eprintln!(
+                    "Warning: {} doesn't match the diff; reviewing it with the diff's lines only. \
+                     Pass --content-rev to read the matching revision.",
+                    diff.path()
+                );
             }
-            Err(_) => {
-                // Skip files that can't be read (binary, permissions, etc.)
-                continue;
+            matches
+        });
+        let Some(content) = content else {
+            // Skip files with nothing to rebuild (missing, binary, no hunks, etc.)
+            if let Some(rebuilt) = rebuild_post_image(diff) {
+                contents.insert(diff.path().to_string(), rebuilt);
             }
+            continue;
+        };
+        let limits = review.settings_for(diff.path()).context;
+        let line_count = content.lines().count();
+        if line_count <= limits.max_file_lines {
+            contents.insert(diff.path().to_string(), content);
+        } else {
+            // Large file: extract hunk regions + surrounding context
+            let excerpt = extract_hunk_context(&content, diff, limits.surrounding_lines);
+            contents.insert(diff.path().to_string(), excerpt);
         }
     }
 
     contents
 }
 
+/// Load the complete contents of the given repo-relative files from
+/// `source`.
+///
+/// Unlike [`load_file_contents`], nothing is excerpted or rebuilt, so
+/// line numbers match the reviewed files. Files that can't be read are
+/// skipped.
+pub async fn load_full_files<'a>(
+    repo_root: &Path,
+    paths: impl IntoIterator<Item = &'a str>,
+    source: &ContentSource,
+) -> IndexMap<String, String> {
+    let mut contents = IndexMap::new();
+    for path in paths {
+        if let Some(content) = source.read(repo_root, path).await {
+            contents.insert(path.to_string(), content);
+        }
+    }
+    contents
+}
+
+/// Whether every added and context line of `diff` appears at its new
+/// line number in `content`.
+fn matches_diff(content: &str, diff: &FileDiff<'_>) -> bool {
+    let lines: Vec<&str> = content.lines().collect();
+    diff.hunks
+        .iter()
+        .flat_map(|hunk| &hunk.lines)
+        .filter_map(|line| Some((line.new_line_no?, &line.content)))
+        .all(|(line_no, expected)| {
+            (line_no as usize)
+                .checked_sub(1)
+                .and_then(|i| lines.get(i))
+                .is_some_and(|actual| actual == expected)
+        })
+}
+
+/// Rebuild as much of the post-image as the hunks reveal: their added
+/// and context lines, with the lines in between marked as omitted.
+fn rebuild_post_image(diff: &FileDiff<'_>) -> Option<String> {
+    if diff.hunks.is_empty() {
+        return None;
+    }
+    let mut result = String::new();
+    let mut next_line = 1;
+    for hunk in &diff.hunks {
+        let omitted = hunk.new_start.saturating_sub(next_line);
+        if omitted > 0 {
+            result.push_str(&format!("[... {omitted} lines omitted ...]\n\n"));
+        }
+        for line in &hunk.lines {
+            if line.new_line_no.is_some() {
+                result.push_str(&line.content);
+                result.push('\n');
+            }
+        }
+        next_line = hunk.new_start + hunk.new_count;
+    }
+    Some(result)
+}
+
 /// Extract regions around each hunk from a large file.
 ///
 /// For each hunk, takes `context` lines before the hunk start and
@@ -214,11 +360,163 @@ mod tests {
             hunks: vec![],
         }];
 
-        let contents = load_file_contents(dir.path(), &diffs, 1000).await;
+        let contents = load_file_contents(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &ReviewConfig::default(),
+        )
+        .await;
         assert_eq!(contents.len(), 1);
         assert!(contents["small.rs"].contains("fn main()"));
     }
 
+    #[tokio::test]
+    async fn index_source_reads_staged_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = dir.path();
+        for args in [
+            &["init"][..],
+            &["config", "user.email", "test@test.com"],
+            &["config", "user.name", "Test"],
+        ] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(p)
+                .output()
+                .unwrap();
+        }
+        std::fs::write(p.join("small.rs"), "fn staged() {}\n").unwrap();
+        std::process::Command::new("git")
+            .args(["add", "small.rs"])
+            .current_dir(p)
+            .output()
+            .unwrap();
+        std::fs::write(p.join("small.rs"), "fn unstaged() {}\n").unwrap();
+
+        let diffs = vec![FileDiff {
+            old_path: "small.rs".into(),
+            new_path: "small.rs".into(),
+            is_new: true,
+            is_deleted: false,
+            is_rename: false,
+            is_binary: false,
+            hunks: vec![],
+        }];
+        let review = ReviewConfig::default();
+        let staged = load_file_contents(p, &diffs, &ContentSource::Index, &review).await;
+        assert_eq!(staged["small.rs"], "fn staged() {}\n");
+        let on_disk = load_file_contents(p, &diffs, &ContentSource::WorkingTree, &review).await;
+        assert_eq!(on_disk["small.rs"], "fn unstaged() {}\n");
+        let missing =
+            load_file_contents(p, &diffs, &ContentSource::Revision("HEAD".into()), &review).await;
+        assert!(missing.is_empty());
+    }
+
+    fn one_line_diff(path: &str, line_no: u32, content: &'static str) -> FileDiff<'static> {
+        FileDiff {
+            old_path: path.into(),
+            new_path: path.into(),
+            is_new: false,
+            is_deleted: false,
+            is_rename: false,
+            is_binary: false,
+            hunks: vec![Hunk {
+                old_start: line_no,
+                old_count: 1,
+                new_start: line_no,
+                new_count: 1,
+                header: None,
+                lines: vec![
+                    DiffLine {
+                        line_type: DiffLineType::Removed,
+                        content: "old".into(),
+                        old_line_no: Some(line_no),
+                        new_line_no: None,
+                    },
+                    DiffLine {
+                        line_type: DiffLineType::Added,
+                        content: content.into(),
+                        old_line_no: None,
+                        new_line_no: Some(line_no),
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn mismatched_content_is_rebuilt_from_hunks() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "one\ntwo\nthree\n").unwrap();
+        let review = ReviewConfig::default();
+
+        let matching = vec![one_line_diff("a.rs", 2, "two")];
+        let contents =
+            load_file_contents(dir.path(), &matching, &ContentSource::WorkingTree, &review).await;
+        assert_eq!(contents["a.rs"], "one\ntwo\nthree\n");
+
+        // Disk content from another commit: fall back to the diff's lines
+        let stale = vec![one_line_diff("a.rs", 2, "TWO")];
+        let contents =
+            load_file_contents(dir.path(), &stale, &ContentSource::WorkingTree, &review).await;
+        assert_eq!(contents["a.rs"], "[... 1 lines omitted ...]\n\nTWO\n");
+
+        // Not on disk at all
+        let missing = vec![one_line_diff("gone.rs", 1, "new")];
+        let contents =
+            load_file_contents(dir.path(), &missing, &ContentSource::WorkingTree, &review).await;
+        assert_eq!(contents["gone.rs"], "new\n");
+    }
+
+    #[tokio::test]
+    async fn blob_source_reads_objects_named_in_the_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = dir.path();
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(p)
+            .output()
+            .unwrap();
+        std::fs::write(p.join("a.rs"), "one\nTWO\n").unwrap();
+        let hash = std::process::Command::new("git")
+            .args(["hash-object", "-w", "a.rs"])
+            .current_dir(p)
+            .output()
+            .unwrap();
+        let id = String::from_utf8(hash.stdout).unwrap().trim().to_string();
+        // The working tree has since moved on
+        std::fs::write(p.join("a.rs"), "one\ntwo\nthree\n").unwrap();
+
+        let raw = format!(
+            "diff --git a/a.rs b/a.rs\nindex 1234567..{} 100644\n",
+            &id[..12]
+        );
+        let source = ContentSource::for_raw_diff(&raw);
+        assert!(matches!(source, ContentSource::Blobs(_)));
+        let diffs = vec![one_line_diff("a.rs", 2, "TWO")];
+        let contents = load_file_contents(p, &diffs, &source, &ReviewConfig::default()).await;
+        assert_eq!(contents["a.rs"], "one\nTWO\n");
+
+        assert_eq!(ContentSource::for_raw_diff(""), ContentSource::WorkingTree);
+    }
+
+    #[test]
+    fn content_source_follows_input_mode() {
+        assert_eq!(
+            ContentSource::for_input(&InputMode::Staged),
+            ContentSource::Index
+        );
+        assert_eq!(
+            ContentSource::for_input(&InputMode::Range("main...topic".parse().unwrap())),
+            ContentSource::Revision("topic".to_string())
+        );
+        assert_eq!(
+            ContentSource::for_input(&InputMode::GitBase("main".to_string())),
+            ContentSource::WorkingTree
+        );
+    }
+
     #[tokio::test]
     async fn skip_deleted_file() {
         let dir = tempfile::tempdir().unwrap();
@@ -232,7 +530,13 @@ mod tests {
             hunks: vec![],
         }];
 
-        let contents = load_file_contents(dir.path(), &diffs, 1000).await;
+        let contents = load_file_contents(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &ReviewConfig::default(),
+        )
+        .await;
         assert!(contents.is_empty());
     }
 
@@ -251,7 +555,13 @@ mod tests {
             hunks: vec![],
         }];
 
-        let contents = load_file_contents(dir.path(), &diffs, 1000).await;
+        let contents = load_file_contents(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &ReviewConfig::default(),
+        )
+        .await;
         assert!(contents.is_empty());
     }
 
@@ -268,7 +578,13 @@ mod tests {
             hunks: vec![],
         }];
 
-        let contents = load_file_contents(dir.path(), &diffs, 1000).await;
+        let contents = load_file_contents(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &ReviewConfig::default(),
+        )
+        .await;
         assert!(contents.is_empty());
     }
 
@@ -295,18 +611,52 @@ mod tests {
                 header: None,
                 lines: vec![DiffLine {
                     line_type: DiffLineType::Added,
-                    content: "new line".into(),
+                    content: "line 100".into(),
                     old_line_no: None,
                     new_line_no: Some(100),
                 }],
             }],
         }];
 
-        // max_lines = 50, so the 200-line file should trigger excerpt mode
-        let contents = load_file_contents(dir.path(), &diffs, 50).await;
+        // max_file_lines = 50, so the 200-line file should trigger excerpt mode
+        let mut review = ReviewConfig::default();
+        review.context.max_file_lines = 50;
+        review.context.surrounding_lines = 50;
+        let contents =
+            load_file_contents(dir.path(), &diffs, &ContentSource::WorkingTree, &review).await;
         assert_eq!(contents.len(), 1);
         // Should contain context around line 100 but not all 200 lines
         assert!(contents["big.rs"].contains("line 100"));
         assert!(contents["big.rs"].contains("omitted"));
     }
+
+    #[tokio::test]
+    async fn path_override_raises_context_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines: Vec<String> = (1..=200).map(|i| format!("line {i}")).collect();
+        std::fs::create_dir(dir.path().join("core")).unwrap();
+        std::fs::write(dir.path().join("core/big.rs"), lines.join("\n")).unwrap();
+
+        let diffs = vec![FileDiff {
+            old_path: "core/big.rs".into(),
+            new_path: "core/big.rs".into(),
+            is_new: true,
+            is_deleted: false,
+            is_rename: false,
+            is_binary: false,
+            hunks: vec![],
+        }];
+
+        let mut review = ReviewConfig::default();
+        review.context.max_file_lines = 50;
+        review.overrides.push(crate::config::PathOverride {
+            paths: vec!["core/**".to_string()],
+            max_file_lines: Some(500),
+            ..Default::default()
+        });
+        let contents =
+            load_file_contents(dir.path(), &diffs, &ContentSource::WorkingTree, &review).await;
+        assert!(contents["core/big.rs"].contains("line 1\n"));
+        assert!(!contents["core/big.rs"].contains("omitted"));
+    }
 }
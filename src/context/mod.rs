@@ -8,6 +8,7 @@
 //! that the orchestrator injects into prompts.
 
 pub mod files;
+pub mod pr_description;
 pub mod project_docs;
 
 use std::path::Path;
@@ -26,18 +27,23 @@ use crate::models::diff::FileDiff;
 /// When `skip_project_docs` is true, no project docs are included.
 /// Otherwise, `exclude_docs` can filter out specific filenames.
 ///
+/// File contents are read from `source` (see
+/// [`ContentSource::for_input`](files::ContentSource::for_input)).
+///
 /// `commit_log` is passed through as-is — the caller is responsible for
 /// gathering it (via `git_log`) when the input mode is a git ref diff.
+/// The pull request description (see [`pr_description`]) is left for
+/// the caller to fill in as well.
 pub async fn build_baseline_context(
     repo_root: &Path,
     diffs: &[FileDiff<'_>],
+    source: &files::ContentSource,
     config: &Config,
     skip_project_docs: bool,
     exclude_docs: &[String],
     commit_log: Vec<String>,
 ) -> BaselineContext {
-    let file_contents =
-        files::load_file_contents(repo_root, diffs, config.review.context.max_file_lines).await;
+    let file_contents = files::load_file_contents(repo_root, diffs, source, &config.review).await;
 
     let project_docs = if skip_project_docs {
         IndexMap::new()
@@ -49,6 +55,7 @@ pub async fn build_baseline_context(
         file_contents,
         project_docs,
         commit_log,
+        pr_description: None,
     }
 }
 
@@ -56,6 +63,7 @@ pub async fn build_baseline_context(
 mod tests {
     use super::*;
     use crate::models::diff::{FileDiff, Hunk};
+    use files::ContentSource;
 
     fn make_diff(path: &str) -> FileDiff<'_> {
         FileDiff {
@@ -85,7 +93,16 @@ mod tests {
         let diffs = vec![make_diff("main.rs")];
         let config = Config::default();
 
-        let ctx = build_baseline_context(dir.path(), &diffs, &config, false, &[], Vec::new()).await;
+        let ctx = build_baseline_context(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &config,
+            false,
+            &[],
+            Vec::new(),
+        )
+        .await;
         assert!(ctx.file_contents.contains_key("main.rs"));
         assert_eq!(ctx.project_docs.len(), 1);
         assert!(ctx.project_docs.contains_key("AGENTS.md"));
@@ -96,7 +113,16 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let config = Config::default();
 
-        let _ctx = build_baseline_context(dir.path(), &[], &config, false, &[], Vec::new()).await;
+        let _ctx = build_baseline_context(
+            dir.path(),
+            &[],
+            &ContentSource::WorkingTree,
+            &config,
+            false,
+            &[],
+            Vec::new(),
+        )
+        .await;
     }
 
     #[tokio::test]
@@ -108,8 +134,16 @@ mod tests {
         diff.is_deleted = true;
         let config = Config::default();
 
-        let ctx =
-            build_baseline_context(dir.path(), &[diff], &config, false, &[], Vec::new()).await;
+        let ctx = build_baseline_context(
+            dir.path(),
+            &[diff],
+            &ContentSource::WorkingTree,
+            &config,
+            false,
+            &[],
+            Vec::new(),
+        )
+        .await;
         assert!(ctx.file_contents.is_empty());
     }
 
@@ -120,8 +154,16 @@ mod tests {
         let diffs = vec![make_diff("nonexistent.rs")];
         let config = Config::default();
 
-        let _ctx =
-            build_baseline_context(dir.path(), &diffs, &config, false, &[], Vec::new()).await;
+        let _ctx = build_baseline_context(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &config,
+            false,
+            &[],
+            Vec::new(),
+        )
+        .await;
         let dir = tempfile::tempdir().unwrap();
         std::fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
         std::fs::write(dir.path().join("AGENTS.md"), "# Guide").unwrap();
@@ -130,7 +172,16 @@ mod tests {
         let diffs = vec![make_diff("main.rs")];
         let config = Config::default();
 
-        let _ctx = build_baseline_context(dir.path(), &diffs, &config, true, &[], Vec::new()).await;
+        let _ctx = build_baseline_context(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &config,
+            true,
+            &[],
+            Vec::new(),
+        )
+        .await;
     }
 
     #[tokio::test]
@@ -144,8 +195,16 @@ mod tests {
         let config = Config::default();
         let exclude = vec!["AGENTS.md".to_string()];
 
-        let ctx =
-            build_baseline_context(dir.path(), &diffs, &config, false, &exclude, Vec::new()).await;
+        let ctx = build_baseline_context(
+            dir.path(),
+            &diffs,
+            &ContentSource::WorkingTree,
+            &config,
+            false,
+            &exclude,
+            Vec::new(),
+        )
+        .await;
         assert_eq!(ctx.file_contents.len(), 1);
         assert_eq!(ctx.project_docs.len(), 1);
         assert!(!ctx.project_docs.contains_key("AGENTS.md"));
@@ -159,7 +218,16 @@ mod tests {
 
         let config = Config::default();
         // Even with an empty exclude list, skip_project_docs=true wins
-        let ctx = build_baseline_context(dir.path(), &[], &config, true, &[], Vec::new()).await;
+        let ctx = build_baseline_context(
+            dir.path(),
+            &[],
+            &ContentSource::WorkingTree,
+            &config,
+            true,
+            &[],
+            Vec::new(),
+        )
+        .await;
         assert!(ctx.project_docs.is_empty());
     }
 }
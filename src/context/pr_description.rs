@@ -0,0 +1,151 @@
+//! Pull request title and description.
+//!
+//! The description is where authors explain intent, link tickets and
+//! call out risky areas. Inside CI it is read from the pipeline
+//! environment; `--pr` reviews take it from the fetched pull request
+//! and `--pr-description-file` overrides both.
+
+use std::path::Path;
+
+use crate::constants::MAX_PR_DESCRIPTION_CHARS;
+use crate::diff::forge;
+use crate::env::Env;
+use crate::models::Forge;
+
+/// Join a title and description into the text shown to reviewers,
+/// capped at [`MAX_PR_DESCRIPTION_CHARS`]. `None` when both are blank.
+pub fn compose(title: &str, description: &str) -> Option<String> {
+    let text = match (title.trim(), description.trim()) {
+        ("", "") => return None,
+        (title, "") => title.to_string(),
+        ("", description) => description.to_string(),
+        (title, description) => format!("{title}\n\n{description}"),
+    };
+    Some(cap(text))
+}
+
+/// Detect the description of the pull request a CI pipeline runs for.
+///
+/// Tries, in order:
+/// 1. The `pull_request` event payload at `GITHUB_EVENT_PATH` (GitHub
+///    and Forgejo Actions)
+/// 2. `CI_MERGE_REQUEST_TITLE` and `CI_MERGE_REQUEST_DESCRIPTION` (GitLab CI)
+/// 3. The Forgejo API for `CI_COMMIT_PULL_REQUEST` when `FORGEJO_TOKEN`
+///    is set (Woodpecker CI, which doesn't expose the description)
+pub async fn detect(env: &Env, repo_root: &Path) -> Option<String> {
+    if let Ok(path) = env.var("GITHUB_EVENT_PATH") {
+        let event = tokio::fs::read_to_string(&path)
+            .await
+            .ok()
+            .and_then(|payload| serde_json::from_str::<serde_json::Value>(&payload).ok());
+        if let Some(pr) = event.as_ref().and_then(|e| e.get("pull_request")) {
+            return compose(
+                pr["title"].as_str().unwrap_or_default(),
+                pr["body"].as_str().unwrap_or_default(),
+            );
+        }
+    }
+
+    let title = env.var("CI_MERGE_REQUEST_TITLE");
+    let description = env.var("CI_MERGE_REQUEST_DESCRIPTION");
+    if title.is_ok() || description.is_ok() {
+        return compose(&title.unwrap_or_default(), &description.unwrap_or_default());
+    }
+
+    let number = env.var("CI_COMMIT_PULL_REQUEST").ok()?.parse().ok()?;
+    if !env.is_set("FORGEJO_TOKEN") {
+        return None;
+    }
+    match forge::fetch_description(Forge::Forgejo, number, repo_root, env).await {
+        Ok((title, description)) => compose(&title, &description),
+        Err(e) => {
+            eprintln!("Warning: failed to fetch the pull request description: {e}");
+            None
+        }
+    }
+}
+
+/// Truncate `text` to [`MAX_PR_DESCRIPTION_CHARS`], marking the cut.
+fn cap(text: String) -> String {
+    match text.char_indices().nth(MAX_PR_DESCRIPTION_CHARS) {
+        Some((end, _)) => format!("{}\n[... description truncated ...]", &text[..end]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock::{MockServer, Route};
+
+    #[test]
+    fn compose_joins_and_caps() {
+        assert_eq!(compose("Fix login", " \n").as_deref(), Some("Fix login"));
+        assert_eq!(
+            compose("Fix login", "Closes #12.").as_deref(),
+            Some("Fix login\n\nCloses #12.")
+        );
+        assert_eq!(compose("", ""), None);
+
+        let long = compose("", &"é".repeat(MAX_PR_DESCRIPTION_CHARS + 10)).unwrap();
+        assert!(long.ends_with("[... description truncated ...]"));
+        assert_eq!(
+            long.chars().filter(|&c| c == 'é').count(),
+            MAX_PR_DESCRIPTION_CHARS
+        );
+    }
+
+    #[tokio::test]
+    async fn detects_github_event_payload() {
+        let dir = tempfile::tempdir().unwrap();
+        let event = dir.path().join("event.json");
+        std::fs::write(
+            &event,
+            r#"{"pull_request":{"number":3,"title":"Add cache","body":"Risky: eviction."}}"#,
+        )
+        .unwrap();
+        let env = Env::mock([("GITHUB_EVENT_PATH", event.to_str().unwrap())]);
+        assert_eq!(
+            detect(&env, dir.path()).await.as_deref(),
+            Some("Add cache\n\nRisky: eviction.")
+        );
+
+        std::fs::write(&event, r#"{"ref":"refs/heads/main"}"#).unwrap();
+        assert_eq!(detect(&env, dir.path()).await, None);
+    }
+
+    #[tokio::test]
+    async fn detects_gitlab_and_woodpecker_descriptions() {
+        let env = Env::mock([
+            ("CI_MERGE_REQUEST_TITLE", "Bump deps"),
+            ("CI_MERGE_REQUEST_DESCRIPTION", "See JIRA-1."),
+        ]);
+        assert_eq!(
+            detect(&env, Path::new(".")).await.as_deref(),
+            Some("Bump deps\n\nSee JIRA-1.")
+        );
+
+        let server = MockServer::start(vec![Route::new(
+            "GET",
+            "/api/v1/repos/user/repo/pulls/4",
+            200,
+            r#"{"title":"Refactor","body":"No behavior change."}"#,
+        )])
+        .await;
+        let env = Env::mock([
+            ("CI_FORGE_URL", server.url.as_str()),
+            ("CI_REPO_OWNER", "user"),
+            ("CI_REPO_NAME", "repo"),
+            ("CI_COMMIT_PULL_REQUEST", "4"),
+            ("FORGEJO_TOKEN", "tok"),
+        ]);
+        assert_eq!(
+            detect(&env, Path::new(".")).await.as_deref(),
+            Some("Refactor\n\nNo behavior change.")
+        );
+        assert_eq!(
+            detect(&Env::mock(Vec::<(&str, &str)>::new()), Path::new(".")).await,
+            None
+        );
+    }
+}
@@ -37,20 +37,40 @@ const PROJECT_DOC_FILES: &[&str] = &[
 /// Pass `exclude` to skip specific filenames (e.g. `["AGENTS.md"]`).
 /// The names are matched exactly against the filename lists.
 pub async fn detect_project_docs(repo_root: &Path, exclude: &[String]) -> IndexMap<String, String> {
+    let read = async |filename: &str| {
+        let path = repo_root.join(filename);
+        // Check file size before reading
+        if let Ok(metadata) = tokio::fs::metadata(&path).await {
+            if metadata.len() > crate::constants::MAX_DOC_SIZE {
+                return None;
+            }
+        }
+        tokio::fs::read_to_string(&path).await.ok()
+    };
+    detect_project_docs_with(read, exclude).await
+}
+
+/// Like [`detect_project_docs`], reading each candidate with `read`
+/// instead of from disk. `--pr` reviews use this to read the docs at
+/// the pull request's head from the forge.
+pub async fn detect_project_docs_with(
+    read: impl AsyncFn(&str) -> Option<String>,
+    exclude: &[String],
+) -> IndexMap<String, String> {
     // First pass: check for priority review context files.
-    let priority = load_doc_list(repo_root, PRIORITY_DOC_FILES, exclude).await;
+    let priority = load_doc_list(&read, PRIORITY_DOC_FILES, exclude).await;
     if !priority.is_empty() {
         return priority;
     }
 
     // Fallback: scan the generic project doc list.
-    load_doc_list(repo_root, PROJECT_DOC_FILES, exclude).await
+    load_doc_list(&read, PROJECT_DOC_FILES, exclude).await
 }
 
-/// Load docs from `candidates` that exist on disk, are not excluded,
+/// Load docs from `candidates` that `read` finds, are not excluded,
 /// and are within the size limit.
 async fn load_doc_list(
-    repo_root: &Path,
+    read: &impl AsyncFn(&str) -> Option<String>,
     candidates: &[&str],
     exclude: &[String],
 ) -> IndexMap<String, String> {
@@ -61,21 +81,11 @@ async fn load_doc_list(
             continue;
         }
 
-        let path = repo_root.join(filename);
-        if !path.exists() {
-            continue;
-        }
-
-        // Check file size before reading
-        if let Ok(metadata) = tokio::fs::metadata(&path).await {
-            if metadata.len() > crate::constants::MAX_DOC_SIZE {
-                continue;
+        if let Some(content) = read(filename).await {
+            if content.len() as u64 <= crate::constants::MAX_DOC_SIZE {
+                docs.insert(filename.to_string(), content);
             }
         }
-
-        if let Ok(content) = tokio::fs::read_to_string(&path).await {
-            docs.insert(filename.to_string(), content);
-        }
     }
 
     docs
@@ -226,4 +236,17 @@ mod tests {
         assert_eq!(docs.len(), 1);
         assert!(docs.contains_key("CONVENTIONS.md"));
     }
+
+    #[tokio::test]
+    async fn detect_docs_with_custom_reader() {
+        let read = async |filename: &str| match filename {
+            "AGENTS.md" => Some("# Agent Guide".to_string()),
+            "CONVENTIONS.md" => Some("x".repeat(crate::constants::MAX_DOC_SIZE as usize + 1)),
+            _ => None,
+        };
+        let docs = detect_project_docs_with(read, &[]).await;
+        // Oversized docs are skipped, as on disk
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs["AGENTS.md"], "# Agent Guide");
+    }
 }
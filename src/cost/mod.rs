@@ -0,0 +1,366 @@
+//! Token and cost estimation and usage accounting.
+//!
+//! # Bounded Context: Cost Estimation
+//!
+//! Owns the tokenizer approximation, the model price table, budget
+//! selection, and aggregation of the usage providers report. Works on
+//! prompt text, model names and token counts only — never calls an LLM,
+//! so estimates are free and need no API key.
+//!
+//! Token counts are approximate: real tokenizers differ per provider,
+//! and agentic runs spend extra turns the estimate doesn't model.
+
+pub mod pricing;
+pub mod usage;
+
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+
+use crate::config::loader::ModelPrice;
+use crate::constants::ESTIMATED_OUTPUT_TOKENS;
+
+pub use pricing::price_for;
+pub use usage::{TaskUsage, TokenUsage, UsageReport};
+
+/// Approximate the number of tokens in `text`.
+///
+/// Uses ~3.5 characters per token, which errs on the high side for
+/// source code compared to prose.
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64 * 2).div_ceil(7)
+}
+
+/// Estimated cost of a single chunk×agent review task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskEstimate {
+    pub file: String,
+    pub agent: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Cost in USD, or `None` when the model has no known price.
+    pub cost: Option<f64>,
+    /// Whether the result is already cached (no LLM call, no cost).
+    pub cached: bool,
+}
+
+impl TaskEstimate {
+    /// Estimate a task from its full prompt text (system + user).
+    pub fn new(
+        file: &str,
+        agent: &str,
+        model: &str,
+        prompt_text: &str,
+        cached: bool,
+        pricing: &IndexMap<String, ModelPrice>,
+    ) -> Self {
+        let input_tokens = estimate_tokens(prompt_text);
+        let output_tokens = ESTIMATED_OUTPUT_TOKENS;
+        let cost = if cached {
+            Some(0.0)
+        } else {
+            price_for(model, pricing).map(|p| p.cost(input_tokens, output_tokens))
+        };
+        Self {
+            file: file.to_string(),
+            agent: agent.to_string(),
+            model: model.to_string(),
+            input_tokens,
+            output_tokens,
+            cost,
+            cached,
+        }
+    }
+}
+
+impl ModelPrice {
+    /// Cost in USD for the given token counts.
+    pub fn cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input + output_tokens as f64 * self.output) / 1_000_000.0
+    }
+
+    /// Price per million input tokens read from the prompt cache.
+    pub fn cached_input_rate(&self) -> f64 {
+        self.cached_input.unwrap_or(self.input)
+    }
+
+    /// Cost in USD of reported usage, with prompt-cache reads billed at
+    /// [`cached_input_rate`](Self::cached_input_rate).
+    pub fn usage_cost(&self, usage: &TokenUsage) -> f64 {
+        let cached = usage.cached_input_tokens.min(usage.input_tokens);
+        let uncached = usage.input_tokens - cached;
+        self.cost(uncached, usage.output_tokens)
+            + cached as f64 * self.cached_input_rate() / 1_000_000.0
+    }
+}
+
+/// Estimate for a whole review run.
+#[derive(Debug, Clone, Default)]
+pub struct Estimate {
+    pub tasks: Vec<TaskEstimate>,
+}
+
+impl Estimate {
+    /// Total estimated cost in USD; tasks without a known price count as zero.
+    pub fn total_cost(&self) -> f64 {
+        self.tasks.iter().filter_map(|t| t.cost).sum()
+    }
+
+    /// Models used by uncached tasks that have no known price.
+    pub fn unpriced_models(&self) -> Vec<&str> {
+        let mut models: Vec<&str> = Vec::new();
+        for task in self.tasks.iter().filter(|t| t.cost.is_none()) {
+            if !models.contains(&task.model.as_str()) {
+                models.push(&task.model);
+            }
+        }
+        models
+    }
+
+    /// Render a per-file, per-agent breakdown as a plain-text table.
+    pub fn render(&self) -> String {
+        // Chunks of the same file are summed into one row per file×agent
+        let mut rows: IndexMap<(&str, &str), TaskEstimate> = IndexMap::new();
+        for task in &self.tasks {
+            let row = rows
+                .entry((&task.file, &task.agent))
+                .or_insert_with(|| TaskEstimate {
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cost: Some(0.0),
+                    cached: true,
+                    ..task.clone()
+                });
+            row.input_tokens += task.input_tokens;
+            row.output_tokens += task.output_tokens;
+            row.cost = row.cost.zip(task.cost).map(|(a, b)| a + b);
+            row.cached &= task.cached;
+        }
+
+        let file_width = rows.keys().map(|(f, _)| f.len()).max().unwrap_or(0).max(4);
+        let agent_width = rows.keys().map(|(_, a)| a.len()).max().unwrap_or(0).max(5);
+        let model_width = rows
+            .values()
+            .map(|r| r.model.len())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{:<file_width$}  {:<agent_width$}  {:<model_width$}  {:>10}  {:>10}  {:>10}",
+            "File", "Agent", "Model", "Input", "Output", "Cost"
+        );
+        for ((file, agent), row) in &rows {
+            let (input, output) = if row.cached {
+                ("cached".to_string(), "-".to_string())
+            } else {
+                (
+                    format_tokens(row.input_tokens),
+                    format_tokens(row.output_tokens),
+                )
+            };
+            let _ = writeln!(
+                out,
+                "{file:<file_width$}  {agent:<agent_width$}  {:<model_width$}  {input:>10}  {output:>10}  {:>10}",
+                row.model,
+                format_cost(row.cost),
+            );
+        }
+
+        let uncached: Vec<&TaskEstimate> = self.tasks.iter().filter(|t| !t.cached).collect();
+        let input: u64 = uncached.iter().map(|t| t.input_tokens).sum();
+        let output: u64 = uncached.iter().map(|t| t.output_tokens).sum();
+        let cached = self.tasks.len() - uncached.len();
+        let _ = writeln!(out);
+        let _ = writeln!(
+            out,
+            "{} task(s), {} cached — ~{} input + ~{} output tokens, estimated {}",
+            self.tasks.len(),
+            cached,
+            format_tokens(input),
+            format_tokens(output),
+            format_cost(Some(self.total_cost())),
+        );
+        let unpriced = self.unpriced_models();
+        if !unpriced.is_empty() {
+            let _ = writeln!(
+                out,
+                "No price known for {} — add it under [pricing] in .nitpik.toml.",
+                unpriced.join(", ")
+            );
+        }
+        out
+    }
+}
+
+/// What to do when the estimate exceeds `--max-cost`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BudgetAction {
+    /// Refuse to start the review.
+    #[default]
+    Abort,
+    /// Drop the lowest-priority tasks until the rest fit the budget.
+    Drop,
+}
+
+/// A spending cap for a review run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Budget {
+    /// Maximum estimated cost in USD.
+    pub max_cost: f64,
+    pub action: BudgetAction,
+}
+
+/// Choose which tasks to keep under `max_cost`.
+///
+/// `estimates` must be in priority order, highest first. Cached tasks
+/// are always kept and unpriced ones never are, since their cost is
+/// unknown; the others are kept greedily while they fit, so a cheaper
+/// task may still run after a costlier one was dropped. Returns one
+/// flag per estimate.
+pub fn select_within_budget(estimates: &[TaskEstimate], max_cost: f64) -> Vec<bool> {
+    let mut spent = 0.0;
+    estimates
+        .iter()
+        .map(|t| match t.cost {
+            Some(cost) if spent + cost > max_cost => false,
+            Some(cost) => {
+                spent += cost;
+                true
+            }
+            None => false,
+        })
+        .collect()
+}
+
+/// Format a token count with thousands separators.
+fn format_tokens(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Format a USD amount, or `?` when unknown.
+pub(crate) fn format_cost(cost: Option<f64>) -> String {
+    match cost {
+        Some(c) => format!("${c:.4}"),
+        None => "?".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(file: &str, agent: &str, cost: Option<f64>, cached: bool) -> TaskEstimate {
+        TaskEstimate {
+            file: file.to_string(),
+            agent: agent.to_string(),
+            model: "claude-sonnet-4-20250514".to_string(),
+            input_tokens: 1000,
+            output_tokens: 500,
+            cost,
+            cached,
+        }
+    }
+
+    #[test]
+    fn estimate_tokens_approximation() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcdefg"), 2);
+        assert_eq!(estimate_tokens(&"x".repeat(3500)), 1000);
+    }
+
+    #[test]
+    fn task_estimate_uses_price_table() {
+        let prompt = "x".repeat(3_500_000);
+        let est = TaskEstimate::new(
+            "a.rs",
+            "backend",
+            "claude-sonnet-4-20250514",
+            &prompt,
+            false,
+            &IndexMap::new(),
+        );
+        assert_eq!(est.input_tokens, 1_000_000);
+        assert_eq!(est.output_tokens, ESTIMATED_OUTPUT_TOKENS);
+        let expected = 3.0 + ESTIMATED_OUTPUT_TOKENS as f64 * 15.0 / 1_000_000.0;
+        assert!((est.cost.unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cached_tasks_are_free() {
+        let est = TaskEstimate::new(
+            "a.rs",
+            "backend",
+            "llama3",
+            "prompt",
+            true,
+            &IndexMap::new(),
+        );
+        assert_eq!(est.cost, Some(0.0));
+    }
+
+    #[test]
+    fn unknown_model_has_no_cost() {
+        let est = TaskEstimate::new(
+            "a.rs",
+            "backend",
+            "llama3",
+            "prompt",
+            false,
+            &IndexMap::new(),
+        );
+        assert_eq!(est.cost, None);
+        let estimate = Estimate { tasks: vec![est] };
+        assert_eq!(estimate.unpriced_models(), vec!["llama3"]);
+        assert_eq!(estimate.total_cost(), 0.0);
+    }
+
+    #[test]
+    fn render_groups_chunks_per_file_and_agent() {
+        let estimate = Estimate {
+            tasks: vec![
+                task("src/main.rs", "backend", Some(0.01), false),
+                task("src/main.rs", "backend", Some(0.02), false),
+                task("src/lib.rs", "backend", Some(0.0), true),
+            ],
+        };
+        let out = estimate.render();
+        assert!(out.contains("src/main.rs"));
+        assert!(out.contains("2,000"));
+        assert!(out.contains("$0.0300"));
+        assert!(out.contains("cached"));
+        assert!(out.contains("3 task(s), 1 cached"));
+    }
+
+    #[test]
+    fn budget_keeps_tasks_in_priority_order() {
+        let estimates = vec![
+            task("a.rs", "security", Some(0.5), false),
+            task("b.rs", "security", Some(0.4), false),
+            task("a.rs", "backend", Some(0.05), false),
+            task("c.rs", "backend", Some(0.0), true),
+            task("d.rs", "backend", None, false),
+        ];
+        let keep = select_within_budget(&estimates, 0.6);
+        assert_eq!(keep, vec![true, false, true, true, false]);
+    }
+
+    #[test]
+    fn format_tokens_separators() {
+        assert_eq!(format_tokens(0), "0");
+        assert_eq!(format_tokens(999), "999");
+        assert_eq!(format_tokens(1000), "1,000");
+        assert_eq!(format_tokens(1234567), "1,234,567");
+    }
+}
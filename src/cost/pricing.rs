@@ -0,0 +1,165 @@
+//! Built-in model price table.
+//!
+//! Prices are list prices in USD per million tokens. They drift as
+//! providers change their rates, so `[pricing]` in config always takes
+//! precedence over this table.
+
+use indexmap::IndexMap;
+
+use crate::config::loader::ModelPrice;
+
+/// Built-in prices keyed by model-name prefix:
+/// `(prefix, input, output, cached input)`.
+///
+/// The longest matching prefix wins, so dated snapshots such as
+/// `claude-sonnet-4-20250514` resolve to their family entry. The cached
+/// input price is the provider's published prompt-cache read rate, or
+/// the full input price where there is no discount.
+const BUILTIN_PRICES: &[(&str, f64, f64, f64)] = &[
+    // Anthropic: cache reads at 10% of input
+    ("claude-opus-4", 15.0, 75.0, 1.5),
+    ("claude-sonnet-4", 3.0, 15.0, 0.3),
+    ("claude-3-7-sonnet", 3.0, 15.0, 0.3),
+    ("claude-3-5-sonnet", 3.0, 15.0, 0.3),
+    ("claude-3-5-haiku", 0.8, 4.0, 0.08),
+    ("claude-haiku-4", 1.0, 5.0, 0.1),
+    // OpenAI
+    ("gpt-4o-mini", 0.15, 0.6, 0.075),
+    ("gpt-4o", 2.5, 10.0, 1.25),
+    ("gpt-4.1-nano", 0.1, 0.4, 0.025),
+    ("gpt-4.1-mini", 0.4, 1.6, 0.1),
+    ("gpt-4.1", 2.0, 8.0, 0.5),
+    ("o3-mini", 1.1, 4.4, 0.55),
+    ("o4-mini", 1.1, 4.4, 0.275),
+    ("o3", 2.0, 8.0, 0.5),
+    // Google
+    ("gemini-2.5-pro", 1.25, 10.0, 0.31),
+    ("gemini-2.5-flash", 0.3, 2.5, 0.075),
+    ("gemini-2.0-flash", 0.1, 0.4, 0.025),
+    // Others
+    ("command-r-plus", 2.5, 10.0, 2.5),
+    ("deepseek-chat", 0.27, 1.1, 0.07),
+    ("deepseek-reasoner", 0.55, 2.19, 0.14),
+    ("grok-3", 3.0, 15.0, 0.75),
+    ("mistral-large", 2.0, 6.0, 2.0),
+    ("llama-3.3-70b-versatile", 0.59, 0.79, 0.59),
+    ("moonshot-v1-32k", 1.0, 3.0, 1.0),
+    ("sonar-pro", 3.0, 15.0, 3.0),
+];
+
+/// Look up the price of `model`.
+///
+/// Config overrides are consulted before the built-in table, each by
+/// exact name first and then by longest prefix. Router-style names
+/// (`anthropic/claude-sonnet-4`) fall back to the part after the last `/`.
+///
+/// An override without a `cached_input` price gets the built-in
+/// model's prompt-cache discount, applied to its own input price.
+pub fn price_for(model: &str, overrides: &IndexMap<String, ModelPrice>) -> Option<ModelPrice> {
+    let bare = model.rsplit('/').next().unwrap_or(model);
+    [model, bare].into_iter().find_map(|name| {
+        let Some(mut price) = lookup_override(name, overrides) else {
+            return lookup_builtin(name);
+        };
+        if price.cached_input.is_none() {
+            price.cached_input = [name, bare]
+                .into_iter()
+                .find_map(lookup_builtin)
+                .and_then(|builtin| Some(price.input * builtin.cached_input? / builtin.input));
+        }
+        Some(price)
+    })
+}
+
+fn lookup_override(model: &str, overrides: &IndexMap<String, ModelPrice>) -> Option<ModelPrice> {
+    if let Some(price) = overrides.get(model) {
+        return Some(price.clone());
+    }
+    overrides
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, price)| price.clone())
+}
+
+fn lookup_builtin(model: &str) -> Option<ModelPrice> {
+    BUILTIN_PRICES
+        .iter()
+        .filter(|(prefix, ..)| model.starts_with(prefix))
+        .max_by_key(|(prefix, ..)| prefix.len())
+        .map(|&(_, input, output, cached_input)| ModelPrice {
+            input,
+            output,
+            cached_input: Some(cached_input),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_prefix_match() {
+        let price = price_for("claude-sonnet-4-20250514", &IndexMap::new()).unwrap();
+        assert_eq!(
+            price,
+            ModelPrice {
+                input: 3.0,
+                output: 15.0,
+                cached_input: Some(0.3),
+            }
+        );
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let price = price_for("gpt-4o-mini-2024-07-18", &IndexMap::new()).unwrap();
+        assert_eq!(price.input, 0.15);
+        let price = price_for("gpt-4o", &IndexMap::new()).unwrap();
+        assert_eq!(price.input, 2.5);
+    }
+
+    #[test]
+    fn router_names_use_bare_model() {
+        let price = price_for("anthropic/claude-sonnet-4-20250514", &IndexMap::new()).unwrap();
+        assert_eq!(price.output, 15.0);
+    }
+
+    #[test]
+    fn overrides_take_precedence() {
+        let mut overrides = IndexMap::new();
+        overrides.insert(
+            "claude-sonnet-4".to_string(),
+            ModelPrice {
+                input: 1.0,
+                output: 2.0,
+                cached_input: None,
+            },
+        );
+        let price = price_for("claude-sonnet-4-20250514", &overrides).unwrap();
+        assert_eq!(price.input, 1.0);
+        // The built-in 90% cache-read discount carries over
+        assert!((price.cached_input.unwrap() - 0.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_override_has_no_cache_discount() {
+        let mut overrides = IndexMap::new();
+        overrides.insert(
+            "my-finetune".to_string(),
+            ModelPrice {
+                input: 0.5,
+                output: 1.5,
+                cached_input: None,
+            },
+        );
+        let price = price_for("my-finetune", &overrides).unwrap();
+        assert_eq!(price.cached_input, None);
+        assert_eq!(price.cached_input_rate(), 0.5);
+    }
+
+    #[test]
+    fn unknown_model_has_no_price() {
+        assert!(price_for("llama3", &IndexMap::new()).is_none());
+    }
+}
@@ -0,0 +1,279 @@
+//! Actual token usage reported by providers.
+//!
+//! Usage is recorded per chunk×agent task and aggregated per agent, per
+//! file and in total. Cache hits make no LLM call and cost nothing; the
+//! tokens they would have used are estimated so the report can show what
+//! the cache saved.
+
+use std::fmt::Write;
+use std::ops::AddAssign;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::config::loader::ModelPrice;
+
+use super::{format_cost, format_tokens, price_for};
+
+/// Token counts for one or more LLM calls.
+///
+/// `cached_input_tokens` and `reasoning_tokens` are zero when the
+/// provider doesn't report them separately.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Part of `input_tokens` read from the provider's prompt cache.
+    pub cached_input_tokens: u64,
+    /// Output tokens spent on hidden reasoning.
+    pub reasoning_tokens: u64,
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cached_input_tokens += other.cached_input_tokens;
+        self.reasoning_tokens += other.reasoning_tokens;
+    }
+}
+
+/// Usage of a single chunk×agent review task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskUsage {
+    pub file: String,
+    pub agent: String,
+    pub model: String,
+    /// Tokens reported by the provider; zero for cache hits.
+    pub usage: TokenUsage,
+    /// Whether the result came from the cache.
+    pub cached: bool,
+    /// Estimated tokens a cache hit avoided; zero otherwise.
+    pub saved: TokenUsage,
+}
+
+/// Aggregated usage for a group of tasks.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageSummary {
+    /// Number of tasks sent to the LLM.
+    pub requests: usize,
+    /// Number of tasks served from the cache.
+    pub cache_hits: usize,
+    #[serde(flatten)]
+    pub tokens: TokenUsage,
+    /// Cost in USD, or `None` when a model used has no known price.
+    pub cost: Option<f64>,
+    /// Estimated cost in USD avoided by cache hits.
+    pub saved_cost: Option<f64>,
+}
+
+impl UsageSummary {
+    fn add(&mut self, task: &TaskUsage, pricing: &IndexMap<String, ModelPrice>) {
+        let price = price_for(&task.model, pricing);
+        let cost_of = |usage: &TokenUsage| price.as_ref().map(|p| p.usage_cost(usage));
+        if task.cached {
+            self.cache_hits += 1;
+            self.saved_cost = self
+                .saved_cost
+                .zip(cost_of(&task.saved))
+                .map(|(a, b)| a + b);
+        } else {
+            self.requests += 1;
+            self.tokens += task.usage;
+            self.cost = self.cost.zip(cost_of(&task.usage)).map(|(a, b)| a + b);
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            cost: Some(0.0),
+            saved_cost: Some(0.0),
+            ..Self::default()
+        }
+    }
+}
+
+/// Usage for a whole review run, in total and broken down.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub total: UsageSummary,
+    pub by_agent: IndexMap<String, UsageSummary>,
+    pub by_file: IndexMap<String, UsageSummary>,
+}
+
+impl UsageReport {
+    /// Aggregate task usage, pricing it with `pricing` overrides first.
+    pub fn new(tasks: &[TaskUsage], pricing: &IndexMap<String, ModelPrice>) -> Self {
+        let mut report = Self {
+            total: UsageSummary::empty(),
+            ..Self::default()
+        };
+        for task in tasks {
+            report.total.add(task, pricing);
+            report
+                .by_agent
+                .entry(task.agent.clone())
+                .or_insert_with(UsageSummary::empty)
+                .add(task, pricing);
+            report
+                .by_file
+                .entry(task.file.clone())
+                .or_insert_with(UsageSummary::empty)
+                .add(task, pricing);
+        }
+        report
+    }
+
+    /// Render per-agent and per-file tables followed by a total line.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        render_table(&mut out, "Agent", &self.by_agent);
+        let _ = writeln!(out);
+        render_table(&mut out, "File", &self.by_file);
+        let _ = writeln!(out);
+
+        let total = &self.total;
+        let _ = write!(
+            out,
+            "Usage: {} input ({} from prompt cache) + {} output tokens across {} request(s), {}",
+            format_tokens(total.tokens.input_tokens),
+            format_tokens(total.tokens.cached_input_tokens),
+            format_tokens(total.tokens.output_tokens),
+            total.requests,
+            format_cost(total.cost),
+        );
+        if total.cache_hits > 0 {
+            let _ = write!(
+                out,
+                "; {} cache hit(s) saved ~{}",
+                total.cache_hits,
+                format_cost(total.saved_cost)
+            );
+        }
+        let _ = writeln!(out);
+        out
+    }
+}
+
+fn render_table(out: &mut String, label: &str, rows: &IndexMap<String, UsageSummary>) {
+    let width = rows
+        .keys()
+        .map(|k| k.len())
+        .max()
+        .unwrap_or(0)
+        .max(label.len());
+    let _ = writeln!(
+        out,
+        "{label:<width$}  {:>8}  {:>6}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "Requests", "Cached", "Input", "Cache read", "Output", "Cost"
+    );
+    for (key, row) in rows {
+        let _ = writeln!(
+            out,
+            "{key:<width$}  {:>8}  {:>6}  {:>10}  {:>10}  {:>10}  {:>10}",
+            row.requests,
+            row.cache_hits,
+            format_tokens(row.tokens.input_tokens),
+            format_tokens(row.tokens.cached_input_tokens),
+            format_tokens(row.tokens.output_tokens),
+            format_cost(row.cost),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(file: &str, agent: &str, model: &str, input: u64, cached: bool) -> TaskUsage {
+        let tokens = TokenUsage {
+            input_tokens: input,
+            output_tokens: 1000,
+            ..TokenUsage::default()
+        };
+        TaskUsage {
+            file: file.to_string(),
+            agent: agent.to_string(),
+            model: model.to_string(),
+            usage: if cached {
+                TokenUsage::default()
+            } else {
+                tokens
+            },
+            cached,
+            saved: if cached {
+                tokens
+            } else {
+                TokenUsage::default()
+            },
+        }
+    }
+
+    #[test]
+    fn report_aggregates_per_agent_and_file() {
+        let model = "claude-sonnet-4-20250514";
+        let report = UsageReport::new(
+            &[
+                task("a.rs", "backend", model, 1_000_000, false),
+                task("a.rs", "security", model, 1_000_000, false),
+                task("b.rs", "backend", model, 1_000_000, true),
+            ],
+            &IndexMap::new(),
+        );
+        assert_eq!(report.total.requests, 2);
+        assert_eq!(report.total.cache_hits, 1);
+        assert_eq!(report.total.tokens.input_tokens, 2_000_000);
+        assert!((report.total.cost.unwrap() - 6.03).abs() < 1e-9);
+        assert!((report.total.saved_cost.unwrap() - 3.015).abs() < 1e-9);
+
+        assert_eq!(report.by_agent["backend"].requests, 1);
+        assert_eq!(report.by_agent["backend"].cache_hits, 1);
+        assert_eq!(report.by_file["a.rs"].requests, 2);
+        assert_eq!(report.by_file["b.rs"].cost, Some(0.0));
+    }
+
+    #[test]
+    fn prompt_cache_reads_are_billed_at_the_cached_rate() {
+        let mut cached = task("a.rs", "backend", "claude-sonnet-4", 1_000_000, false);
+        cached.usage.cached_input_tokens = 800_000;
+        let report = UsageReport::new(&[cached], &IndexMap::new());
+        // 200k uncached at $3, 800k cached at $0.30, 1k output at $15
+        let expected = 0.6 + 0.24 + 0.015;
+        assert!((report.total.cost.unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_model_has_no_cost() {
+        let report = UsageReport::new(
+            &[task("a.rs", "backend", "llama3", 10, false)],
+            &IndexMap::new(),
+        );
+        assert_eq!(report.total.cost, None);
+        assert_eq!(report.total.tokens.input_tokens, 10);
+    }
+
+    #[test]
+    fn render_includes_breakdown_and_total() {
+        let report = UsageReport::new(
+            &[task("src/main.rs", "backend", "gpt-4o", 1500, false)],
+            &IndexMap::new(),
+        );
+        let out = report.render();
+        assert!(out.contains("backend"));
+        assert!(out.contains("src/main.rs"));
+        assert!(out.contains("1,500 input"));
+        assert!(!out.contains("cache hit"));
+    }
+
+    #[test]
+    fn serializes_flat_token_counts() {
+        let report = UsageReport::new(
+            &[task("a.rs", "backend", "gpt-4o", 10, false)],
+            &IndexMap::new(),
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["total"]["input_tokens"], 10);
+        assert_eq!(json["by_agent"]["backend"]["requests"], 1);
+        assert_eq!(json["by_file"]["a.rs"]["cache_hits"], 0);
+    }
+}
@@ -1,68 +1,214 @@
 //! Chunk splitter for large diffs.
 //!
-//! Splits large file diffs into smaller chunks at heuristic boundaries
-//! (blank lines, function boundaries) to fit within LLM context limits.
+//! Splits large file diffs into smaller chunks to fit within LLM context
+//! limits. Chunks break between hunks where possible; a hunk that is
+//! too large on its own (such as a whole file in `--scan` mode) is split
+//! at function, class or impl boundaries found with a brace/indent
+//! heuristic, and each piece is headed by the symbol that encloses it.
 
-use crate::models::diff::FileDiff;
+use std::sync::LazyLock;
 
-/// Split a file diff into smaller chunks if it exceeds the line limit.
+use regex::Regex;
+
+use crate::models::diff::{DiffLine, DiffLineType, FileDiff, Hunk};
+
+/// Lines that open a definition in common languages: Rust, Python,
+/// JavaScript/TypeScript, Go, Java/Kotlin/C#, Ruby, PHP and friends.
+static DEFINITION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^(?:(?:pub(?:\([^)]*\))?|export|default|async|static|public|private|protected|internal|abstract|final|sealed|open|override|unsafe|extern(?:\s+"[^"]*")?|const|data|inline)\s+)*(?:fn|impl|struct|enum|trait|mod|union|macro_rules!|class|interface|def|function|func|object|module|namespace|type)\b"#,
+    )
+    .unwrap()
+});
+
+/// Longest hunk header taken from a definition line, in characters.
+const MAX_HEADER_CHARS: usize = 80;
+
+/// Split a file diff into chunks of at most `max_lines` diff lines.
 ///
-/// Each chunk is a subset of hunks from the original diff. Returns the
-/// original diff in a single-element vec if it's small enough.
-pub fn chunk_diff<'a>(diff: &FileDiff<'a>, max_lines: Option<usize>) -> Vec<FileDiff<'a>> {
-    let max = max_lines.unwrap_or(crate::constants::DEFAULT_CHUNK_LINES);
+/// Whole hunks are grouped into chunks first. A hunk longer than
+/// `max_lines` is split into smaller hunks at function, class or impl
+/// boundaries, each headed by its enclosing symbol. Returns the original
+/// diff in a single-element vec if it's small enough.
+pub fn chunk_diff<'a>(diff: &FileDiff<'a>, max_lines: usize) -> Vec<FileDiff<'a>> {
+    let max = max_lines.max(1);
 
     let total_lines: usize = diff.hunks.iter().map(|h| h.lines.len()).sum();
     if total_lines <= max {
         return vec![diff.clone()];
     }
 
-    // Split by hunks first — each hunk becomes its own chunk
-    // If a single hunk is too large, we keep it as-is (LLM can handle it)
+    let chunk = |hunks| FileDiff {
+        old_path: diff.old_path.clone(),
+        new_path: diff.new_path.clone(),
+        is_new: diff.is_new,
+        is_deleted: diff.is_deleted,
+        is_rename: diff.is_rename,
+        is_binary: diff.is_binary,
+        hunks,
+    };
+
     let mut chunks: Vec<FileDiff> = Vec::new();
     let mut current_hunks = Vec::new();
     let mut current_lines = 0;
 
-    for hunk in &diff.hunks {
+    for hunk in diff.hunks.iter().flat_map(|h| split_hunk(h, max)) {
         let hunk_lines = hunk.lines.len();
 
         if current_lines + hunk_lines > max && !current_hunks.is_empty() {
-            // Flush current chunk
-            chunks.push(FileDiff {
-                old_path: diff.old_path.clone(),
-                new_path: diff.new_path.clone(),
-                is_new: diff.is_new,
-                is_deleted: diff.is_deleted,
-                is_rename: diff.is_rename,
-                is_binary: diff.is_binary,
-                hunks: std::mem::take(&mut current_hunks),
-            });
+            chunks.push(chunk(std::mem::take(&mut current_hunks)));
             current_lines = 0;
         }
 
-        current_hunks.push(hunk.clone());
+        current_hunks.push(hunk);
         current_lines += hunk_lines;
     }
 
     if !current_hunks.is_empty() {
-        chunks.push(FileDiff {
-            old_path: diff.old_path.clone(),
-            new_path: diff.new_path.clone(),
-            is_new: diff.is_new,
-            is_deleted: diff.is_deleted,
-            is_rename: diff.is_rename,
-            is_binary: diff.is_binary,
-            hunks: current_hunks,
-        });
+        chunks.push(chunk(current_hunks));
     }
 
     chunks
 }
 
+/// Split a hunk into pieces of at most `max_lines` lines.
+///
+/// Each cut goes before the least indented definition (or, failing
+/// that, after the last blank line) in the back three quarters of the
+/// window, keeping attributes, decorators and comments with the
+/// definition they annotate. Only a window without either is cut
+/// mid-block. Pieces keep their original line numbers and are headed
+/// by the definition that encloses their first line.
+fn split_hunk<'a>(hunk: &Hunk<'a>, max_lines: usize) -> Vec<Hunk<'a>> {
+    let max = max_lines.max(1);
+    if hunk.lines.len() <= max {
+        return vec![hunk.clone()];
+    }
+
+    let mut pieces = Vec::new();
+    let (mut old_line, mut new_line) = (hunk.old_start, hunk.new_start);
+    let mut start = 0;
+    while start < hunk.lines.len() {
+        let end = if hunk.lines.len() - start <= max {
+            hunk.lines.len()
+        } else {
+            cut_point(&hunk.lines, start + max / 4 + 1, start + max)
+        };
+
+        let lines = hunk.lines[start..end].to_vec();
+        let old_count = count(&lines, DiffLineType::Added);
+        let new_count = count(&lines, DiffLineType::Removed);
+        pieces.push(Hunk {
+            old_start: old_line,
+            old_count,
+            new_start: new_line,
+            new_count,
+            header: enclosing_symbol(&hunk.lines, start).or_else(|| hunk.header.clone()),
+            lines,
+        });
+        old_line += old_count;
+        new_line += new_count;
+        start = end;
+    }
+    pieces
+}
+
+/// Number of lines in `lines` on the side without `excluded` lines.
+fn count(lines: &[DiffLine<'_>], excluded: DiffLineType) -> u32 {
+    lines.iter().filter(|l| l.line_type != excluded).count() as u32
+}
+
+/// Pick where to end a piece: an index in `earliest..=latest`.
+fn cut_point(lines: &[DiffLine<'_>], earliest: usize, latest: usize) -> usize {
+    let definition = (earliest..=latest)
+        .filter_map(|i| definition_indent(&lines[i]).map(|indent| (indent, i)))
+        // Least indented first; among equals, the latest keeps pieces large
+        .min_by_key(|&(indent, i)| (indent, std::cmp::Reverse(i)));
+    if let Some((_, i)) = definition {
+        let attached = (earliest..i)
+            .rev()
+            .take_while(|&j| is_annotation(&lines[j]))
+            .last();
+        return attached.unwrap_or(i);
+    }
+    (earliest..latest)
+        .rev()
+        .find(|&i| is_new_side(&lines[i]) && lines[i].content.trim().is_empty())
+        .map_or(latest, |i| i + 1)
+}
+
+/// The definition line enclosing the code that starts at `lines[index]`:
+/// the nearest one at or before it that is indented no deeper, trimmed
+/// for use as a header. Leading blank lines and annotations are skipped.
+fn enclosing_symbol(lines: &[DiffLine<'_>], index: usize) -> Option<String> {
+    let first = (index..lines.len()).find(|&i| {
+        let line = &lines[i];
+        is_new_side(line) && !line.content.trim().is_empty() && !is_annotation(line)
+    })?;
+    let limit = indent(&lines[first].content);
+    lines[..=first]
+        .iter()
+        .rev()
+        .find(|l| definition_indent(l).is_some_and(|i| i <= limit))
+        .map(|l| l.content.trim().chars().take(MAX_HEADER_CHARS).collect())
+}
+
+/// The indentation of `line` if it starts a function, class, impl or
+/// other block on the new side of the diff.
+fn definition_indent(line: &DiffLine<'_>) -> Option<usize> {
+    if !is_new_side(line) {
+        return None;
+    }
+    let code = line.content.trim();
+    (DEFINITION_RE.is_match(code) || opens_signature(code)).then(|| indent(&line.content))
+}
+
+/// Whether `code` looks like a signature opening a block, such as
+/// `public void run() {` or `int main(void) {`, rather than control flow,
+/// an assignment or a call taking a closure.
+fn opens_signature(code: &str) -> bool {
+    const CONTROL: &[&str] = &[
+        "if", "else", "for", "foreach", "while", "do", "loop", "match", "switch", "try", "catch",
+        "finally", "return", "with", "unless", "until",
+    ];
+    let first_word = code
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default();
+    code.ends_with('{')
+        && code.contains('(')
+        && !code.contains('=')
+        && code.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && !CONTROL.contains(&first_word)
+}
+
+/// Whether `line` belongs to the definition below it: an attribute,
+/// decorator or comment.
+fn is_annotation(line: &DiffLine<'_>) -> bool {
+    let code = line.content.trim_start();
+    is_new_side(line)
+        && ["#[", "@", "//", "/*", "* ", "*/", "# "]
+            .iter()
+            .any(|prefix| code.starts_with(prefix))
+}
+
+/// Whether `line` exists in the new file.
+fn is_new_side(line: &DiffLine<'_>) -> bool {
+    line.line_type != DiffLineType::Removed
+}
+
+/// Leading whitespace width, counting a tab as four columns.
+fn indent(content: &str) -> usize {
+    content
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::diff::{DiffLine, DiffLineType, Hunk};
 
     fn make_hunk(line_count: usize) -> Hunk<'static> {
         Hunk {
@@ -93,7 +239,7 @@ mod tests {
             is_binary: false,
             hunks: vec![make_hunk(10)],
         };
-        let chunks = chunk_diff(&diff, Some(500));
+        let chunks = chunk_diff(&diff, 500);
         assert_eq!(chunks.len(), 1);
     }
 
@@ -108,10 +254,118 @@ mod tests {
             is_binary: false,
             hunks: vec![make_hunk(100), make_hunk(100), make_hunk(100)],
         };
-        let chunks = chunk_diff(&diff, Some(150));
+        let chunks = chunk_diff(&diff, 150);
         assert_eq!(chunks.len(), 3);
         for chunk in &chunks {
             assert_eq!(chunk.hunks.len(), 1);
         }
     }
+
+    /// A scanned-file hunk: every line added, numbered from 1.
+    fn added_hunk(source: &str) -> Hunk<'static> {
+        let lines: Vec<DiffLine> = source
+            .lines()
+            .enumerate()
+            .map(|(i, line)| DiffLine {
+                line_type: DiffLineType::Added,
+                content: std::borrow::Cow::Owned(line.to_string()),
+                old_line_no: None,
+                new_line_no: Some(i as u32 + 1),
+            })
+            .collect();
+        Hunk {
+            old_start: 0,
+            old_count: 0,
+            new_start: 1,
+            new_count: lines.len() as u32,
+            header: None,
+            lines,
+        }
+    }
+
+    fn rust_source(functions: usize, body_lines: usize) -> String {
+        let mut source = String::from("impl Engine {\n");
+        for f in 0..functions {
+            source.push_str(&format!(
+                "    /// Step {f}.\n    pub fn step_{f}(&self) {{\n"
+            ));
+            for i in 0..body_lines {
+                source.push_str(&format!("        let x{i} = {i};\n"));
+            }
+            source.push_str("    }\n\n");
+        }
+        source.push_str("}\n");
+        source
+    }
+
+    #[test]
+    fn oversized_hunk_split_at_function_boundaries() {
+        let hunk = added_hunk(&rust_source(6, 20));
+        let pieces = split_hunk(&hunk, 50);
+
+        assert!(pieces.len() > 1);
+        assert!(pieces.iter().all(|p| p.lines.len() <= 50));
+        let total: usize = pieces.iter().map(|p| p.lines.len()).sum();
+        assert_eq!(total, hunk.lines.len());
+        for piece in &pieces[1..] {
+            // Each piece starts with a function's doc comment
+            assert!(piece.lines[0].content.trim_start().starts_with("/// Step"));
+            let header = piece.header.as_deref().unwrap();
+            assert!(header.starts_with("pub fn step_"), "header: {header}");
+            assert_eq!(
+                piece.new_start,
+                piece.lines[0].new_line_no.unwrap(),
+                "pieces keep their line numbers"
+            );
+            assert_eq!(piece.new_count as usize, piece.lines.len());
+        }
+    }
+
+    #[test]
+    fn long_function_split_mid_body_keeps_enclosing_header() {
+        let hunk = added_hunk(&rust_source(1, 120));
+        let pieces = split_hunk(&hunk, 50);
+
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].header.as_deref(), Some("impl Engine {"));
+        assert_eq!(pieces[1].header.as_deref(), Some("pub fn step_0(&self) {"));
+        assert_eq!(pieces[2].header.as_deref(), Some("pub fn step_0(&self) {"));
+    }
+
+    #[test]
+    fn python_definitions_and_decorators_recognized() {
+        let mut source = String::new();
+        for f in 0..4 {
+            source.push_str(&format!("@cached\ndef handler_{f}(request):\n"));
+            for i in 0..15 {
+                source.push_str(&format!("    value_{i} = request.get({i})\n"));
+            }
+            source.push('\n');
+        }
+        let pieces = split_hunk(&added_hunk(&source), 40);
+        assert!(pieces.len() > 1);
+        for piece in &pieces[1..] {
+            assert_eq!(piece.lines[0].content, "@cached");
+            assert!(piece.header.as_deref().unwrap().starts_with("def handler_"));
+        }
+    }
+
+    #[test]
+    fn scanned_file_chunked_within_limit() {
+        let diff = FileDiff {
+            old_path: "src/engine.rs".into(),
+            new_path: "src/engine.rs".into(),
+            is_new: true,
+            is_deleted: false,
+            is_rename: false,
+            is_binary: false,
+            hunks: vec![added_hunk(&rust_source(40, 100))],
+        };
+        let chunks = chunk_diff(&diff, 500);
+        assert!(chunks.len() >= 8);
+        for chunk in &chunks {
+            let lines: usize = chunk.hunks.iter().map(|h| h.lines.len()).sum();
+            assert!(lines <= 500);
+        }
+    }
 }
@@ -0,0 +1,269 @@
+//! Path filters applied to diffs before review.
+//!
+//! Keeps lockfiles, minified bundles, snapshots, vendored and generated
+//! code away from the LLM. A file is reviewed when it matches an
+//! `--include` glob (if any were given), matches no exclude glob, and is
+//! not marked `linguist-generated` or `-diff` in the repo's root
+//! `.gitattributes`.
+
+use std::path::Path;
+
+use crate::glob;
+use crate::models::diff::FileDiff;
+
+/// Include/exclude rules for the files in a review.
+#[derive(Debug, Clone, Default)]
+pub struct PathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    attributes: Vec<AttributeRule>,
+}
+
+/// A `.gitattributes` line that sets or unsets a skip marker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AttributeRule {
+    pattern: String,
+    /// `Some(true)` marks matching files as skipped, `Some(false)` clears
+    /// an earlier mark; `None` leaves them untouched.
+    generated: Option<bool>,
+    no_diff: Option<bool>,
+}
+
+impl PathFilter {
+    /// Create a filter from include and exclude globs.
+    ///
+    /// An empty include list admits every path.
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Self {
+        Self {
+            include,
+            exclude,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Also skip files marked generated or non-diffable in the
+    /// `.gitattributes` at `repo_root`, if there is one.
+    pub async fn with_gitattributes(self, repo_root: &Path) -> Self {
+        let path = repo_root.join(crate::constants::GITATTRIBUTES_FILENAME);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => self.with_attributes(&content),
+            Err(_) => self,
+        }
+    }
+
+    /// Also skip files marked generated or non-diffable by `content`, a
+    /// `.gitattributes` file read from elsewhere (e.g. a forge).
+    pub fn with_attributes(mut self, content: &str) -> Self {
+        self.attributes = parse_gitattributes(content);
+        self
+    }
+
+    /// Whether `path` should be reviewed.
+    pub fn allows(&self, path: &str) -> bool {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        if !self.include.is_empty() && !glob::matches_any(path, &self.include) {
+            return false;
+        }
+        !glob::matches_any(path, &self.exclude) && !self.is_generated(path)
+    }
+
+    /// Keep only the diffs whose path passes the filter.
+    ///
+    /// Returns the kept diffs and the number that were dropped.
+    pub fn apply<'a>(&self, diffs: &[FileDiff<'a>]) -> (Vec<FileDiff<'a>>, usize) {
+        let kept: Vec<FileDiff<'a>> = diffs
+            .iter()
+            .filter(|d| self.allows(d.path()))
+            .cloned()
+            .collect();
+        let dropped = diffs.len() - kept.len();
+        (kept, dropped)
+    }
+
+    /// Resolve the skip markers for `path`; later lines win, as in git.
+    fn is_generated(&self, path: &str) -> bool {
+        let mut generated = false;
+        let mut no_diff = false;
+        for rule in self
+            .attributes
+            .iter()
+            .filter(|r| attribute_pattern_matches(&r.pattern, path))
+        {
+            if let Some(value) = rule.generated {
+                generated = value;
+            }
+            if let Some(value) = rule.no_diff {
+                no_diff = value;
+            }
+        }
+        generated || no_diff
+    }
+}
+
+/// Parse the `linguist-generated` and `diff` attributes from a
+/// `.gitattributes` file. Other attributes are ignored.
+fn parse_gitattributes(content: &str) -> Vec<AttributeRule> {
+    let mut rules = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let Some(pattern) = parts.next() else {
+            continue;
+        };
+        let mut rule = AttributeRule {
+            pattern: pattern.to_string(),
+            generated: None,
+            no_diff: None,
+        };
+        for attr in parts {
+            match attr {
+                "linguist-generated" | "linguist-generated=true" => rule.generated = Some(true),
+                "-linguist-generated" | "linguist-generated=false" | "!linguist-generated" => {
+                    rule.generated = Some(false)
+                }
+                // `binary` is a macro for `-diff -merge -text`
+                "-diff" | "binary" => rule.no_diff = Some(true),
+                "diff" | "!diff" => rule.no_diff = Some(false),
+                _ => {}
+            }
+        }
+        if rule.generated.is_some() || rule.no_diff.is_some() {
+            rules.push(rule);
+        }
+    }
+    rules
+}
+
+/// Match a `.gitattributes` pattern: patterns without a `/` match the
+/// file name at any depth, others are anchored at the repo root.
+fn attribute_pattern_matches(pattern: &str, path: &str) -> bool {
+    if pattern.contains('/') {
+        glob::glob_match(pattern.trim_start_matches('/'), path)
+    } else {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        glob::glob_match(pattern, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> Vec<String> {
+        crate::constants::DEFAULT_EXCLUDES
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn make_diff(path: &str) -> FileDiff<'static> {
+        FileDiff {
+            old_path: path.to_string(),
+            new_path: path.to_string(),
+            is_new: false,
+            is_deleted: false,
+            is_rename: false,
+            is_binary: false,
+            hunks: vec![],
+        }
+    }
+
+    #[test]
+    fn default_excludes_skip_noise() {
+        let filter = PathFilter::new(Vec::new(), defaults());
+        assert!(!filter.allows("Cargo.lock"));
+        assert!(!filter.allows("web/package-lock.json"));
+        assert!(!filter.allows("static/app.min.js"));
+        assert!(!filter.allows("tests/__snapshots__/render.snap"));
+        assert!(!filter.allows("vendor/github.com/pkg/errors/errors.go"));
+        assert!(filter.allows("src/main.rs"));
+        assert!(filter.allows("web/app.js"));
+    }
+
+    #[test]
+    fn include_restricts_to_matching_paths() {
+        let filter = PathFilter::new(vec!["src/**".to_string()], Vec::new());
+        assert!(filter.allows("src/lib.rs"));
+        assert!(filter.allows("./src/lib.rs"));
+        assert!(!filter.allows("docs/index.md"));
+    }
+
+    #[test]
+    fn exclude_wins_over_include() {
+        let filter = PathFilter::new(
+            vec!["src/**".to_string()],
+            vec!["src/generated/**".to_string()],
+        );
+        assert!(filter.allows("src/lib.rs"));
+        assert!(!filter.allows("src/generated/schema.rs"));
+    }
+
+    #[test]
+    fn apply_counts_dropped_diffs() {
+        let filter = PathFilter::new(Vec::new(), defaults());
+        let diffs = vec![make_diff("src/main.rs"), make_diff("Cargo.lock")];
+        let (kept, dropped) = filter.apply(&diffs);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].path(), "src/main.rs");
+        assert_eq!(dropped, 1);
+    }
+
+    #[test]
+    fn parse_gitattributes_markers() {
+        let rules = parse_gitattributes(
+            "# comment\n\
+             *.pb.go linguist-generated=true\n\
+             docs/*.svg -diff\n\
+             *.png binary\n\
+             *.rs text eol=lf\n\
+             api/keep.pb.go -linguist-generated\n",
+        );
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules[0].generated, Some(true));
+        assert_eq!(rules[1].no_diff, Some(true));
+        assert_eq!(rules[2].no_diff, Some(true));
+        assert_eq!(rules[3].generated, Some(false));
+    }
+
+    #[test]
+    fn gitattributes_markers_skip_files() {
+        let filter = PathFilter::new(Vec::new(), Vec::new()).with_attributes(
+            "*.pb.go linguist-generated\n\
+             /docs/*.svg -diff\n\
+             api/keep.pb.go -linguist-generated\n",
+        );
+        assert!(!filter.allows("api/user.pb.go"));
+        assert!(!filter.allows("user.pb.go"));
+        assert!(!filter.allows("docs/diagram.svg"));
+        assert!(filter.allows("web/docs/diagram.svg"));
+        assert!(filter.allows("api/keep.pb.go"));
+        assert!(filter.allows("api/user.go"));
+    }
+
+    #[tokio::test]
+    async fn with_gitattributes_reads_repo_root() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".gitattributes"),
+            "gen/** linguist-generated\n",
+        )
+        .unwrap();
+        let filter = PathFilter::new(Vec::new(), Vec::new())
+            .with_gitattributes(dir.path())
+            .await;
+        assert!(!filter.allows("gen/models.rs"));
+        assert!(filter.allows("src/models.rs"));
+    }
+
+    #[tokio::test]
+    async fn with_gitattributes_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let filter = PathFilter::new(Vec::new(), Vec::new())
+            .with_gitattributes(dir.path())
+            .await;
+        assert!(filter.allows("gen/models.rs"));
+    }
+}
@@ -0,0 +1,680 @@
+//! Pull request retrieval from forge REST APIs.
+//!
+//! `--pr` reviews fetch the pull request's unified diff, title,
+//! description and head file contents from GitHub, GitLab, Forgejo or
+//! Bitbucket instead of a local git checkout. API URLs and tokens come
+//! from the same environment variables the publishers in
+//! [`crate::output`] read. The repository is taken from the CI
+//! environment, or from the `origin` remote when those are unset.
+
+use std::path::Path;
+
+use indexmap::IndexMap;
+use thiserror::Error;
+
+use crate::diff::git;
+use crate::env::Env;
+use crate::models::Forge;
+use crate::models::diff::FileDiff;
+
+/// Default GitHub REST API base URL (overridden by `GITHUB_API_URL`).
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Default GitLab REST API base URL (overridden by `CI_API_V4_URL`).
+const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// Default Bitbucket Cloud API base URL (overridden by `BITBUCKET_API_URL`).
+const BITBUCKET_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Page size for GitLab's merge request diff listing.
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Errors from fetching a pull request.
+#[derive(Error, Debug)]
+pub enum ForgeError {
+    #[error("missing environment variable: {0}")]
+    MissingEnvVar(String),
+
+    #[error("cannot determine the {0} repository: set {1} or add an origin remote")]
+    UnknownRepository(Forge, &'static str),
+
+    #[error("API request failed: {0}")]
+    ApiError(String),
+}
+
+/// A pull request fetched from a forge, with its unified diff.
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub description: String,
+    /// Commit at the head of the pull request, used to read file contents.
+    pub head_sha: String,
+    /// The pull request's changes as a unified diff.
+    pub diff: String,
+    api: ForgeApi,
+}
+
+impl PullRequest {
+    /// The cache and history scope for this pull request, e.g.
+    /// `pr/github/acme/app/42`.
+    ///
+    /// Derived from the forge rather than the local branch, which for
+    /// `--pr` reviews is unrelated to the pull request.
+    pub fn review_scope(&self) -> String {
+        format!(
+            "pr/{}/{}/{}",
+            self.api.forge, self.api.repository, self.number
+        )
+    }
+
+    /// Fetch one file at the head commit, or `None` if the forge can't
+    /// serve it (e.g. it doesn't exist).
+    pub async fn fetch_file(&self, path: &str) -> Option<String> {
+        self.api.file(path, &self.head_sha).await.ok()
+    }
+
+    /// Fetch the head contents of every changed file in `diffs`.
+    ///
+    /// Deleted and binary files are skipped, as are files the API
+    /// can't serve; those are reviewed from the diff's lines alone.
+    pub async fn fetch_contents(&self, diffs: &[FileDiff<'_>]) -> IndexMap<String, String> {
+        let mut contents = IndexMap::new();
+        for diff in diffs {
+            if diff.is_deleted || diff.is_binary {
+                continue;
+            }
+            match self.api.file(diff.path(), &self.head_sha).await {
+                Ok(content) => {
+                    contents.insert(diff.path().to_string(), content);
+                }
+                Err(e) => eprintln!("Warning: failed to fetch {}: {e}", diff.path()),
+            }
+        }
+        contents
+    }
+}
+
+/// Fetch pull request `number` from `forge`.
+///
+/// # Environment variables
+///
+/// | Forge | API URL | Repository | Token |
+/// |---|---|---|---|
+/// | GitHub | `GITHUB_API_URL` (optional) | `GITHUB_REPOSITORY` | `GITHUB_TOKEN` |
+/// | GitLab | `CI_API_V4_URL` (optional) | `CI_PROJECT_ID` | `GITLAB_TOKEN` |
+/// | Forgejo | `CI_FORGE_URL` | `CI_REPO_OWNER`, `CI_REPO_NAME` | `FORGEJO_TOKEN` |
+/// | Bitbucket | `BITBUCKET_API_URL` (optional) | `BITBUCKET_WORKSPACE`, `BITBUCKET_REPO_SLUG` | `BITBUCKET_TOKEN` (not needed in Pipelines) |
+///
+/// When the repository variables are unset, the repository is read
+/// from the `origin` remote of `repo_root`.
+pub async fn fetch_pull_request(
+    forge: Forge,
+    number: u64,
+    repo_root: &Path,
+    env: &Env,
+) -> Result<PullRequest, ForgeError> {
+    let api = ForgeApi::from_env(forge, repo_root, env).await?;
+    let (title, description, head_sha) = api.metadata(number).await?;
+    if head_sha.is_empty() {
+        return Err(ForgeError::ApiError(format!(
+            "pull request {number} has no head commit"
+        )));
+    }
+    let diff = api.diff(number).await?;
+
+    Ok(PullRequest {
+        number,
+        title,
+        description,
+        head_sha,
+        diff,
+        api,
+    })
+}
+
+/// Fetch only the title and description of pull request `number`,
+/// with the same environment as [`fetch_pull_request`].
+pub async fn fetch_description(
+    forge: Forge,
+    number: u64,
+    repo_root: &Path,
+    env: &Env,
+) -> Result<(String, String), ForgeError> {
+    let api = ForgeApi::from_env(forge, repo_root, env).await?;
+    let (title, description, _) = api.metadata(number).await?;
+    Ok((title, description))
+}
+
+/// Authenticated access to one repository's API endpoints.
+struct ForgeApi {
+    forge: Forge,
+    client: reqwest::Client,
+    /// Repository path (`owner/repo`) or GitLab project ID.
+    repository: String,
+    repo_url: String,
+    /// Authentication header name and value, if any.
+    auth: Option<(&'static str, String)>,
+}
+
+impl ForgeApi {
+    /// Resolve the API URL, repository and credentials for `forge`.
+    async fn from_env(forge: Forge, repo_root: &Path, env: &Env) -> Result<Self, ForgeError> {
+        let repository = |vars: &[&str]| {
+            let values: Option<Vec<String>> = vars.iter().map(|v| env.var(v).ok()).collect();
+            values.map(|v| v.join("/"))
+        };
+        let remote = || async {
+            git::origin_url(repo_root)
+                .await
+                .and_then(|url| remote_repository(&url))
+        };
+        let api_url = |name: &str, default: &str| {
+            env.var(name)
+                .unwrap_or_else(|_| default.to_string())
+                .trim_end_matches('/')
+                .to_string()
+        };
+
+        let mut client = crate::http::build_client();
+        let (repo_url, repository, auth) = match forge {
+            Forge::Github => {
+                let repo = match repository(&["GITHUB_REPOSITORY"]) {
+                    Some(repo) => repo,
+                    None => remote()
+                        .await
+                        .ok_or(ForgeError::UnknownRepository(forge, "GITHUB_REPOSITORY"))?,
+                };
+                let token = require_env(env, "GITHUB_TOKEN")?;
+                (
+                    format!("{}/repos/{repo}", api_url("GITHUB_API_URL", GITHUB_API_URL)),
+                    repo,
+                    Some(("Authorization", format!("Bearer {token}"))),
+                )
+            }
+            Forge::Gitlab => {
+                let project = match repository(&["CI_PROJECT_ID"]) {
+                    Some(id) => id,
+                    None => remote()
+                        .await
+                        .ok_or(ForgeError::UnknownRepository(forge, "CI_PROJECT_ID"))?,
+                };
+                let token = require_env(env, "GITLAB_TOKEN")?;
+                (
+                    format!(
+                        "{}/projects/{}",
+                        api_url("CI_API_V4_URL", GITLAB_API_URL),
+                        encode_path(&project, false)
+                    ),
+                    project,
+                    Some(("PRIVATE-TOKEN", token)),
+                )
+            }
+            Forge::Forgejo => {
+                let forge_url = require_env(env, "CI_FORGE_URL")?;
+                let repo = match repository(&["CI_REPO_OWNER", "CI_REPO_NAME"]) {
+                    Some(repo) => repo,
+                    None => remote().await.ok_or(ForgeError::UnknownRepository(
+                        forge,
+                        "CI_REPO_OWNER and CI_REPO_NAME",
+                    ))?,
+                };
+                let token = require_env(env, "FORGEJO_TOKEN")?;
+                (
+                    format!("{}/api/v1/repos/{repo}", forge_url.trim_end_matches('/')),
+                    repo,
+                    Some(("Authorization", format!("token {token}"))),
+                )
+            }
+            Forge::Bitbucket => {
+                let repo = match repository(&["BITBUCKET_WORKSPACE", "BITBUCKET_REPO_SLUG"]) {
+                    Some(repo) => repo,
+                    None => remote().await.ok_or(ForgeError::UnknownRepository(
+                        forge,
+                        "BITBUCKET_WORKSPACE and BITBUCKET_REPO_SLUG",
+                    ))?,
+                };
+                // Inside Pipelines the local proxy injects credentials and
+                // expects plain http://, as for the publisher.
+                let in_pipelines = env.is_set("BITBUCKET_BUILD_NUMBER");
+                let (default_url, auth) = if in_pipelines {
+                    client = crate::http::build_bitbucket_pipelines_client();
+                    (BITBUCKET_API_URL.replacen("https", "http", 1), None)
+                } else {
+                    let token = require_env(env, "BITBUCKET_TOKEN")?;
+                    (
+                        BITBUCKET_API_URL.to_string(),
+                        Some(("Authorization", format!("Bearer {token}"))),
+                    )
+                };
+                (
+                    format!(
+                        "{}/repositories/{repo}",
+                        api_url("BITBUCKET_API_URL", &default_url)
+                    ),
+                    repo,
+                    auth,
+                )
+            }
+        };
+
+        Ok(Self {
+            forge,
+            client: client
+                .map_err(|e| ForgeError::ApiError(format!("failed to build HTTP client: {e}")))?,
+            repository,
+            repo_url,
+            auth,
+        })
+    }
+
+    /// Path of the pull request resource, relative to the repository URL.
+    fn pull_path(&self, number: u64) -> String {
+        match self.forge {
+            Forge::Github | Forge::Forgejo => format!("/pulls/{number}"),
+            Forge::Gitlab => format!("/merge_requests/{number}"),
+            Forge::Bitbucket => format!("/pullrequests/{number}"),
+        }
+    }
+
+    /// Start a GET request to `path` relative to the repository URL.
+    fn request(&self, path: &str) -> reqwest::RequestBuilder {
+        self.request_as(path, "application/vnd.github+json")
+    }
+
+    /// Like [`request`](Self::request), asking GitHub for `media_type`
+    /// (e.g. the diff or raw file representation).
+    fn request_as(&self, path: &str, media_type: &str) -> reqwest::RequestBuilder {
+        let mut request = self.client.get(format!("{}{path}", self.repo_url));
+        if let Some((name, value)) = &self.auth {
+            request = request.header(*name, value);
+        }
+        if self.forge == Forge::Github {
+            request = request
+                .header("Accept", media_type)
+                .header("X-GitHub-Api-Version", "2022-11-28");
+        }
+        request
+    }
+
+    /// Send a request and return the body, turning non-success statuses
+    /// into errors.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        what: &str,
+    ) -> Result<String, ForgeError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ForgeError::ApiError(e.to_string()))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ForgeError::ApiError(format!("{what} response: {e}")))?;
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(ForgeError::ApiError(format!(
+                "{what} failed with HTTP {status}: {body}"
+            )))
+        }
+    }
+
+    /// Fetch and parse a JSON resource.
+    async fn json(&self, path: &str, what: &str) -> Result<serde_json::Value, ForgeError> {
+        let body = self.send(self.request(path), what).await?;
+        serde_json::from_str(&body)
+            .map_err(|e| ForgeError::ApiError(format!("invalid {what} response: {e}")))
+    }
+
+    /// Fetch the pull request's title, description and head commit.
+    async fn metadata(&self, number: u64) -> Result<(String, String, String), ForgeError> {
+        let pr = self
+            .json(&self.pull_path(number), "pull request lookup")
+            .await?;
+        let text = |value: &serde_json::Value| value.as_str().unwrap_or_default().to_string();
+        Ok(match self.forge {
+            Forge::Github | Forge::Forgejo => (
+                text(&pr["title"]),
+                text(&pr["body"]),
+                text(&pr["head"]["sha"]),
+            ),
+            Forge::Gitlab => (
+                text(&pr["title"]),
+                text(&pr["description"]),
+                text(&pr["sha"]),
+            ),
+            Forge::Bitbucket => (
+                text(&pr["title"]),
+                text(&pr["description"]),
+                text(&pr["source"]["commit"]["hash"]),
+            ),
+        })
+    }
+
+    /// Fetch the pull request's changes as a unified diff.
+    async fn diff(&self, number: u64) -> Result<String, ForgeError> {
+        let pull = self.pull_path(number);
+        let request = match self.forge {
+            Forge::Github => self.request_as(&pull, "application/vnd.github.diff"),
+            Forge::Forgejo => self.request(&format!("{pull}.diff")),
+            Forge::Bitbucket => self.request(&format!("{pull}/diff")),
+            // GitLab only lists per-file hunks, so the headers are rebuilt
+            Forge::Gitlab => {
+                let mut diff = String::new();
+                for page in 1.. {
+                    let request = self
+                        .request(&format!("{pull}/diffs"))
+                        .query(&[("per_page", LIST_PAGE_SIZE), ("page", page)]);
+                    let body = self.send(request, "diff listing").await?;
+                    let batch: Vec<serde_json::Value> = serde_json::from_str(&body)
+                        .map_err(|e| ForgeError::ApiError(format!("invalid diff listing: {e}")))?;
+                    for file in &batch {
+                        diff.push_str(&gitlab_file_diff(file));
+                    }
+                    if batch.len() < LIST_PAGE_SIZE {
+                        break;
+                    }
+                }
+                return Ok(diff);
+            }
+        };
+        self.send(request, "diff download").await
+    }
+
+    /// Fetch the raw contents of `path` at commit `sha`.
+    async fn file(&self, path: &str, sha: &str) -> Result<String, ForgeError> {
+        let request = match self.forge {
+            Forge::Github => self
+                .request_as(
+                    &format!("/contents/{}", encode_path(path, true)),
+                    "application/vnd.github.raw",
+                )
+                .query(&[("ref", sha)]),
+            Forge::Gitlab => self
+                .request(&format!(
+                    "/repository/files/{}/raw",
+                    encode_path(path, false)
+                ))
+                .query(&[("ref", sha)]),
+            Forge::Forgejo => self
+                .request(&format!("/raw/{}", encode_path(path, true)))
+                .query(&[("ref", sha)]),
+            Forge::Bitbucket => self.request(&format!("/src/{sha}/{}", encode_path(path, true))),
+        };
+        self.send(request, "file download").await
+    }
+}
+
+/// Read a required environment variable or return a [`ForgeError`].
+fn require_env(env: &Env, name: &str) -> Result<String, ForgeError> {
+    env.var(name)
+        .map_err(|_| ForgeError::MissingEnvVar(name.into()))
+}
+
+/// Rebuild one file's unified diff from a GitLab `diffs` entry, which
+/// carries the hunks without the `diff --git` and `---`/`+++` headers.
+fn gitlab_file_diff(file: &serde_json::Value) -> String {
+    let old_path = file["old_path"].as_str().unwrap_or_default();
+    let new_path = file["new_path"].as_str().unwrap_or_default();
+    let flag = |name: &str| file[name].as_bool().unwrap_or(false);
+
+    let mut diff = format!("diff --git a/{old_path} b/{new_path}\n");
+    if flag("new_file") {
+        diff.push_str("new file mode 100644\n");
+    } else if flag("deleted_file") {
+        diff.push_str("deleted file mode 100644\n");
+    } else if flag("renamed_file") {
+        diff.push_str(&format!("rename from {old_path}\nrename to {new_path}\n"));
+    }
+
+    let hunks = file["diff"].as_str().unwrap_or_default();
+    if !hunks.is_empty() {
+        let old = if flag("new_file") {
+            "/dev/null".to_string()
+        } else {
+            format!("a/{old_path}")
+        };
+        let new = if flag("deleted_file") {
+            "/dev/null".to_string()
+        } else {
+            format!("b/{new_path}")
+        };
+        diff.push_str(&format!("--- {old}\n+++ {new}\n{hunks}"));
+        if !hunks.ends_with('\n') {
+            diff.push('\n');
+        }
+    }
+    diff
+}
+
+/// Extract the `owner/repo` path from a remote URL: SSH
+/// (`git@host:owner/repo.git`), `ssh://` or HTTP(S).
+fn remote_repository(url: &str) -> Option<String> {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then(|| path.to_string())
+}
+
+/// Percent-encode a URL path, keeping slashes unless `keep_slashes` is
+/// false (GitLab expects file paths and project paths as one segment).
+fn encode_path(path: &str, keep_slashes: bool) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if keep_slashes => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::parser;
+    use crate::http::mock::{MockServer, Route};
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@
+ fn keep() {}
+-fn old() {}
++fn new() {}
+";
+
+    #[tokio::test]
+    async fn fetches_github_pull_request() {
+        let pr = r#"{"title":"Rename old","body":"Because.","head":{"sha":"abc123"}}"#;
+        let server = MockServer::start(vec![
+            Route::new("GET", "/repos/acme/app/pulls/7", 200, pr),
+            Route::new("GET", "/repos/acme/app/pulls/7", 200, DIFF),
+            Route::new(
+                "GET",
+                "/repos/acme/app/contents/src/lib.rs",
+                200,
+                "fn keep() {}\nfn new() {}\n",
+            ),
+        ])
+        .await;
+        let env = Env::mock([
+            ("GITHUB_API_URL", server.url.as_str()),
+            ("GITHUB_REPOSITORY", "acme/app"),
+            ("GITHUB_TOKEN", "tok"),
+        ]);
+
+        let pr = fetch_pull_request(Forge::Github, 7, Path::new("."), &env)
+            .await
+            .unwrap();
+        assert_eq!(pr.title, "Rename old");
+        assert_eq!(pr.description, "Because.");
+        assert_eq!(pr.head_sha, "abc123");
+        assert_eq!(pr.review_scope(), "pr/github/acme/app/7");
+        let diffs = parser::parse_unified_diff(&pr.diff);
+        let contents = pr.fetch_contents(&diffs).await;
+        assert_eq!(contents["src/lib.rs"], "fn keep() {}\nfn new() {}\n");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].header("authorization"), Some("Bearer tok"));
+        assert_eq!(
+            requests[1].header("accept"),
+            Some("application/vnd.github.diff")
+        );
+        assert_eq!(
+            requests[2].path,
+            "/repos/acme/app/contents/src/lib.rs?ref=abc123"
+        );
+    }
+
+    #[tokio::test]
+    async fn rebuilds_gitlab_diff_headers() {
+        let mr = r#"{"title":"Add docs","description":"Docs.","sha":"def456"}"#;
+        let diffs = serde_json::json!([
+            {
+                "old_path": "docs/new.md",
+                "new_path": "docs/new.md",
+                "new_file": true,
+                "deleted_file": false,
+                "renamed_file": false,
+                "diff": "@@ -0,0 +1 @@\n+hello\n",
+            },
+        ])
+        .to_string();
+        let server = MockServer::start(vec![
+            Route::new("GET", "/projects/42/merge_requests/3", 200, mr),
+            Route::new("GET", "/projects/42/merge_requests/3/diffs", 200, &diffs),
+            Route::new(
+                "GET",
+                "/projects/42/repository/files/docs%2Fnew.md/raw",
+                200,
+                "hello\n",
+            ),
+        ])
+        .await;
+        let env = Env::mock([
+            ("CI_API_V4_URL", server.url.as_str()),
+            ("CI_PROJECT_ID", "42"),
+            ("GITLAB_TOKEN", "tok"),
+        ]);
+
+        let pr = fetch_pull_request(Forge::Gitlab, 3, Path::new("."), &env)
+            .await
+            .unwrap();
+        assert_eq!(pr.head_sha, "def456");
+        assert_eq!(pr.review_scope(), "pr/gitlab/42/3");
+        let diffs = parser::parse_unified_diff(&pr.diff);
+        assert_eq!(diffs.len(), 1);
+        assert!(diffs[0].is_new);
+        assert_eq!(diffs[0].new_path, "docs/new.md");
+        let contents = pr.fetch_contents(&diffs).await;
+        assert_eq!(contents["docs/new.md"], "hello\n");
+        assert_eq!(pr.fetch_file("REVIEW.md").await, None);
+        assert_eq!(server.requests()[0].header("private-token"), Some("tok"));
+    }
+
+    #[tokio::test]
+    async fn fetches_forgejo_and_bitbucket_pull_requests() {
+        let server = MockServer::start(vec![
+            Route::new(
+                "GET",
+                "/api/v1/repos/user/repo/pulls/5",
+                200,
+                r#"{"title":"T","body":null,"head":{"sha":"aaa"}}"#,
+            ),
+            Route::new("GET", "/api/v1/repos/user/repo/pulls/5.diff", 200, DIFF),
+            Route::new(
+                "GET",
+                "/repositories/ws/repo/pullrequests/9",
+                200,
+                r#"{"title":"B","description":"D","source":{"commit":{"hash":"bbb"}}}"#,
+            ),
+            Route::new(
+                "GET",
+                "/repositories/ws/repo/pullrequests/9/diff",
+                200,
+                DIFF,
+            ),
+        ])
+        .await;
+
+        let env = Env::mock([
+            ("CI_FORGE_URL", server.url.as_str()),
+            ("CI_REPO_OWNER", "user"),
+            ("CI_REPO_NAME", "repo"),
+            ("FORGEJO_TOKEN", "tok"),
+        ]);
+        let pr = fetch_pull_request(Forge::Forgejo, 5, Path::new("."), &env)
+            .await
+            .unwrap();
+        assert_eq!((pr.title.as_str(), pr.description.as_str()), ("T", ""));
+        assert_eq!(pr.diff, DIFF);
+
+        let env = Env::mock([
+            ("BITBUCKET_API_URL", server.url.as_str()),
+            ("BITBUCKET_WORKSPACE", "ws"),
+            ("BITBUCKET_REPO_SLUG", "repo"),
+            ("BITBUCKET_TOKEN", "tok"),
+        ]);
+        let pr = fetch_pull_request(Forge::Bitbucket, 9, Path::new("."), &env)
+            .await
+            .unwrap();
+        assert_eq!(pr.head_sha, "bbb");
+        assert_eq!(pr.description, "D");
+
+        let requests = server.requests();
+        assert_eq!(requests[0].header("authorization"), Some("token tok"));
+        assert_eq!(requests[2].header("authorization"), Some("Bearer tok"));
+    }
+
+    #[tokio::test]
+    async fn reports_missing_token_and_api_errors() {
+        let env = Env::mock([("GITHUB_REPOSITORY", "acme/app")]);
+        let err = fetch_pull_request(Forge::Github, 1, Path::new("."), &env)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("GITHUB_TOKEN"), "got: {err}");
+
+        let server = MockServer::start(vec![]).await;
+        let env = Env::mock([
+            ("GITHUB_API_URL", server.url.as_str()),
+            ("GITHUB_REPOSITORY", "acme/app"),
+            ("GITHUB_TOKEN", "tok"),
+        ]);
+        let err = fetch_pull_request(Forge::Github, 1, Path::new("."), &env)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("HTTP 404"), "got: {err}");
+    }
+
+    #[test]
+    fn parses_remote_repositories() {
+        assert_eq!(
+            remote_repository("git@github.com:acme/app.git").as_deref(),
+            Some("acme/app")
+        );
+        assert_eq!(
+            remote_repository("https://gitlab.com/group/sub/app").as_deref(),
+            Some("group/sub/app")
+        );
+        assert_eq!(
+            remote_repository("ssh://git@codeberg.org:2222/user/repo.git/").as_deref(),
+            Some("user/repo")
+        );
+        assert_eq!(remote_repository("/srv/git/app.git"), None);
+        assert_eq!(encode_path("a b/c.rs", true), "a%20b/c.rs");
+        assert_eq!(encode_path("group/app", false), "group%2Fapp");
+    }
+}
@@ -6,11 +6,45 @@ use std::path::Path;
 
 use super::DiffError;
 use crate::env::Env;
+use crate::models::GitRange;
 
 /// Run `git diff <base_ref>` and return the unified diff output.
 pub async fn git_diff(repo_root: &Path, base_ref: &str) -> Result<String, DiffError> {
+    run_diff(repo_root, "diff", &[base_ref]).await
+}
+
+/// Run `git diff --cached`: the changes staged in the index.
+pub async fn git_diff_staged(repo_root: &Path) -> Result<String, DiffError> {
+    run_diff(repo_root, "diff", &["--cached"]).await
+}
+
+/// Run `git diff`: the working-tree changes that are not staged.
+pub async fn git_diff_working_tree(repo_root: &Path) -> Result<String, DiffError> {
+    run_diff(repo_root, "diff", &[]).await
+}
+
+/// Diff a single commit against its first parent (or the empty tree for
+/// a root commit).
+pub async fn git_diff_commit(repo_root: &Path, commit: &str) -> Result<String, DiffError> {
+    run_diff(
+        repo_root,
+        "diff-tree",
+        &["--no-commit-id", "--root", "-m", "--first-parent", commit],
+    )
+    .await
+}
+
+/// Diff a revision range; `A...B` diffs `B` against the merge base.
+pub async fn git_diff_range(repo_root: &Path, range: &GitRange) -> Result<String, DiffError> {
+    run_diff(repo_root, "diff", &[&range.to_string()]).await
+}
+
+/// Run a `git diff`-family command with `a/`/`b/` path prefixes and
+/// return its unified diff output.
+async fn run_diff(repo_root: &Path, command: &str, args: &[&str]) -> Result<String, DiffError> {
     let output = tokio::process::Command::new("git")
-        .args(["diff", "--src-prefix=a/", "--dst-prefix=b/", base_ref])
+        .args([command, "-p", "--src-prefix=a/", "--dst-prefix=b/"])
+        .args(args)
         .current_dir(repo_root)
         .output()
         .await
@@ -19,7 +53,7 @@ pub async fn git_diff(repo_root: &Path, base_ref: &str) -> Result<String, DiffEr
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(DiffError::GitError(format!(
-            "git diff failed (exit {}): {stderr}",
+            "git {command} failed (exit {}): {stderr}",
             output.status
         )));
     }
@@ -28,6 +62,36 @@ pub async fn git_diff(repo_root: &Path, base_ref: &str) -> Result<String, DiffEr
         .map_err(|e| DiffError::GitError(format!("git output is not valid UTF-8: {e}")))
 }
 
+/// Read a file as stored in git: in `rev`'s tree, or in the index when
+/// `rev` is empty.
+pub async fn show_file(repo_root: &Path, rev: &str, path: &str) -> Result<String, DiffError> {
+    show_object(repo_root, &format!("{rev}:{path}")).await
+}
+
+/// Read a blob by object name, e.g. an ID from a diff's `index` line.
+///
+/// The name is never parsed as an option, and anything but a blob is
+/// refused.
+pub async fn show_object(repo_root: &Path, object: &str) -> Result<String, DiffError> {
+    let output = tokio::process::Command::new("git")
+        .args(["cat-file", "blob", "--end-of-options", object])
+        .current_dir(repo_root)
+        .output()
+        .await
+        .map_err(|e| DiffError::GitError(format!("failed to run git cat-file: {e}")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DiffError::GitError(format!(
+            "git cat-file failed (exit {}): {stderr}",
+            output.status
+        )));
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|e| DiffError::GitError(format!("{object} is not valid UTF-8: {e}")))
+}
+
 /// Detect the current branch or review scope.
 ///
 /// Tries, in order:
@@ -68,6 +132,44 @@ pub async fn detect_branch(repo_root: &Path, env: &Env) -> String {
     String::new()
 }
 
+/// Return the full SHA of `HEAD`, or `None` outside a git repository or
+/// before the first commit.
+pub async fn head_commit(repo_root: &Path) -> Option<String> {
+    resolve_commit(repo_root, "HEAD").await
+}
+
+/// Return the full SHA of the commit `rev` names, or `None` when it
+/// doesn't name one.
+pub async fn resolve_commit(repo_root: &Path, rev: &str) -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", "--end-of-options"])
+        .arg(format!("{rev}^{{commit}}"))
+        .current_dir(repo_root)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!sha.is_empty()).then_some(sha)
+}
+
+/// Return the URL of the `origin` remote, or `None` when there is none.
+pub async fn origin_url(repo_root: &Path) -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(repo_root)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
 /// Retrieve the commit log between `base_ref` and HEAD.
 ///
 /// Returns a list of one-line commit summaries (`<short-sha> <subject>`)
@@ -78,10 +180,19 @@ pub async fn git_log(
     base_ref: &str,
     max_commits: usize,
 ) -> Result<Vec<String>, DiffError> {
-    let range = format!("{base_ref}..HEAD");
+    git_log_range(repo_root, &format!("{base_ref}..HEAD"), max_commits).await
+}
+
+/// Retrieve the commit log for a revision range such as `A..B` or
+/// `<sha>^!`, formatted like [`git_log`].
+pub async fn git_log_range(
+    repo_root: &Path,
+    range: &str,
+    max_commits: usize,
+) -> Result<Vec<String>, DiffError> {
     let max_count = format!("--max-count={max_commits}");
     let output = tokio::process::Command::new("git")
-        .args(["log", "--oneline", "--no-decorate", &max_count, &range])
+        .args(["log", "--oneline", "--no-decorate", &max_count, range])
         .current_dir(repo_root)
         .output()
         .await
@@ -208,6 +319,18 @@ mod tests {
         assert!(!root.is_empty());
     }
 
+    #[tokio::test]
+    async fn head_commit_outside_and_inside_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(head_commit(dir.path()).await, None);
+
+        let repo = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let sha = head_commit(repo).await.unwrap();
+        assert_eq!(sha.len(), 40);
+        assert_eq!(resolve_commit(repo, &sha[..12]).await, Some(sha));
+        assert_eq!(resolve_commit(repo, "--output=/tmp/x").await, None);
+    }
+
     #[tokio::test]
     async fn detect_branch_returns_branch_name_in_git_repo() {
         // Create a temp git repo on a named branch
@@ -508,4 +631,62 @@ mod tests {
         let result = git_log(dir.path(), "HEAD", 50).await;
         assert!(result.is_err());
     }
+
+    async fn git(dir: &Path, args: &[&str]) {
+        let output = tokio::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .await
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    #[tokio::test]
+    async fn diff_modes_select_the_right_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let p = dir.path();
+        git(p, &["init", "-b", "main"]).await;
+        git(p, &["config", "user.email", "test@test.com"]).await;
+        git(p, &["config", "user.name", "Test"]).await;
+        tokio::fs::write(p.join("f.txt"), "v1\n").await.unwrap();
+        git(p, &["add", "."]).await;
+        git(p, &["commit", "-m", "root"]).await;
+        git(p, &["checkout", "-b", "feature"]).await;
+        tokio::fs::write(p.join("f.txt"), "v2\n").await.unwrap();
+        git(p, &["commit", "-am", "committed"]).await;
+        tokio::fs::write(p.join("f.txt"), "v3\n").await.unwrap();
+        git(p, &["add", "."]).await;
+        tokio::fs::write(p.join("f.txt"), "v4\n").await.unwrap();
+
+        let staged = git_diff_staged(p).await.unwrap();
+        assert!(staged.contains("-v2") && staged.contains("+v3"));
+        let unstaged = git_diff_working_tree(p).await.unwrap();
+        assert!(unstaged.contains("-v3") && unstaged.contains("+v4"));
+        let commit = git_diff_commit(p, "HEAD").await.unwrap();
+        assert!(commit.contains("diff --git a/f.txt b/f.txt"));
+        assert!(commit.contains("-v1") && commit.contains("+v2"));
+        let root = git_diff_commit(p, "main").await.unwrap();
+        assert!(root.contains("+v1"));
+        let range = git_diff_range(p, &"main...feature".parse().unwrap())
+            .await
+            .unwrap();
+        assert!(range.contains("+v2") && !range.contains("v3"));
+
+        assert_eq!(show_file(p, "", "f.txt").await.unwrap(), "v3\n");
+        assert_eq!(show_file(p, "main", "f.txt").await.unwrap(), "v1\n");
+        assert!(show_file(p, "main", "missing.txt").await.is_err());
+        let target = p.join("written.txt");
+        let option = format!("--output={}", target.display());
+        assert!(show_object(p, &option).await.is_err());
+        assert!(!target.exists());
+        assert!(
+            show_object(p, "HEAD").await.is_err(),
+            "commits aren't blobs"
+        );
+
+        let log = git_log_range(p, "HEAD^!", 50).await.unwrap();
+        assert_eq!(log.len(), 1);
+        assert!(log[0].contains("committed"));
+    }
 }
@@ -3,11 +3,13 @@
 //! # Bounded Context: Diff Retrieval & Parsing
 //!
 //! Owns git invocation, unified-diff parsing, directory scanning,
-//! and chunk splitting. Produces [`FileDiff`](crate::models::diff::FileDiff)
+//! path filtering, and chunk splitting. Produces [`FileDiff`](crate::models::diff::FileDiff)
 //! values — never interprets diff content semantically.
 
 pub mod chunker;
 pub mod file;
+pub mod filter;
+pub mod forge;
 pub mod git;
 pub mod parser;
 pub mod scanner;
@@ -15,6 +17,7 @@ pub mod scanner;
 use std::path::Path;
 use thiserror::Error;
 
+use crate::env::Env;
 use crate::models::InputMode;
 use crate::models::diff::FileDiff;
 
@@ -33,6 +36,9 @@ pub enum DiffError {
 
     #[error("path not found: {0}")]
     PathNotFound(String),
+
+    #[error("failed to fetch pull request: {0}")]
+    ForgeError(#[from] forge::ForgeError),
 }
 
 /// Read a unified diff from stdin.
@@ -46,14 +52,18 @@ pub async fn read_diff_stdin() -> Result<String, DiffError> {
     Ok(buf)
 }
 
-/// The raw diff source: either a string to parse (zero-copy-friendly)
-/// or already-parsed owned diffs from a directory scan.
+/// The raw diff source: a string to parse (zero-copy-friendly), a
+/// fetched pull request carrying one, or already-parsed owned diffs
+/// from a directory scan.
 pub enum DiffSource {
     /// Raw unified diff text — call [`parser::parse_unified_diff`] to
     /// get `FileDiff` values that borrow from this string.
     Raw(String),
     /// Pre-parsed diffs from [`scanner::scan_path`] (all owned).
     Scanned(Vec<FileDiff<'static>>),
+    /// A pull request fetched from a forge; parse its `diff` like
+    /// [`DiffSource::Raw`].
+    PullRequest(Box<forge::PullRequest>),
 }
 
 /// Obtain the diff source for the given input mode.
@@ -61,7 +71,8 @@ pub enum DiffSource {
 /// For git/file/stdin modes this returns the raw diff string so the
 /// caller can parse it in a scope where the string lives long enough
 /// to be borrowed (zero-copy).  For direct-path scans the diffs are
-/// returned pre-parsed with owned content.
+/// returned pre-parsed with owned content. Pull requests are fetched
+/// with the credentials in the process environment.
 pub async fn get_diff_source(input: &InputMode, repo_root: &Path) -> Result<DiffSource, DiffError> {
     match input {
         InputMode::DiffFile(path) => Ok(DiffSource::Raw(file::read_diff_file(path).await?)),
@@ -70,6 +81,19 @@ pub async fn get_diff_source(input: &InputMode, repo_root: &Path) -> Result<Diff
             Ok(DiffSource::Raw(git::git_diff(repo_root, base_ref).await?))
         }
         InputMode::DirectPath(path) => Ok(DiffSource::Scanned(scanner::scan_path(path).await?)),
+        InputMode::Staged => Ok(DiffSource::Raw(git::git_diff_staged(repo_root).await?)),
+        InputMode::WorkingTree => Ok(DiffSource::Raw(
+            git::git_diff_working_tree(repo_root).await?,
+        )),
+        InputMode::Commit(commit) => Ok(DiffSource::Raw(
+            git::git_diff_commit(repo_root, commit).await?,
+        )),
+        InputMode::Range(range) => Ok(DiffSource::Raw(
+            git::git_diff_range(repo_root, range).await?,
+        )),
+        InputMode::PullRequest { number, forge } => Ok(DiffSource::PullRequest(Box::new(
+            forge::fetch_pull_request(*forge, *number, repo_root, &Env::real()).await?,
+        ))),
     }
 }
 
@@ -120,6 +144,7 @@ mod tests {
                 })
                 .collect()),
             DiffSource::Scanned(diffs) => Ok(diffs),
+            DiffSource::PullRequest(_) => unreachable!("tests don't fetch pull requests"),
         }
     }
 
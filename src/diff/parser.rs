@@ -7,6 +7,8 @@
 
 use std::borrow::Cow;
 
+use indexmap::IndexMap;
+
 use crate::models::diff::{DiffLine, DiffLineType, FileDiff, Hunk};
 
 /// States for the unified diff parser state machine.
@@ -244,6 +246,39 @@ pub fn parse_unified_diff<'a>(input: &'a str) -> Vec<FileDiff<'a>> {
     files
 }
 
+/// Collect the post-image blob ID of each file from the diff's
+/// `index <old>..<new>` lines, keyed by the file's new path.
+///
+/// Deleted files (an all-zero new ID) are left out, as is anything that
+/// isn't a lowercase hex object ID, since the diff may be untrusted.
+pub fn post_image_blobs(input: &str) -> IndexMap<String, String> {
+    let mut blobs = IndexMap::new();
+    let mut path: Option<String> = None;
+    for line in input.lines() {
+        if line.starts_with("diff --git ") {
+            path = Some(parse_diff_header(line).1);
+        } else if let Some(ids) = line.strip_prefix("index ") {
+            let new_id = ids
+                .split_whitespace()
+                .next()
+                .and_then(|range| range.split_once(".."))
+                .map(|(_, new)| new);
+            if let (Some(path), Some(id)) = (path.take(), new_id) {
+                // Diffs may be untrusted: only accept plain hex object IDs
+                if is_object_id(id) && !id.chars().all(|c| c == '0') {
+                    blobs.insert(path, id.to_string());
+                }
+            }
+        }
+    }
+    blobs
+}
+
+/// Whether `id` is an abbreviated or full hex object ID (7 to 64 digits).
+fn is_object_id(id: &str) -> bool {
+    (7..=64).contains(&id.len()) && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
 /// Parse the "diff --git a/path b/path" header line.
 fn parse_diff_header(line: &str) -> (String, String) {
     let rest = line.strip_prefix("diff --git ").unwrap_or(line);
@@ -340,6 +375,30 @@ fn parse_range(s: &str) -> Option<(u32, u32)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn post_image_blobs_from_index_lines() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\nindex 1234567..abcdef0 100644\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-index old..new\n+x\n\
+                    diff --git a/gone.rs b/gone.rs\ndeleted file mode 100644\nindex 1234567..0000000\n\
+                    diff --git a/old.rs b/new.rs\nsimilarity index 90%\nrename from old.rs\nrename to new.rs\nindex 89abcde..fedcba9\n";
+        let blobs = post_image_blobs(diff);
+        assert_eq!(
+            blobs.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("src/a.rs".to_string(), "abcdef0".to_string()),
+                ("new.rs".to_string(), "fedcba9".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn post_image_blobs_rejects_non_hex_ids() {
+        let diff = "diff --git a/a.rs b/a.rs\nindex 1234567..--output=/tmp/x 100644\n\
+                    diff --git a/b.rs b/b.rs\nindex 1234567..HEAD:secret.txt 100644\n\
+                    diff --git a/c.rs b/c.rs\nindex 1234567..abc 100644\n\
+                    diff --git a/d.rs b/d.rs\nindex 1234567..ABCDEF0 100644\n";
+        assert!(post_image_blobs(diff).is_empty());
+    }
+
     const SAMPLE_DIFF: &str = r#"diff --git a/src/main.rs b/src/main.rs
 index 1234567..abcdefg 100644
 --- a/src/main.rs
@@ -0,0 +1,750 @@
+//! Applying structured fixes from a saved review report.
+//!
+//! # Bounded Context: Fix Application
+//!
+//! Reads the JSON report written by `nitpik review --format json`,
+//! checks each finding's [`Fix`] against the current file, and produces
+//! the patched content plus a unified diff. A fix applies when the lines
+//! it recorded at review time are still there — either at the original
+//! position or, if unrelated edits shifted them, at a single unambiguous
+//! new position. Everything else is reported as a conflict and skipped.
+
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::models::finding::{Finding, Fix};
+
+/// Lines of unchanged context around each change in the unified diff.
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Errors from loading a report or applying its fixes.
+#[derive(Error, Debug)]
+pub enum FixError {
+    #[error("failed to read report {path}: {source}")]
+    ReadReport {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse report {path}: {source}")]
+    ParseReport {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("finding #{0} does not exist in the report")]
+    UnknownFinding(usize),
+
+    #[error("finding #{0} has no structured fix")]
+    NoFix(usize),
+
+    #[error("failed to write {path}: {source}")]
+    WriteFile {
+        path: String,
+        source: std::io::Error,
+    },
+}
+
+/// Load findings from a JSON report.
+///
+/// Accepts the `{"findings": [...]}` shape written by the JSON renderer
+/// as well as a bare array of findings.
+pub async fn load_report(path: &Path) -> Result<Vec<Finding>, FixError> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|source| FixError::ReadReport {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Report {
+        Wrapped { findings: Vec<Finding> },
+        Bare(Vec<Finding>),
+    }
+
+    let report: Report =
+        serde_json::from_str(&content).map_err(|source| FixError::ParseReport {
+            path: path.to_path_buf(),
+            source,
+        })?;
+    Ok(match report {
+        Report::Wrapped { findings } | Report::Bare(findings) => findings,
+    })
+}
+
+/// Pick the findings to fix, keyed by their 1-based position in the report.
+///
+/// With no selection every finding carrying a fix is returned. An explicit
+/// selection must only name findings that exist and have a fix.
+pub fn select<'a>(
+    findings: &'a [Finding],
+    only: &[usize],
+) -> Result<Vec<(usize, &'a Finding)>, FixError> {
+    if only.is_empty() {
+        return Ok(findings
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.fix.is_some())
+            .map(|(i, f)| (i + 1, f))
+            .collect());
+    }
+
+    let mut selected = Vec::new();
+    for &number in only {
+        let finding = number
+            .checked_sub(1)
+            .and_then(|i| findings.get(i))
+            .ok_or(FixError::UnknownFinding(number))?;
+        if finding.fix.is_none() {
+            return Err(FixError::NoFix(number));
+        }
+        if !selected.iter().any(|(n, _)| *n == number) {
+            selected.push((number, finding));
+        }
+    }
+    Ok(selected)
+}
+
+/// Whether a fix can be applied to the current file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixStatus {
+    /// The recorded lines are unchanged at the recorded position.
+    Ready,
+    /// The recorded lines moved; the fix applies at this 1-based line.
+    Relocated(u32),
+    /// The fix can't be applied safely.
+    Conflict(String),
+}
+
+/// A selected fix and how it relates to the current file.
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    /// 1-based position of the finding in the report.
+    pub number: usize,
+    /// The finding the fix belongs to.
+    pub finding: Finding,
+    /// Result of the conflict check.
+    pub status: FixStatus,
+}
+
+impl FixOutcome {
+    /// Returns `true` if the fix will be applied.
+    pub fn is_applicable(&self) -> bool {
+        !matches!(self.status, FixStatus::Conflict(_))
+    }
+}
+
+/// The planned changes to a single file.
+#[derive(Debug, Clone)]
+pub struct FilePlan {
+    /// Repo-relative path of the file.
+    pub path: String,
+    /// Every selected fix for this file, in report order.
+    pub outcomes: Vec<FixOutcome>,
+    /// Current content of the file (empty if it couldn't be read).
+    pub original: String,
+    /// Content after applying every applicable fix.
+    pub patched: String,
+    /// Unified diff from `original` to `patched` (empty if unchanged).
+    pub diff: String,
+}
+
+/// Check the selected fixes against the files under `repo_root`.
+///
+/// Returns one plan per file, in order of first appearance. Files that
+/// can't be read, or whose path points outside `repo_root`, turn all
+/// their fixes into conflicts.
+pub async fn plan(repo_root: &Path, selected: &[(usize, &Finding)]) -> Vec<FilePlan> {
+    let mut by_file: IndexMap<&str, Vec<(usize, &Finding)>> = IndexMap::new();
+    for &(number, finding) in selected {
+        by_file
+            .entry(finding.file.as_str())
+            .or_default()
+            .push((number, finding));
+    }
+
+    let mut plans = Vec::new();
+    for (path, fixes) in by_file {
+        let content = match resolve(repo_root, path) {
+            Ok(target) => tokio::fs::read_to_string(target)
+                .await
+                .map_err(|e| format!("cannot read file: {e}")),
+            Err(reason) => Err(reason),
+        };
+        let plan = match content {
+            Ok(content) => plan_file(path, &content, &fixes),
+            Err(reason) => FilePlan {
+                path: path.to_string(),
+                outcomes: fixes
+                    .iter()
+                    .map(|&(number, finding)| FixOutcome {
+                        number,
+                        finding: finding.clone(),
+                        status: FixStatus::Conflict(reason.clone()),
+                    })
+                    .collect(),
+                original: String::new(),
+                patched: String::new(),
+                diff: String::new(),
+            },
+        };
+        plans.push(plan);
+    }
+    plans
+}
+
+/// Write every changed file back to disk.
+///
+/// Plans whose path doesn't resolve inside `repo_root` are skipped;
+/// [`plan`] already reported their fixes as conflicts.
+pub async fn write(repo_root: &Path, plans: &[FilePlan]) -> Result<(), FixError> {
+    for plan in plans.iter().filter(|p| p.patched != p.original) {
+        let Ok(target) = resolve(repo_root, &plan.path) else {
+            continue;
+        };
+        tokio::fs::write(target, &plan.patched)
+            .await
+            .map_err(|source| FixError::WriteFile {
+                path: plan.path.clone(),
+                source,
+            })?;
+    }
+    Ok(())
+}
+
+/// Resolve a report path to a file under `repo_root`.
+///
+/// Reports may come from elsewhere, so the path must be relative with
+/// only normal components, and must not lead outside the repository
+/// through a symlink.
+fn resolve(repo_root: &Path, path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(path);
+    let normal = relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if path.is_empty() || !normal {
+        return Err("path is not inside the repository".to_string());
+    }
+
+    let target = repo_root.join(relative);
+    if let (Ok(root), Ok(resolved)) = (repo_root.canonicalize(), target.canonicalize())
+        && !resolved.starts_with(&root)
+    {
+        return Err("path resolves outside the repository".to_string());
+    }
+    Ok(target)
+}
+
+/// A located replacement: 0-based start, number of old lines, new lines.
+struct Edit<'a> {
+    number: usize,
+    start: usize,
+    len: usize,
+    lines: Vec<&'a str>,
+}
+
+/// Check and apply the fixes for one file's content.
+///
+/// Fixes are considered in report order; a fix that overlaps one already
+/// accepted is a conflict.
+pub fn plan_file(path: &str, content: &str, fixes: &[(usize, &Finding)]) -> FilePlan {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut edits: Vec<Edit<'_>> = Vec::new();
+    let mut outcomes = Vec::new();
+
+    for &(number, finding) in fixes {
+        let status = match finding.fix.as_ref() {
+            None => FixStatus::Conflict("finding has no fix".to_string()),
+            Some(fix) => match locate(fix, &lines) {
+                Err(reason) => FixStatus::Conflict(reason),
+                Ok((start, len)) => {
+                    if let Some(other) = edits.iter().find(|e| overlaps(e, start, len)) {
+                        FixStatus::Conflict(format!("overlaps fix #{}", other.number))
+                    } else {
+                        edits.push(Edit {
+                            number,
+                            start,
+                            len,
+                            lines: replacement_lines(&fix.replacement),
+                        });
+                        if start + 1 == fix.start_line as usize {
+                            FixStatus::Ready
+                        } else {
+                            FixStatus::Relocated(start as u32 + 1)
+                        }
+                    }
+                }
+            },
+        };
+        outcomes.push(FixOutcome {
+            number,
+            finding: finding.clone(),
+            status,
+        });
+    }
+
+    edits.sort_by_key(|e| e.start);
+    let patched = apply(content, &lines, &edits);
+    let diff = unified_diff(path, content, &lines, &edits);
+
+    FilePlan {
+        path: path.to_string(),
+        outcomes,
+        original: content.to_string(),
+        patched,
+        diff,
+    }
+}
+
+/// Find where a fix applies in the current lines.
+///
+/// Returns the 0-based start and number of lines to replace.
+fn locate(fix: &Fix, lines: &[&str]) -> Result<(usize, usize), String> {
+    let start = (fix.start_line as usize).saturating_sub(1);
+    let Some(original) = fix.original.as_deref() else {
+        // Nothing recorded to compare against: only check the range fits
+        let end = fix.end_line as usize;
+        return if fix.start_line >= 1 && end >= fix.start_line as usize && end <= lines.len() {
+            Ok((start, end - start))
+        } else {
+            Err("line range is outside the file".to_string())
+        };
+    };
+
+    let expected: Vec<&str> = original.split('\n').collect();
+    let len = expected.len();
+    let matches_at = |at: usize| {
+        at + expected.len() <= lines.len()
+            && lines[at..at + expected.len()]
+                .iter()
+                .zip(&expected)
+                .all(|(a, b)| a.trim_end() == b.trim_end())
+    };
+
+    if matches_at(start) {
+        return Ok((start, len));
+    }
+    let candidates: Vec<usize> = (0..lines.len()).filter(|&at| matches_at(at)).collect();
+    match candidates.as_slice() {
+        [at] => Ok((*at, len)),
+        [] => Err("lines changed since the review".to_string()),
+        many => Err(format!(
+            "lines changed since the review and now appear {} times",
+            many.len()
+        )),
+    }
+}
+
+/// Returns `true` if an accepted edit shares a line with the 0-based range.
+///
+/// Pure insertions (empty ranges) still conflict with an edit at the same
+/// position, since their relative order would be ambiguous.
+fn overlaps(edit: &Edit<'_>, start: usize, len: usize) -> bool {
+    edit.start < start + len.max(1) && start < edit.start + edit.len.max(1)
+}
+
+/// Split replacement text into lines. Empty text deletes the range.
+fn replacement_lines(replacement: &str) -> Vec<&str> {
+    let text = replacement
+        .strip_suffix('\n')
+        .map(|t| t.strip_suffix('\r').unwrap_or(t))
+        .unwrap_or(replacement);
+    if text.is_empty() && !replacement.contains('\n') {
+        return Vec::new();
+    }
+    text.split('\n')
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .collect()
+}
+
+/// Line ending used by the file (CRLF if any line uses it).
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Rebuild the file content with the (sorted) edits applied.
+///
+/// Preserves the file's line ending and whether it ends with a newline.
+fn apply(content: &str, lines: &[&str], edits: &[Edit<'_>]) -> String {
+    if edits.is_empty() {
+        return content.to_string();
+    }
+
+    let mut out: Vec<&str> = Vec::with_capacity(lines.len());
+    let mut pos = 0;
+    for edit in edits {
+        out.extend_from_slice(&lines[pos..edit.start]);
+        out.extend_from_slice(&edit.lines);
+        pos = edit.start + edit.len;
+    }
+    out.extend_from_slice(&lines[pos..]);
+
+    let eol = line_ending(content);
+    let mut patched = out.join(eol);
+    if content.ends_with('\n') && !patched.is_empty() {
+        patched.push_str(eol);
+    }
+    patched
+}
+
+/// Render a unified diff for the (sorted) edits.
+///
+/// Edits whose context would touch are merged into one hunk, matching
+/// what `git diff` produces so the output applies with `git apply`.
+fn unified_diff(path: &str, content: &str, lines: &[&str], edits: &[Edit<'_>]) -> String {
+    if edits.is_empty() {
+        return String::new();
+    }
+
+    // Group edits into hunks
+    let mut groups: Vec<&[Edit<'_>]> = Vec::new();
+    let mut group_start = 0;
+    for i in 1..edits.len() {
+        let prev_end = edits[i - 1].start + edits[i - 1].len;
+        if edits[i].start > prev_end + 2 * DIFF_CONTEXT_LINES {
+            groups.push(&edits[group_start..i]);
+            group_start = i;
+        }
+    }
+    groups.push(&edits[group_start..]);
+
+    let no_eol_marker = "\\ No newline at end of file\n";
+    let missing_eol = !content.is_empty() && !content.ends_with('\n');
+    let last = lines.len().saturating_sub(1);
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    let mut delta: isize = 0;
+    for group in groups {
+        let first = &group[0];
+        let final_edit = &group[group.len() - 1];
+        let ctx_start = first.start.saturating_sub(DIFF_CONTEXT_LINES);
+        let ctx_end = (final_edit.start + final_edit.len + DIFF_CONTEXT_LINES).min(lines.len());
+
+        let old_len = ctx_end - ctx_start;
+        let growth: isize = group
+            .iter()
+            .map(|e| e.lines.len() as isize - e.len as isize)
+            .sum();
+        let new_len = (old_len as isize + growth) as usize;
+        let new_start = (ctx_start as isize + delta) as usize;
+        delta += growth;
+
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(ctx_start, old_len),
+            hunk_range(new_start, new_len)
+        ));
+
+        let mut pos = ctx_start;
+        for edit in group {
+            for (i, line) in lines.iter().enumerate().take(edit.start).skip(pos) {
+                out.push_str(&format!(" {line}\n"));
+                if missing_eol && i == last {
+                    out.push_str(no_eol_marker);
+                }
+            }
+            for (i, line) in lines.iter().enumerate().skip(edit.start).take(edit.len) {
+                out.push_str(&format!("-{line}\n"));
+                if missing_eol && i == last {
+                    out.push_str(no_eol_marker);
+                }
+            }
+            let reaches_end = edit.start + edit.len == lines.len();
+            for (i, line) in edit.lines.iter().enumerate() {
+                out.push_str(&format!("+{line}\n"));
+                if missing_eol && reaches_end && i + 1 == edit.lines.len() {
+                    out.push_str(no_eol_marker);
+                }
+            }
+            pos = edit.start + edit.len;
+        }
+        for (i, line) in lines.iter().enumerate().take(ctx_end).skip(pos) {
+            out.push_str(&format!(" {line}\n"));
+            if missing_eol && i == last {
+                out.push_str(no_eol_marker);
+            }
+        }
+    }
+    out
+}
+
+/// Format a hunk header range (`start,len`, 1-based; `start` is the line
+/// before the hunk when it is empty).
+fn hunk_range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{len}", start + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::Severity;
+
+    fn finding(start_line: u32, end_line: u32, original: &str, replacement: &str) -> Finding {
+        Finding {
+            file: "src/app.rs".into(),
+            line: start_line,
+            end_line: Some(end_line),
+            severity: Severity::Warning,
+            title: "Title".into(),
+            message: "Message".into(),
+            suggestion: None,
+            agent: "backend".into(),
+            fix: Some(Fix {
+                start_line,
+                end_line,
+                replacement: replacement.into(),
+                original: Some(original.into()),
+            }),
+            served_by: None,
+        }
+    }
+
+    fn plan_one(content: &str, findings: &[Finding]) -> FilePlan {
+        let numbered: Vec<_> = findings
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (i + 1, f))
+            .collect();
+        plan_file("src/app.rs", content, &numbered)
+    }
+
+    const CONTENT: &str =
+        "fn main() {\n    let a = 1;\n    let b = a.unwrap();\n    println!(\"{b}\");\n}\n";
+
+    #[test]
+    fn applies_fix_and_renders_diff() {
+        let f = finding(3, 3, "    let b = a.unwrap();", "    let b = a?;");
+        let plan = plan_one(CONTENT, &[f]);
+        assert_eq!(plan.outcomes[0].status, FixStatus::Ready);
+        assert_eq!(
+            plan.patched,
+            "fn main() {\n    let a = 1;\n    let b = a?;\n    println!(\"{b}\");\n}\n"
+        );
+        assert_eq!(
+            plan.diff,
+            "--- a/src/app.rs\n+++ b/src/app.rs\n@@ -1,5 +1,5 @@\n fn main() {\n     let a = 1;\n-    let b = a.unwrap();\n+    let b = a?;\n     println!(\"{b}\");\n }\n"
+        );
+    }
+
+    #[test]
+    fn relocates_shifted_lines() {
+        let shifted = format!("// header\n{CONTENT}");
+        let f = finding(3, 3, "    let b = a.unwrap();", "    let b = a?;");
+        let plan = plan_one(&shifted, &[f]);
+        assert_eq!(plan.outcomes[0].status, FixStatus::Relocated(4));
+        assert!(plan.patched.contains("    let b = a?;\n"));
+    }
+
+    #[test]
+    fn changed_lines_conflict() {
+        let f = finding(3, 3, "    let b = a.expect(\"x\");", "    let b = a?;");
+        let plan = plan_one(CONTENT, &[f]);
+        assert!(matches!(plan.outcomes[0].status, FixStatus::Conflict(_)));
+        assert_eq!(plan.patched, CONTENT);
+        assert!(plan.diff.is_empty());
+    }
+
+    #[test]
+    fn ambiguous_relocation_conflicts() {
+        let content = "x\ndup\ny\ndup\n";
+        let f = finding(1, 1, "dup", "fixed");
+        let plan = plan_one(content, &[f]);
+        assert_eq!(
+            plan.outcomes[0].status,
+            FixStatus::Conflict("lines changed since the review and now appear 2 times".into())
+        );
+    }
+
+    #[test]
+    fn overlapping_fixes_conflict() {
+        let first = finding(
+            2,
+            3,
+            "    let a = 1;\n    let b = a.unwrap();",
+            "    let b = 1;",
+        );
+        let second = finding(3, 3, "    let b = a.unwrap();", "    let b = a?;");
+        let plan = plan_one(CONTENT, &[first, second]);
+        assert_eq!(plan.outcomes[0].status, FixStatus::Ready);
+        assert_eq!(
+            plan.outcomes[1].status,
+            FixStatus::Conflict("overlaps fix #1".into())
+        );
+        assert!(plan.patched.contains("    let b = 1;\n    println!"));
+    }
+
+    #[test]
+    fn empty_replacement_deletes_lines() {
+        let f = finding(4, 4, "    println!(\"{b}\");", "");
+        let plan = plan_one(CONTENT, &[f]);
+        assert_eq!(
+            plan.patched,
+            "fn main() {\n    let a = 1;\n    let b = a.unwrap();\n}\n"
+        );
+        assert!(plan.diff.contains("@@ -1,5 +1,4 @@"));
+    }
+
+    #[test]
+    fn preserves_crlf_and_missing_final_newline() {
+        let content = "a\r\nb\r\nc";
+        let f = finding(3, 3, "c", "C");
+        let plan = plan_one(content, &[f]);
+        assert_eq!(plan.patched, "a\r\nb\r\nC");
+        assert!(
+            plan.diff
+                .ends_with("-c\n\\ No newline at end of file\n+C\n\\ No newline at end of file\n")
+        );
+    }
+
+    #[test]
+    fn distant_fixes_get_separate_hunks() {
+        let content: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+        let plan = plan_one(
+            &content,
+            &[
+                finding(2, 2, "line 2", "LINE 2"),
+                finding(18, 18, "line 18", "LINE 18\nextra"),
+            ],
+        );
+        assert!(plan.diff.contains("@@ -1,5 +1,5 @@\n"));
+        assert!(plan.diff.contains("@@ -15,6 +15,7 @@\n"));
+
+        let near = plan_one(
+            &content,
+            &[
+                finding(2, 2, "line 2", "LINE 2"),
+                finding(8, 8, "line 8", "LINE 8"),
+            ],
+        );
+        assert_eq!(near.diff.matches("@@ -").count(), 1);
+    }
+
+    #[test]
+    fn select_all_or_explicit() {
+        let mut plain = finding(1, 1, "a", "b");
+        plain.fix = None;
+        let findings = vec![plain, finding(2, 2, "a", "b")];
+
+        let all = select(&findings, &[]).unwrap();
+        assert_eq!(all.iter().map(|(n, _)| *n).collect::<Vec<_>>(), vec![2]);
+
+        assert!(matches!(
+            select(&findings, &[3]),
+            Err(FixError::UnknownFinding(3))
+        ));
+        assert!(matches!(
+            select(&findings, &[0]),
+            Err(FixError::UnknownFinding(0))
+        ));
+        assert!(matches!(select(&findings, &[1]), Err(FixError::NoFix(1))));
+        assert_eq!(select(&findings, &[2, 2]).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn load_report_accepts_wrapped_and_bare() {
+        let dir = tempfile::tempdir().unwrap();
+        let findings = vec![finding(3, 3, "x", "y")];
+
+        let wrapped = dir.path().join("report.json");
+        let json = serde_json::json!({ "findings": findings, "summary": {} });
+        std::fs::write(&wrapped, json.to_string()).unwrap();
+        let loaded = load_report(&wrapped).await.unwrap();
+        assert_eq!(loaded[0].fix, findings[0].fix);
+
+        let bare = dir.path().join("bare.json");
+        std::fs::write(&bare, serde_json::to_string(&findings).unwrap()).unwrap();
+        assert_eq!(load_report(&bare).await.unwrap().len(), 1);
+
+        std::fs::write(&bare, "not json").unwrap();
+        assert!(matches!(
+            load_report(&bare).await,
+            Err(FixError::ParseReport { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn plan_and_write_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/app.rs"), CONTENT).unwrap();
+
+        let mut missing = finding(1, 1, "a", "b");
+        missing.file = "src/missing.rs".into();
+        let findings = vec![
+            finding(3, 3, "    let b = a.unwrap();", "    let b = a?;"),
+            missing,
+        ];
+        let selected = select(&findings, &[]).unwrap();
+        let plans = plan(dir.path(), &selected).await;
+        assert_eq!(plans.len(), 2);
+        assert!(matches!(
+            plans[1].outcomes[0].status,
+            FixStatus::Conflict(ref reason) if reason.starts_with("cannot read file")
+        ));
+
+        write(dir.path(), &plans).await.unwrap();
+        let written = std::fs::read_to_string(dir.path().join("src/app.rs")).unwrap();
+        assert!(written.contains("let b = a?;"));
+        assert!(!dir.path().join("src/missing.rs").exists());
+    }
+
+    #[tokio::test]
+    async fn paths_outside_repo_conflict() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        std::fs::write(dir.path().join("outside.rs"), "a\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(dir.path().join("outside.rs"), repo.join("link.rs")).unwrap();
+
+        let mut findings = Vec::new();
+        for path in ["../outside.rs", "/etc/x", "link.rs"] {
+            let mut f = finding(1, 1, "a", "b");
+            f.file = path.into();
+            findings.push(f);
+        }
+        let selected = select(&findings, &[]).unwrap();
+        let plans = plan(&repo, &selected).await;
+
+        assert_eq!(
+            plans[0].outcomes[0].status,
+            FixStatus::Conflict("path is not inside the repository".into())
+        );
+        assert_eq!(
+            plans[1].outcomes[0].status,
+            FixStatus::Conflict("path is not inside the repository".into())
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            plans[2].outcomes[0].status,
+            FixStatus::Conflict("path resolves outside the repository".into())
+        );
+
+        // Even a hand-built plan for an escaping path is never written
+        let forged = FilePlan {
+            path: "../outside.rs".into(),
+            outcomes: Vec::new(),
+            original: "a\n".into(),
+            patched: "b\n".into(),
+            diff: String::new(),
+        };
+        write(&repo, &[forged]).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("outside.rs")).unwrap(),
+            "a\n"
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Path glob matching.
+//!
+//! # Bounded Context: Path Matching
+//!
+//! A minimal glob dialect shared by every feature that selects files by
+//! pattern (threat rule allowlists, per-path review overrides). Patterns
+//! are matched against repo-relative paths with `/` separators:
+//!
+//! - `*` matches within a single path segment
+//! - `**` matches any number of segments (including none)
+//! - `**/` matches whole directories only: `**/test/**` matches
+//!   `test/a.rs` and `src/test/a.rs` but not `latest/a.rs`
+//! - `?` matches a single character
+
+use regex::Regex;
+
+/// Check if a path matches a glob pattern.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let mut regex_str = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' => {
+                if chars.peek() == Some(&'*') {
+                    chars.next(); // consume second *
+                    // `**/` matches zero or more whole directories
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex_str.push_str("(?:.*/)?");
+                    } else {
+                        regex_str.push_str(".*");
+                    }
+                } else {
+                    regex_str.push_str("[^/]*");
+                }
+            }
+            '?' => regex_str.push('.'),
+            '.' | '+' | '(' | ')' | '{' | '}' | '[' | ']' | '^' | '$' | '|' | '\\' => {
+                regex_str.push('\\');
+                regex_str.push(c);
+            }
+            _ => regex_str.push(c),
+        }
+    }
+    regex_str.push('$');
+
+    Regex::new(&regex_str)
+        .map(|re| re.is_match(path))
+        .unwrap_or(false)
+}
+
+/// Check if a path matches any of the given glob patterns.
+pub fn matches_any(path: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|pat| glob_match(pat, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_star_stays_in_segment() {
+        assert!(glob_match("src/*.rs", "src/main.rs"));
+        assert!(!glob_match("src/*.rs", "src/cli/args.rs"));
+    }
+
+    #[test]
+    fn double_star_spans_segments() {
+        assert!(glob_match("web/**", "web/app/index.ts"));
+        assert!(glob_match("**/*.pb.go", "api/v1/user.pb.go"));
+        assert!(glob_match("**/*.pb.go", "user.pb.go"));
+        assert!(!glob_match("web/**", "webhooks/handler.rs"));
+        assert!(glob_match("**/vendor/**", "vendor/a.go"));
+        assert!(glob_match("**/vendor/**", "third_party/vendor/a.go"));
+        assert!(!glob_match("**/vendor/**", "myvendor/a.go"));
+        assert!(glob_match("**/test_*", "pkg/test_app.py"));
+        assert!(!glob_match("**/test_*", "pkg/latest_app.py"));
+    }
+
+    #[test]
+    fn literal_characters_are_escaped() {
+        assert!(glob_match("Cargo.lock", "Cargo.lock"));
+        assert!(!glob_match("Cargo.lock", "Cargo_lock"));
+        assert!(glob_match("file?.txt", "file1.txt"));
+    }
+
+    #[test]
+    fn matches_any_pattern() {
+        let patterns = vec!["vendor/**".to_string(), "*.min.js".to_string()];
+        assert!(matches_any("vendor/lib/a.go", &patterns));
+        assert!(matches_any("app.min.js", &patterns));
+        assert!(!matches_any("src/app.js", &patterns));
+        assert!(!matches_any("src/app.js", &[]));
+    }
+}
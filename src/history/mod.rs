@@ -0,0 +1,409 @@
+//! Persistent review history and trend reports.
+//!
+//! # Bounded Context: Review History
+//!
+//! Owns the record of past review runs — when they ran, on which branch
+//! and commit, which agents took part, what they found and what it
+//! cost — along with run-to-run comparison and trend rendering. Runs
+//! are stored as plain JSON files (see [`store`]); nothing here calls an
+//! LLM or reads diffs.
+//!
+//! Findings are compared across runs the same way a baseline matches
+//! them: same file, same hash of the flagged lines, and a similar title.
+//! Line numbers alone never decide a match, so findings whose code
+//! merely moved count as persisting.
+
+pub mod store;
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::baseline::{content_hash, titles_match};
+use crate::cost::UsageReport;
+use crate::cost::format_cost;
+use crate::models::finding::{Finding, Severity};
+
+pub use store::{HistoryError, HistoryStore, find_run};
+
+/// One recorded review run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Short random identifier, used to refer to the run.
+    pub id: String,
+    /// When the run finished, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Branch or review scope, as used for the cache.
+    pub branch: String,
+    /// The reviewed commit: the `--commit`, the head of the `--range` or
+    /// `--pr`, or `HEAD` for local changes.
+    pub commit: Option<String>,
+    /// Profiles that took part.
+    pub agents: Vec<String>,
+    /// Files that were reviewed.
+    pub files: Vec<String>,
+    /// Findings the run reported.
+    pub findings: Vec<RecordedFinding>,
+    /// Review tasks that failed after retries.
+    pub failed_tasks: usize,
+    /// Tokens and cost of the run.
+    pub usage: UsageReport,
+}
+
+/// A finding together with the hash used to match it across runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedFinding {
+    pub finding: Finding,
+    /// Hash of the whitespace-normalized lines the finding covered.
+    pub content_hash: String,
+}
+
+impl RecordedFinding {
+    /// Record `findings`, hashing their lines from `file_contents`.
+    pub fn from_findings(
+        findings: &[Finding],
+        file_contents: &IndexMap<String, String>,
+    ) -> Vec<Self> {
+        findings
+            .iter()
+            .map(|f| Self {
+                finding: f.clone(),
+                content_hash: content_hash(f, file_contents),
+            })
+            .collect()
+    }
+
+    /// Whether two recorded findings describe the same issue.
+    fn same_issue(&self, other: &Self) -> bool {
+        self.finding.file == other.finding.file
+            && self.content_hash == other.content_hash
+            && titles_match(&self.finding.title, &other.finding.title)
+    }
+}
+
+impl RunRecord {
+    /// An empty run stamped with a fresh ID and the current time.
+    pub fn now() -> Self {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self {
+            id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            timestamp,
+            ..Self::default()
+        }
+    }
+
+    /// Number of findings at `severity`.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.finding.severity == severity)
+            .count()
+    }
+
+    /// Number of findings reported by `agent`.
+    pub fn count_for_agent(&self, agent: &str) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.finding.agent == agent)
+            .count()
+    }
+}
+
+/// Findings compared between an older and a newer run.
+#[derive(Debug, Default)]
+pub struct RunDiff<'a> {
+    /// Reported by the newer run only.
+    pub new: Vec<&'a Finding>,
+    /// Reported by the older run, in a file the newer run reviewed, but
+    /// not by the newer run.
+    pub fixed: Vec<&'a Finding>,
+    /// Reported by both runs (as of the newer run).
+    pub persisting: Vec<&'a Finding>,
+}
+
+/// Compare the findings of two runs.
+///
+/// Older findings in files the newer run didn't review are left out:
+/// a run that never looked at a file says nothing about its findings.
+pub fn diff_runs<'a>(old: &'a RunRecord, new: &'a RunRecord) -> RunDiff<'a> {
+    let mut matched = vec![false; old.findings.len()];
+    let mut diff = RunDiff::default();
+    for finding in &new.findings {
+        let hit = old
+            .findings
+            .iter()
+            .enumerate()
+            .position(|(i, f)| !matched[i] && f.same_issue(finding));
+        match hit {
+            Some(i) => {
+                matched[i] = true;
+                diff.persisting.push(&finding.finding);
+            }
+            None => diff.new.push(&finding.finding),
+        }
+    }
+
+    let reviewed: HashSet<&str> = new.files.iter().map(String::as_str).collect();
+    diff.fixed = old
+        .findings
+        .iter()
+        .zip(&matched)
+        .filter(|(f, hit)| !**hit && reviewed.contains(f.finding.file.as_str()))
+        .map(|(f, _)| &f.finding)
+        .collect();
+    diff
+}
+
+/// Render recorded runs as a table, most recent first.
+pub fn render_list(runs: &[RunRecord]) -> String {
+    let mut out = String::new();
+    let branch_width = runs
+        .iter()
+        .map(|r| r.branch.len())
+        .max()
+        .unwrap_or(0)
+        .max("Branch".len());
+    let _ = writeln!(
+        out,
+        "{:<8}  {:<16}  {:<branch_width$}  {:<8}  {:>8}  {:>6}  {:>8}  {:>6}  {:>10}",
+        "ID", "Date (UTC)", "Branch", "Commit", "Findings", "Errors", "Warnings", "Info", "Cost"
+    );
+    for run in runs.iter().rev() {
+        let commit = run.commit.as_deref().unwrap_or("-");
+        let _ = writeln!(
+            out,
+            "{:<8}  {:<16}  {:<branch_width$}  {:<8}  {:>8}  {:>6}  {:>8}  {:>6}  {:>10}",
+            run.id,
+            format_timestamp(run.timestamp),
+            run.branch,
+            &commit[..commit.len().min(8)],
+            run.findings.len(),
+            run.count(Severity::Error),
+            run.count(Severity::Warning),
+            run.count(Severity::Info),
+            format_cost(run.usage.total.cost),
+        );
+    }
+    out
+}
+
+/// Render the comparison of two runs.
+pub fn render_diff(old: &RunRecord, new: &RunRecord, diff: &RunDiff<'_>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Comparing {} ({}) → {} ({})",
+        old.id,
+        format_timestamp(old.timestamp),
+        new.id,
+        format_timestamp(new.timestamp)
+    );
+    for (label, findings) in [
+        ("New", &diff.new),
+        ("Fixed", &diff.fixed),
+        ("Persisting", &diff.persisting),
+    ] {
+        let _ = writeln!(out, "\n{label} ({}):", findings.len());
+        for f in findings {
+            let _ = writeln!(
+                out,
+                "  {:<7}  {}:{}  {}  [{}]",
+                f.severity.to_string(),
+                f.file,
+                f.line,
+                f.title,
+                f.agent
+            );
+        }
+    }
+    out
+}
+
+/// Render finding counts per severity and per agent across runs,
+/// oldest first.
+pub fn render_trends(runs: &[RunRecord]) -> String {
+    let mut out = String::new();
+    let label = |run: &RunRecord| format!("{}  {}", run.id, format_timestamp(run.timestamp));
+    // Run ID, a gap, then the date
+    let width = 8 + 2 + 16;
+
+    let _ = writeln!(
+        out,
+        "{:<width$}  {:>6}  {:>8}  {:>6}  {:>6}",
+        "Run", "Errors", "Warnings", "Info", "Total"
+    );
+    for run in runs {
+        let _ = writeln!(
+            out,
+            "{:<width$}  {:>6}  {:>8}  {:>6}  {:>6}",
+            label(run),
+            run.count(Severity::Error),
+            run.count(Severity::Warning),
+            run.count(Severity::Info),
+            run.findings.len(),
+        );
+    }
+
+    let mut agents: Vec<&str> = Vec::new();
+    for name in runs.iter().flat_map(|r| &r.agents) {
+        if !agents.contains(&name.as_str()) {
+            agents.push(name);
+        }
+    }
+    if agents.is_empty() {
+        return out;
+    }
+    let _ = writeln!(out);
+    let _ = write!(out, "{:<width$}", "Run");
+    for agent in &agents {
+        let _ = write!(out, "  {agent:>w$}", w = agent.len().max(4));
+    }
+    let _ = writeln!(out);
+    for run in runs {
+        let _ = write!(out, "{:<width$}", label(run));
+        for agent in &agents {
+            // Agents that didn't take part in a run have no count.
+            let cell = if run.agents.iter().any(|a| a == agent) {
+                run.count_for_agent(agent).to_string()
+            } else {
+                "-".to_string()
+            };
+            let _ = write!(out, "  {cell:>w$}", w = agent.len().max(4));
+        }
+        let _ = writeln!(out);
+    }
+    out
+}
+
+/// Format Unix seconds as `YYYY-MM-DD HH:MM` in UTC.
+fn format_timestamp(secs: u64) -> String {
+    let secs = secs as i64;
+    let (y, m, d) = crate::license::epoch_secs_to_ymd(secs);
+    let minutes = secs.rem_euclid(86400) / 60;
+    format!(
+        "{y:04}-{m:02}-{d:02} {:02}:{:02}",
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(file: &str, line: u32, title: &str, severity: Severity) -> Finding {
+        Finding {
+            file: file.to_string(),
+            line,
+            end_line: None,
+            severity,
+            title: title.to_string(),
+            message: "msg".to_string(),
+            suggestion: None,
+            agent: "backend".to_string(),
+            fix: None,
+            served_by: None,
+        }
+    }
+
+    fn run(id: &str, findings: &[Finding], content: &str) -> RunRecord {
+        let contents = IndexMap::from([
+            ("a.rs".to_string(), content.to_string()),
+            ("b.rs".to_string(), "fn b() {}\n".to_string()),
+        ]);
+        RunRecord {
+            id: id.to_string(),
+            agents: vec!["backend".to_string()],
+            files: vec!["a.rs".to_string()],
+            findings: RecordedFinding::from_findings(findings, &contents),
+            ..RunRecord::default()
+        }
+    }
+
+    #[test]
+    fn diff_classifies_new_fixed_and_persisting() {
+        let old = run(
+            "old",
+            &[
+                finding("a.rs", 2, "Unchecked unwrap", Severity::Warning),
+                finding("a.rs", 3, "Missing timeout", Severity::Error),
+                finding("b.rs", 1, "Dead code", Severity::Info),
+            ],
+            "fn a() {}\nlet x = y.unwrap();\ncall();\n",
+        );
+        // The unwrap moved down a line; the timeout was fixed.
+        let new = run(
+            "new",
+            &[
+                finding("a.rs", 3, "Unchecked unwrap", Severity::Warning),
+                finding("a.rs", 1, "Shadowed variable", Severity::Info),
+            ],
+            "// note\nfn a() {}\nlet x = y.unwrap();\n",
+        );
+
+        let diff = diff_runs(&old, &new);
+        let titles = |fs: &[&Finding]| fs.iter().map(|f| f.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&diff.persisting), vec!["Unchecked unwrap"]);
+        assert_eq!(titles(&diff.new), vec!["Shadowed variable"]);
+        // b.rs wasn't reviewed by the newer run, so its finding isn't "fixed"
+        assert_eq!(titles(&diff.fixed), vec!["Missing timeout"]);
+    }
+
+    #[test]
+    fn trends_show_counts_per_severity_and_agent() {
+        let mut first = run(
+            "r1",
+            &[finding("a.rs", 1, "A", Severity::Error)],
+            "fn a() {}\n",
+        );
+        first.timestamp = 86_400 + 3_660;
+        let mut second = run("r2", &[], "fn a() {}\n");
+        second.agents = vec!["security".to_string()];
+
+        let out = render_trends(&[first, second]);
+        assert!(out.contains("r1  1970-01-02 01:01"));
+        assert!(out.contains("backend"));
+        assert!(out.contains("security"));
+        let rows: Vec<Vec<&str>> = out
+            .lines()
+            .filter(|l| l.starts_with("r2"))
+            .map(|l| l.split_whitespace().skip(3).collect())
+            .collect();
+        // Severity row, then the agent row: backend didn't run in r2
+        assert_eq!(rows, vec![vec!["0", "0", "0", "0"], vec!["-", "0"]]);
+    }
+
+    #[test]
+    fn list_shows_most_recent_first() {
+        let mut older = run("r1", &[], "");
+        older.timestamp = 1;
+        older.commit = Some("0123456789abcdef".to_string());
+        let mut newer = run("r2", &[], "");
+        newer.timestamp = 2;
+        let out = render_list(&[older, newer]);
+        let ids: Vec<&str> = out
+            .lines()
+            .skip(1)
+            .map(|l| l.split_whitespace().next().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["r2", "r1"]);
+        assert!(out.contains("01234567 "));
+    }
+
+    #[test]
+    fn record_round_trips_through_json() {
+        let record = run(
+            "r1",
+            &[finding("a.rs", 1, "A", Severity::Warning)],
+            "fn a() {}\n",
+        );
+        let json = serde_json::to_string(&record).unwrap();
+        let parsed: RunRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+    }
+}
@@ -0,0 +1,220 @@
+//! File-based store for recorded runs.
+//!
+//! Each run is one JSON file named `<timestamp>-<id>.json`, so a
+//! directory listing sorts runs chronologically and concurrent reviews
+//! never write to the same file.
+
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::RunRecord;
+
+/// Errors from reading or writing the review history.
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("failed to read history in {path}: {source}")]
+    ReadDir {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse history entry {path}: {source}")]
+    ParseRun {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("failed to write history entry {path}: {source}")]
+    WriteRun {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("no recorded run matches '{0}'")]
+    UnknownRun(String),
+
+    #[error("'{0}' matches more than one recorded run; use a longer ID")]
+    AmbiguousRun(String),
+
+    #[error("history directory could not be determined")]
+    NoDirectory,
+}
+
+/// Directory-backed review history for one repository.
+pub struct HistoryStore {
+    dir: PathBuf,
+}
+
+impl HistoryStore {
+    /// Open the history in `dir`, which is created on the first write.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Open the history for `repo_root` under the global config directory.
+    ///
+    /// Repositories are kept apart by a directory named after the repo
+    /// and a hash of its path.
+    pub fn for_repo(repo_root: &Path) -> Result<Self, HistoryError> {
+        let base = dirs::config_dir()
+            .map(|d| {
+                d.join(crate::constants::CONFIG_DIR)
+                    .join(crate::constants::HISTORY_DIR)
+            })
+            .ok_or(HistoryError::NoDirectory)?;
+        Ok(Self::new(base.join(repo_dir_name(repo_root))))
+    }
+
+    /// The directory holding the recorded runs.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write `run` to the history and return the file it was written to.
+    pub async fn record(&self, run: &RunRecord) -> Result<PathBuf, HistoryError> {
+        let path = self.dir.join(format!("{}-{}.json", run.timestamp, run.id));
+        let json = serde_json::to_string_pretty(run).unwrap_or_else(|_| "{}".to_string());
+        let write = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&path, json).await
+        };
+        write.await.map_err(|source| HistoryError::WriteRun {
+            path: path.clone(),
+            source,
+        })?;
+        Ok(path)
+    }
+
+    /// Load every recorded run, oldest first.
+    ///
+    /// A missing directory is an empty history. Run files that can't be
+    /// read or parsed are skipped with a warning, so one corrupt file
+    /// doesn't hide the rest.
+    pub async fn runs(&self) -> Result<Vec<RunRecord>, HistoryError> {
+        let read_err = |source| HistoryError::ReadDir {
+            path: self.dir.clone(),
+            source,
+        };
+        if !tokio::fs::try_exists(&self.dir).await.unwrap_or(false) {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.dir).await.map_err(read_err)?;
+        while let Some(entry) = entries.next_entry().await.map_err(read_err)? {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                paths.push(path);
+            }
+        }
+
+        let mut runs: Vec<RunRecord> = Vec::with_capacity(paths.len());
+        for path in paths {
+            let content = match tokio::fs::read_to_string(&path).await {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("Warning: skipping {}: {e}", path.display());
+                    continue;
+                }
+            };
+            match serde_json::from_str(&content) {
+                Ok(run) => runs.push(run),
+                Err(source) => {
+                    eprintln!("Warning: {}", HistoryError::ParseRun { path, source });
+                }
+            }
+        }
+        runs.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.id.cmp(&b.id)));
+        Ok(runs)
+    }
+}
+
+/// Find the run whose ID starts with `prefix`.
+pub fn find_run<'a>(runs: &'a [RunRecord], prefix: &str) -> Result<&'a RunRecord, HistoryError> {
+    let mut matches = runs.iter().filter(|r| r.id.starts_with(prefix));
+    match (matches.next(), matches.next()) {
+        (Some(run), None) if !prefix.is_empty() => Ok(run),
+        (Some(_), Some(_)) => Err(HistoryError::AmbiguousRun(prefix.to_string())),
+        _ => Err(HistoryError::UnknownRun(prefix.to_string())),
+    }
+}
+
+/// Directory name for a repository: its base name plus a short hash of
+/// the full path, so same-named checkouts don't share a history.
+fn repo_dir_name(repo_root: &Path) -> String {
+    let name = repo_root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "repo".to_string());
+    let hash = hex::encode(Sha256::digest(repo_root.to_string_lossy().as_bytes()));
+    format!("{name}-{}", &hash[..12])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: &str, timestamp: u64) -> RunRecord {
+        RunRecord {
+            id: id.to_string(),
+            timestamp,
+            ..RunRecord::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn record_and_load_runs_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::new(dir.path().join("repo"));
+        assert!(store.runs().await.unwrap().is_empty());
+
+        store.record(&run("bbbb", 200)).await.unwrap();
+        store.record(&run("aaaa", 100)).await.unwrap();
+        let ids: Vec<String> = store
+            .runs()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, vec!["aaaa", "bbbb"]);
+    }
+
+    #[tokio::test]
+    async fn corrupt_run_files_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::new(dir.path().join("repo"));
+        store.record(&run("aaaa", 100)).await.unwrap();
+        std::fs::write(dir.path().join("repo").join("broken.json"), "{\"id\":").unwrap();
+
+        let runs = store.runs().await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].id, "aaaa");
+    }
+
+    #[test]
+    fn find_run_by_prefix() {
+        let runs = vec![run("ab12", 1), run("ab34", 2), run("cd56", 3)];
+        assert_eq!(find_run(&runs, "cd").unwrap().id, "cd56");
+        assert_eq!(find_run(&runs, "ab34").unwrap().id, "ab34");
+        assert!(matches!(
+            find_run(&runs, "ab"),
+            Err(HistoryError::AmbiguousRun(_))
+        ));
+        assert!(matches!(
+            find_run(&runs, "ff"),
+            Err(HistoryError::UnknownRun(_))
+        ));
+        assert!(find_run(&runs, "").is_err());
+    }
+
+    #[test]
+    fn repo_dir_name_separates_same_named_repos() {
+        let a = repo_dir_name(Path::new("/work/app"));
+        let b = repo_dir_name(Path::new("/home/app"));
+        assert!(a.starts_with("app-"));
+        assert_ne!(a, b);
+    }
+}
@@ -37,3 +37,173 @@ pub fn build_bitbucket_pipelines_client() -> Result<reqwest::Client, reqwest::Er
         .proxy(reqwest::Proxy::all("http://localhost:29418").expect("static proxy URL"))
         .build()
 }
+
+/// Minimal in-process HTTP server for exercising API publishers in tests.
+///
+/// Serves canned responses from a fixed route table and records every
+/// request it receives, so tests can assert on method, path, headers,
+/// and JSON body without reaching a real forge.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::sync::{Arc, Mutex};
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// A canned response for requests matching `method` and `path`.
+    #[derive(Debug, Clone)]
+    pub(crate) struct Route {
+        pub method: &'static str,
+        /// Request path (query string excluded) to match exactly.
+        pub path: String,
+        pub status: u16,
+        pub body: String,
+    }
+
+    impl Route {
+        pub(crate) fn new(method: &'static str, path: &str, status: u16, body: &str) -> Self {
+            Self {
+                method,
+                path: path.to_string(),
+                status,
+                body: body.to_string(),
+            }
+        }
+    }
+
+    /// A request captured by the mock server.
+    #[derive(Debug, Clone)]
+    pub(crate) struct RecordedRequest {
+        pub method: String,
+        /// Full request target, including any query string.
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl RecordedRequest {
+        /// Case-insensitive header lookup.
+        pub(crate) fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+
+        /// Parse the request body as JSON.
+        pub(crate) fn json(&self) -> serde_json::Value {
+            serde_json::from_str(&self.body).unwrap_or(serde_json::Value::Null)
+        }
+    }
+
+    /// A running mock server bound to an ephemeral localhost port.
+    pub(crate) struct MockServer {
+        /// Base URL, e.g. `http://127.0.0.1:41234`.
+        pub url: String,
+        requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    }
+
+    impl MockServer {
+        /// Start serving `routes`. Unmatched requests get a 404.
+        ///
+        /// Routes sharing a method and path are served in order, one per
+        /// request, with the last one repeating — e.g. to poll a job that
+        /// finishes on the third check.
+        pub(crate) async fn start(routes: Vec<Route>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let recorded = Arc::clone(&requests);
+
+            tokio::spawn(async move {
+                while let Ok((mut socket, _)) = listener.accept().await {
+                    let Some(request) = read_request(&mut socket).await else {
+                        continue;
+                    };
+                    let route_path = request.path.split('?').next().unwrap_or_default();
+                    let same_route = |method: &str, path: &str| {
+                        method == request.method && path.split('?').next() == Some(route_path)
+                    };
+                    let matching: Vec<&Route> = routes
+                        .iter()
+                        .filter(|r| same_route(r.method, &r.path))
+                        .collect();
+                    let seen = recorded
+                        .lock()
+                        .unwrap()
+                        .iter()
+                        .filter(|r: &&RecordedRequest| same_route(&r.method, &r.path))
+                        .count();
+                    let (status, body) = matching
+                        .get(seen.min(matching.len().saturating_sub(1)))
+                        .map(|r| (r.status, r.body.clone()))
+                        .unwrap_or((404, r#"{"message":"Not Found"}"#.to_string()));
+                    recorded.lock().unwrap().push(request);
+
+                    let response = format!(
+                        "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\n\
+                         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                }
+            });
+
+            Self { url, requests }
+        }
+
+        /// All requests received so far, in arrival order.
+        pub(crate) fn requests(&self) -> Vec<RecordedRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    /// Read one HTTP/1.1 request (headers plus `Content-Length` body).
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> Option<RecordedRequest> {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let n = socket.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                return None;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+
+        let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+        let mut lines = head.lines();
+        let mut request_line = lines.next()?.split_whitespace();
+        let method = request_line.next()?.to_string();
+        let path = request_line.next()?.to_string();
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|l| l.split_once(':'))
+            .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+            .collect();
+
+        let content_length = headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse::<usize>().ok())
+            .unwrap_or(0);
+        while buf.len() < header_end + content_length {
+            let n = socket.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let body_end = buf.len().min(header_end + content_length);
+        let body = String::from_utf8_lossy(&buf[header_end..body_end]).to_string();
+
+        Some(RecordedRequest {
+            method,
+            path,
+            headers,
+            body,
+        })
+    }
+}
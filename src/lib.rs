@@ -3,13 +3,18 @@
 //! Re-exports public modules for integration tests and external use.
 
 pub mod agents;
+pub mod baseline;
 pub mod cache;
 pub mod ci;
 pub mod config;
 pub mod constants;
 pub mod context;
+pub mod cost;
 pub mod diff;
 pub mod env;
+pub mod fix;
+pub mod glob;
+pub mod history;
 pub mod http;
 pub mod license;
 pub mod models;
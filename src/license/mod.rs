@@ -163,7 +163,7 @@ fn today_ymd() -> Ymd {
 
 /// Convert Unix epoch seconds to (year, month, day) in UTC.
 /// Uses Howard Hinnant's civil_from_days algorithm.
-fn epoch_secs_to_ymd(secs: i64) -> Ymd {
+pub(crate) fn epoch_secs_to_ymd(secs: i64) -> Ymd {
     let mut days = secs / 86400;
     if secs < 0 && secs % 86400 != 0 {
         days -= 1;
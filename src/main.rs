@@ -6,12 +6,15 @@
 mod cli;
 
 use nitpik::agents;
+use nitpik::baseline;
 use nitpik::cache;
 use nitpik::config;
 use nitpik::constants;
 use nitpik::context;
 use nitpik::diff;
 use nitpik::env;
+use nitpik::fix;
+use nitpik::history;
 use nitpik::license;
 use nitpik::models;
 use nitpik::orchestrator;
@@ -30,7 +33,10 @@ use std::sync::Arc;
 use anyhow::{Context, Result, bail};
 use clap::Parser;
 
-use cli::args::{CacheAction, Cli, Command, LicenseAction, OutputFormat, UpdateArgs};
+use cli::args::{
+    BaselineAction, CacheAction, Cli, Command, HistoryAction, LicenseAction, OutputFormat,
+    UpdateArgs,
+};
 use config::Config;
 use env::Env;
 use models::{DEFAULT_PROFILE, Severity};
@@ -53,10 +59,22 @@ async fn run() -> Result<()> {
     let no_telemetry = cli.no_telemetry;
 
     match cli.command {
-        Command::Review(args) => run_review(*args, no_telemetry).await,
+        Command::Review(args) => run_review(*args, no_telemetry, ReviewMode::Report).await,
         Command::Profiles(args) => run_profiles(args).await,
         Command::Validate(args) => run_validate(args).await,
+        Command::Fix(args) => run_fix(args).await,
+        Command::Baseline {
+            action: BaselineAction::Create(args),
+        } => {
+            let args = *args;
+            let mode = ReviewMode::CreateBaseline(args.output);
+            run_review(args.review, no_telemetry, mode).await
+        }
+        Command::Cache {
+            action: CacheAction::Explain(args),
+        } => run_review(args.review, no_telemetry, ReviewMode::ExplainCache).await,
         Command::Cache { action } => run_cache(action).await,
+        Command::History { action } => run_history(action).await,
         Command::License { action } => run_license(action).await,
         Command::Update(args) => run_update(args).await,
         Command::Version => run_version(),
@@ -97,6 +115,9 @@ async fn run_profiles(args: cli::args::ProfilesArgs) -> Result<()> {
         if let Some(ref model) = p.model {
             println!("         {}  {}", "model:".cyan(), model);
         }
+        if let Some(ref provider) = p.provider {
+            println!("         {}  {}", "provider:".cyan(), provider);
+        }
         if !p.tools.is_empty() {
             let tool_names: Vec<_> = p.tools.iter().map(|t| t.name.as_str()).collect();
             println!("         {}  {}", "tools:".cyan(), tool_names.join(", "));
@@ -152,9 +173,95 @@ async fn run_validate(args: cli::args::ValidateArgs) -> Result<()> {
     }
 }
 
+/// Apply structured fixes from a saved JSON report.
+///
+/// Status lines go to stderr so that the diff on stdout (dry run or
+/// `--patch -`) can be piped straight into `git apply`.
+async fn run_fix(args: cli::args::FixArgs) -> Result<()> {
+    use colored::Colorize;
+
+    let findings = fix::load_report(&args.report).await?;
+    let selected = fix::select(&findings, &args.only)?;
+    if selected.is_empty() {
+        eprintln!("No fixes found in {}.", args.report.display());
+        return Ok(());
+    }
+
+    let repo_root = resolve_repo_root(&args.path).await?;
+    let plans = fix::plan(Path::new(&repo_root), &selected).await;
+
+    let mut applicable = 0;
+    let mut conflicts = 0;
+    for outcome in plans.iter().flat_map(|p| &p.outcomes) {
+        let f = &outcome.finding;
+        let range = match f.fix.as_ref() {
+            Some(fx) if fx.end_line > fx.start_line => {
+                format!("{}-{}", fx.start_line, fx.end_line)
+            }
+            Some(fx) => fx.start_line.to_string(),
+            None => f.line.to_string(),
+        };
+        let status = match &outcome.status {
+            fix::FixStatus::Ready => "ready".green(),
+            fix::FixStatus::Relocated(line) => format!("moved to line {line}").yellow(),
+            fix::FixStatus::Conflict(reason) => format!("conflict: {reason}").red(),
+        };
+        if outcome.is_applicable() {
+            applicable += 1;
+        } else {
+            conflicts += 1;
+        }
+        eprintln!(
+            "  #{} {}:{} [{}] {} — {status}",
+            outcome.number,
+            f.file,
+            range,
+            f.agent,
+            f.title.bold()
+        );
+    }
+
+    let diff: String = plans.iter().map(|p| p.diff.as_str()).collect();
+
+    if args.write {
+        fix::write(Path::new(&repo_root), &plans).await?;
+        eprintln!("Applied {applicable} fix(es), skipped {conflicts} with conflicts.");
+    } else if let Some(patch) = args.patch {
+        if patch.as_os_str() == "-" {
+            print!("{diff}");
+        } else {
+            tokio::fs::write(&patch, &diff)
+                .await
+                .with_context(|| format!("failed to write {}", patch.display()))?;
+            eprintln!(
+                "Wrote {applicable} fix(es) to {}, skipped {conflicts} with conflicts.",
+                patch.display()
+            );
+        }
+    } else {
+        print!("{diff}");
+        eprintln!(
+            "{}",
+            "Dry run: re-run with --write to apply, or --patch <FILE> to save the diff.".dimmed()
+        );
+    }
+
+    Ok(())
+}
+
 /// Manage the result cache.
+///
+/// Operates on the local cache only, in the directory `[cache] dir`
+/// selects for the current repository.
 async fn run_cache(action: CacheAction) -> Result<()> {
-    let engine = cache::CacheEngine::new(true);
+    let repo_root = resolve_repo_root(Path::new(".")).await?;
+    let repo_root_path = Path::new(&repo_root);
+    let config =
+        Config::load(Some(repo_root_path), &Env::real()).context("failed to load configuration")?;
+    let mut engine = cache::CacheEngine::new(true);
+    if let Some(dir) = &config.cache.dir {
+        engine = engine.with_dir(repo_root_path.join(dir));
+    }
 
     match action {
         CacheAction::Clear => {
@@ -169,16 +276,176 @@ async fn run_cache(action: CacheAction) -> Result<()> {
             let stats = engine.stats().await.context("failed to read cache stats")?;
             println!("Cache entries: {}", stats.entries);
             println!("Cache size:    {}", stats.human_size());
+            for (heading, breakdown) in [
+                ("By scope:", &stats.by_scope),
+                ("By model:", &stats.by_model),
+            ] {
+                if breakdown.is_empty() {
+                    continue;
+                }
+                println!("\n{heading}");
+                for (name, usage) in breakdown {
+                    let name = if name.is_empty() { "(no branch)" } else { name };
+                    println!(
+                        "  {name:<40} {:>6} entries  {:>10}",
+                        usage.entries,
+                        cache::store::human_size(usage.bytes),
+                    );
+                }
+            }
         }
         CacheAction::Path => match engine.path() {
             Some(p) => println!("{}", p.display()),
             None => bail!("cache directory could not be determined"),
         },
+        CacheAction::Prune(args) => {
+            let (ttl, max_size) = if args.older_than.is_some() || args.max_size.is_some() {
+                (args.older_than, args.max_size)
+            } else {
+                (config.cache.ttl, config.cache.max_size)
+            };
+            if ttl.is_none() && max_size.is_none() {
+                bail!(
+                    "nothing to prune by: pass --older-than or --max-size, or set ttl/max_size under [cache]"
+                );
+            }
+            let removed = engine
+                .prune(ttl, max_size)
+                .await
+                .context("failed to prune cache")?;
+            println!(
+                "Pruned {} cached entry/entries ({}).",
+                removed.entries,
+                removed.human_size(),
+            );
+        }
+        CacheAction::Export(args) => {
+            let scope = match args.scope {
+                Some(scope) => scope,
+                None => diff::git::detect_branch(repo_root_path, &Env::real()).await,
+            };
+            let summary = engine.export_scope(&scope, &args.output).await?;
+            println!(
+                "Exported {} cached entry/entries and {} sidecar(s) for scope '{scope}' to {}.",
+                summary.entries,
+                summary.sidecars,
+                args.output.display(),
+            );
+        }
+        CacheAction::Import(args) => {
+            let summary = engine.import(&args.input).await?;
+            println!(
+                "Imported {} cached entry/entries and {} sidecar(s) from {}.",
+                summary.entries,
+                summary.sidecars,
+                args.input.display(),
+            );
+        }
+        CacheAction::Explain(_) => unreachable!("cache explain runs as a review"),
     }
 
     Ok(())
 }
 
+/// Browse the review history of a repository.
+async fn run_history(action: HistoryAction) -> Result<()> {
+    let path = match &action {
+        HistoryAction::List(args) | HistoryAction::Trends(args) => &args.path,
+        HistoryAction::Diff(args) => &args.path,
+    };
+    let repo_root = resolve_repo_root(path).await?;
+    let repo_root_path = Path::new(&repo_root);
+    let config =
+        Config::load(Some(repo_root_path), &Env::real()).context("failed to load configuration")?;
+    let store = history_store(&config, repo_root_path)?;
+    let runs = store.runs().await?;
+    if runs.is_empty() {
+        println!(
+            "No recorded runs in {}. Enable [history] in the config or pass --history to `nitpik review`.",
+            store.path().display()
+        );
+        return Ok(());
+    }
+
+    match action {
+        HistoryAction::List(args) => {
+            let recent = &runs[runs.len().saturating_sub(args.limit)..];
+            print!("{}", history::render_list(recent));
+        }
+        HistoryAction::Trends(args) => {
+            let recent = &runs[runs.len().saturating_sub(args.limit)..];
+            print!("{}", history::render_trends(recent));
+        }
+        HistoryAction::Diff(args) => {
+            let (old, new) = match (&args.old, &args.new) {
+                (Some(old), Some(new)) => (
+                    history::find_run(&runs, old)?,
+                    history::find_run(&runs, new)?,
+                ),
+                (Some(old), None) => (history::find_run(&runs, old)?, &runs[runs.len() - 1]),
+                _ if runs.len() < 2 => bail!("need at least two recorded runs to compare"),
+                _ => (&runs[runs.len() - 2], &runs[runs.len() - 1]),
+            };
+            let diff = history::diff_runs(old, new);
+            print!("{}", history::render_diff(old, new, &diff));
+        }
+    }
+    Ok(())
+}
+
+/// Open the review history for a repository, honouring `[history] dir`.
+fn history_store(config: &Config, repo_root: &Path) -> Result<history::HistoryStore> {
+    Ok(match &config.history.dir {
+        Some(dir) => history::HistoryStore::new(repo_root.join(dir)),
+        None => history::HistoryStore::for_repo(repo_root)?,
+    })
+}
+
+/// Build the result cache from `[cache]`: the local directory and, when
+/// caching is enabled, the shared remote backend.
+fn cache_engine(config: &Config, repo_root: &Path, enabled: bool) -> Result<cache::CacheEngine> {
+    let mut engine = cache::CacheEngine::new(enabled);
+    if let Some(dir) = &config.cache.dir {
+        engine = engine.with_dir(repo_root.join(dir));
+    }
+    if let Some(remote) = config.cache.remote.as_ref().filter(|_| enabled) {
+        let store = remote_cache(remote).context("failed to set up the remote cache")?;
+        engine = engine.with_shared(Box::new(store));
+    }
+    Ok(engine)
+}
+
+/// Build the remote cache backend described by `[cache.remote]`.
+fn remote_cache(remote: &config::loader::RemoteCacheConfig) -> Result<cache::remote::RemoteStore> {
+    use cache::remote::{Credentials, RemoteAuth};
+
+    let auth = match remote.kind {
+        config::loader::RemoteCacheKind::Http => match &remote.token {
+            Some(token) => RemoteAuth::Bearer(token.clone()),
+            None => RemoteAuth::None,
+        },
+        config::loader::RemoteCacheKind::S3 => {
+            let (Some(access_key_id), Some(secret_access_key)) =
+                (&remote.access_key_id, &remote.secret_access_key)
+            else {
+                bail!(
+                    "an s3 remote cache needs access_key_id and secret_access_key, or the AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY environment variables"
+                );
+            };
+            RemoteAuth::S3(Credentials {
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+                session_token: remote.session_token.clone(),
+                region: remote
+                    .region
+                    .clone()
+                    .unwrap_or_else(|| constants::S3_DEFAULT_REGION.to_string()),
+            })
+        }
+    };
+    Ok(cache::remote::RemoteStore::new(&remote.url, auth)?)
+}
+
 /// Update nitpik to the latest release from GitHub.
 async fn run_update(args: UpdateArgs) -> Result<()> {
     update::run_update(args.force)
@@ -323,12 +590,53 @@ async fn build_commit_log(
     if no_commit_context {
         return Vec::new();
     }
-    if let models::InputMode::GitBase(base_ref) = input_mode {
-        diff::git::git_log(repo_root_path, base_ref, 50)
+    // Uncommitted changes have no commits to describe them
+    let range = match input_mode {
+        models::InputMode::GitBase(base_ref) => format!("{base_ref}..HEAD"),
+        models::InputMode::Commit(commit) => format!("{commit}^!"),
+        models::InputMode::Range(range) => format!("{}..{}", range.base, range.head),
+        _ => return Vec::new(),
+    };
+    diff::git::git_log_range(repo_root_path, &range, 50)
+        .await
+        .unwrap_or_default()
+}
+
+/// Find the pull request description for baseline context: from
+/// `--pr-description-file`, the `--pr` pull request, or the CI environment.
+async fn build_pr_description(
+    file: Option<&Path>,
+    diff_source: &diff::DiffSource,
+    repo_root_path: &Path,
+) -> Result<Option<String>> {
+    if let Some(path) = file {
+        let content = tokio::fs::read_to_string(path)
             .await
-            .unwrap_or_default()
-    } else {
-        Vec::new()
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        return Ok(context::pr_description::compose("", &content));
+    }
+    Ok(match diff_source {
+        diff::DiffSource::PullRequest(pr) => {
+            context::pr_description::compose(&pr.title, &pr.description)
+        }
+        _ => context::pr_description::detect(&Env::real(), repo_root_path).await,
+    })
+}
+
+/// The commit a review covers: the reviewed commit, the head of the
+/// reviewed range or pull request, or `HEAD` for local changes.
+async fn reviewed_commit(
+    input_mode: &models::InputMode,
+    diff_source: &diff::DiffSource,
+    repo_root_path: &Path,
+) -> Option<String> {
+    match (input_mode, diff_source) {
+        (_, diff::DiffSource::PullRequest(pr)) => Some(pr.head_sha.clone()),
+        (models::InputMode::Commit(rev), _) => diff::git::resolve_commit(repo_root_path, rev).await,
+        (models::InputMode::Range(range), _) => {
+            diff::git::resolve_commit(repo_root_path, &range.head).await
+        }
+        _ => diff::git::head_commit(repo_root_path).await,
     }
 }
 
@@ -340,17 +648,24 @@ async fn create_orchestrator(
     progress: Arc<dyn progress::ProgressReporter>,
     no_prior_context: bool,
     max_prior_findings: Option<usize>,
+    review_scope: String,
 ) -> Result<(Arc<dyn ReviewProvider>, orchestrator::ReviewOrchestrator)> {
     let provider: Arc<dyn ReviewProvider> = Arc::new(
         RigProvider::new(config.provider.clone(), repo_root_path.to_path_buf())
             .map_err(|e| anyhow::anyhow!("{e}"))?,
     );
-    let review_scope = diff::git::detect_branch(repo_root_path, &Env::real()).await;
-    let cache = cache::CacheEngine::new(!no_cache);
+    let cache = cache_engine(config, repo_root_path, !no_cache)?;
     let stale_age = std::time::Duration::from_secs(30 * 24 * 60 * 60);
     let _removed = cache.cleanup_stale(stale_age).await;
+    if cache.is_enabled() && (config.cache.ttl.is_some() || config.cache.max_size.is_some()) {
+        if let Err(e) = cache.prune(config.cache.ttl, config.cache.max_size).await {
+            eprintln!("Warning: failed to prune cache: {e}");
+        }
+    }
 
-    let orchestrator = orchestrator::ReviewOrchestrator::new(
+    let fallbacks = fallback_chain(&config.provider, repo_root_path)?;
+
+    let mut orchestrator = orchestrator::ReviewOrchestrator::new(
         Arc::clone(&provider),
         config,
         cache,
@@ -358,11 +673,66 @@ async fn create_orchestrator(
         no_prior_context,
         max_prior_findings,
         review_scope,
-    );
+    )
+    .with_fallbacks(fallbacks);
+    for (name, named) in &config.providers {
+        if name == models::DEFAULT_PROVIDER {
+            bail!("[providers.{name}] is reserved; configure it as [provider]");
+        }
+        let link = chain_link(named, repo_root_path)
+            .map_err(|e| anyhow::anyhow!("provider '{name}': {e}"))?;
+        let fallbacks = fallback_chain(named, repo_root_path)?;
+        orchestrator = orchestrator.with_provider(name, link, fallbacks);
+    }
     Ok((provider, orchestrator))
 }
 
-async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<()> {
+/// Build the provider described by `config` as a link in a fallback chain.
+fn chain_link(
+    config: &config::ProviderConfig,
+    repo_root_path: &Path,
+) -> Result<orchestrator::ChainedProvider> {
+    let provider = RigProvider::new(config.clone(), repo_root_path.to_path_buf())
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(orchestrator::ChainedProvider {
+        provider: Arc::new(provider),
+        name: config.name,
+        model: config.resolved_model().to_string(),
+    })
+}
+
+/// Build the `fallbacks` of a provider, in order.
+fn fallback_chain(
+    config: &config::ProviderConfig,
+    repo_root_path: &Path,
+) -> Result<Vec<orchestrator::ChainedProvider>> {
+    config
+        .fallbacks
+        .iter()
+        .map(|fallback| {
+            chain_link(fallback, repo_root_path)
+                .map_err(|e| anyhow::anyhow!("fallback provider '{}': {e}", fallback.name))
+        })
+        .collect()
+}
+
+/// What to do with the findings of a review run.
+enum ReviewMode {
+    /// Render the findings in the requested output format.
+    Report,
+    /// Record the findings as the baseline, at the given path or the
+    /// default location in the repo root.
+    CreateBaseline(Option<std::path::PathBuf>),
+    /// Report whether each planned task would be served from the cache,
+    /// without calling the LLM.
+    ExplainCache,
+}
+
+async fn run_review(
+    args: cli::args::ReviewArgs,
+    no_telemetry: bool,
+    mode: ReviewMode,
+) -> Result<()> {
     let input_mode = args.validate_input().map_err(|e| anyhow::anyhow!("{e}"))?;
     let repo_root = resolve_repo_root(&args.path).await?;
     let repo_root_path = Path::new(&repo_root);
@@ -371,7 +741,15 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
         Config::load(Some(repo_root_path), &Env::real()).context("failed to load configuration")?;
     let license_claims = verify_license(&config);
 
-    let use_agent = args.agent || config.review.agentic.enabled;
+    let use_agent = args.use_agent(config.review.agentic.enabled);
+    if config.review.agentic.enabled && !use_agent && !args.quiet {
+        let runs = if args.pr.is_some() {
+            "--pr reviews"
+        } else {
+            "batch runs"
+        };
+        eprintln!("Note: agentic review is disabled for {runs}.");
+    }
     let scan_secrets = args.scan_secrets || config.secrets.enabled;
     let scan_threats = args.scan_threats || config.threats.enabled;
 
@@ -387,25 +765,159 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
             parsed_diffs = diff::parser::parse_unified_diff(content);
             &parsed_diffs
         }
+        diff::DiffSource::PullRequest(pr) => {
+            parsed_diffs = diff::parser::parse_unified_diff(&pr.diff);
+            &parsed_diffs
+        }
         diff::DiffSource::Scanned(d) => d,
     };
+    // Excluded, generated and override-ignored files are dropped before any scanning
+    let mut exclude = config.review.exclude.clone();
+    exclude.extend(args.exclude.iter().cloned());
+    let path_filter = diff::filter::PathFilter::new(args.include.clone(), exclude);
+    // A pull request's .gitattributes is read at its head, not from the local checkout
+    let path_filter = match &diff_source {
+        diff::DiffSource::PullRequest(pr) => {
+            match pr.fetch_file(constants::GITATTRIBUTES_FILENAME).await {
+                Some(content) => path_filter.with_attributes(&content),
+                None => path_filter,
+            }
+        }
+        _ => path_filter.with_gitattributes(repo_root_path).await,
+    };
+    let (mut kept_diffs, filtered) = path_filter.apply(diffs);
+    kept_diffs.retain(|d| !config.review.is_ignored(d.path()));
+    let diffs: &[models::FileDiff<'_>] = &kept_diffs;
+    if filtered > 0 && !args.quiet {
+        use colored::Colorize;
+        eprintln!(
+            "{}",
+            format!("Skipped {filtered} excluded or generated file(s).").dimmed()
+        );
+    }
     if diffs.is_empty() {
         eprintln!("No changes to review.");
         return Ok(());
     }
 
     let commit_log = build_commit_log(args.no_commit_context, &input_mode, repo_root_path).await;
-    let baseline = context::build_baseline_context(
+    let content_source = match (&args.content_rev, &diff_source) {
+        (Some(rev), _) => context::files::ContentSource::Revision(rev.clone()),
+        (None, diff::DiffSource::Raw(raw))
+            if matches!(
+                input_mode,
+                models::InputMode::DiffFile(_) | models::InputMode::Stdin
+            ) =>
+        {
+            context::files::ContentSource::for_raw_diff(raw)
+        }
+        (None, diff::DiffSource::PullRequest(pr)) => {
+            context::files::ContentSource::Fetched(pr.fetch_contents(diffs).await)
+        }
+        _ => context::files::ContentSource::for_input(&input_mode),
+    };
+    let pull_request = match &diff_source {
+        diff::DiffSource::PullRequest(pr) => Some(pr),
+        _ => None,
+    };
+    let mut baseline = context::build_baseline_context(
         repo_root_path,
         diffs,
+        &content_source,
         &config,
-        args.no_project_docs,
+        args.no_project_docs || pull_request.is_some(),
         &args.exclude_doc,
         commit_log,
     )
     .await;
+    if let (Some(pr), false) = (pull_request, args.no_project_docs) {
+        baseline.project_docs = context::project_docs::detect_project_docs_with(
+            async |name: &str| pr.fetch_file(name).await,
+            &args.exclude_doc,
+        )
+        .await;
+    }
+    let review_scope = match pull_request {
+        Some(pr) => pr.review_scope(),
+        None => diff::git::detect_branch(repo_root_path, &Env::real()).await,
+    };
+    baseline.pr_description = build_pr_description(
+        args.pr_description_file.as_deref(),
+        &diff_source,
+        repo_root_path,
+    )
+    .await?;
 
     let agent_defs = resolve_agents(&args, &config, diffs, repo_root_path).await?;
+    let path_agents = resolve_path_agents(&args, &config, &agent_defs).await?;
+    config.check_provider_refs(agent_defs.iter().chain(&path_agents).map(|a| &a.profile))?;
+
+    let is_path_scan = matches!(input_mode, models::InputMode::DirectPath(_));
+
+    // Cost estimate: plan every task and exit without calling the LLM.
+    if args.estimate {
+        // Redacted contents feed the cache keys, so build the context as a real run does.
+        let (review_ctx, _) = build_review_context(
+            &args,
+            &config,
+            diffs,
+            baseline,
+            &repo_root,
+            scan_secrets,
+            is_path_scan,
+        )?;
+        let cache = cache_engine(&config, repo_root_path, !args.no_cache)?;
+        let tasks = orchestrator::plan::plan_tasks(
+            &review_ctx,
+            &agent_defs,
+            &path_agents,
+            &config,
+            use_agent,
+        );
+        let estimate = orchestrator::plan::estimate_plan(&tasks, &config, &cache).await;
+        print!("{}", estimate.render());
+        return Ok(());
+    }
+
+    // Cache explanation: plan every task and say why it would miss.
+    if let ReviewMode::ExplainCache = mode {
+        // Keys are built from the redacted context, as in a real run.
+        let (review_ctx, _) = build_review_context(
+            &args,
+            &config,
+            diffs,
+            baseline,
+            &repo_root,
+            scan_secrets,
+            is_path_scan,
+        )?;
+        let cache = cache_engine(&config, repo_root_path, !args.no_cache)?;
+        let tasks = orchestrator::plan::plan_tasks(
+            &review_ctx,
+            &agent_defs,
+            &path_agents,
+            &config,
+            use_agent,
+        );
+        for task in &tasks {
+            let explanation = cache
+                .explain(
+                    task.file(),
+                    &task.agent.profile.name,
+                    &task.model,
+                    &review_scope,
+                    &task.key_components,
+                )
+                .await;
+            println!(
+                "{} [{}, {}]: {explanation}",
+                task.file(),
+                task.agent.profile.name,
+                task.model
+            );
+        }
+        return Ok(());
+    }
 
     // Debug-only: dump constructed prompts and exit without calling the LLM.
     #[cfg(debug_assertions)]
@@ -417,7 +929,7 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
             diffs: diffs.to_vec(),
             baseline,
             repo_root: repo_root.clone(),
-            is_path_scan: matches!(input_mode, models::InputMode::DirectPath(_)),
+            is_path_scan,
         };
 
         for agent in &agent_defs {
@@ -425,15 +937,20 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
                 if d.is_binary {
                     continue;
                 }
-                let chunks = chunker::chunk_diff(d, None);
+                let chunk_lines = config.review.settings_for(d.path()).context.chunk_lines;
+                let chunks = chunker::chunk_diff(d, chunk_lines);
                 for chunk in chunks {
-                    let user_prompt =
+                    let prompt =
                         build_prompt(&chunk, &review_ctx, agent, &agent_defs, None, use_agent);
                     println!("═══ {} × {} ═══", chunk.path(), agent.profile.name);
                     println!("── system prompt ──");
-                    println!("{}", agent.system_prompt);
+                    println!("{}", providers::REVIEW_SYSTEM_PROMPT);
+                    if let Some(context) = prompt.context_block() {
+                        println!("── review context ──");
+                        println!("{context}");
+                    }
                     println!("── user prompt ──");
-                    println!("{user_prompt}");
+                    println!("{}", prompt.task_block(&agent.system_prompt));
                     println!();
                 }
             }
@@ -441,12 +958,12 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
         return Ok(());
     }
 
-    let heartbeat = fire_telemetry(&config, diffs, &agent_defs, &license_claims, no_telemetry);
+    let all_agents: Vec<models::AgentDefinition> =
+        agent_defs.iter().chain(&path_agents).cloned().collect();
+    let heartbeat = fire_telemetry(&config, diffs, &all_agents, &license_claims, no_telemetry);
 
-    let progress = setup_progress(&args, diffs, &agent_defs, &baseline, &license_claims);
-    progress.start();
+    let progress = setup_progress(&args, diffs, &all_agents, &baseline, &license_claims);
 
-    let is_path_scan = matches!(input_mode, models::InputMode::DirectPath(_));
     let (review_context, secret_findings) = build_review_context(
         &args,
         &config,
@@ -464,9 +981,44 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
         Arc::clone(&progress) as Arc<dyn progress::ProgressReporter>,
         args.no_prior_context,
         args.max_prior_findings,
+        review_scope.clone(),
     )
     .await?;
 
+    let mut orchestrator = orchestrator
+        .with_path_agents(path_agents)
+        .with_content_source(content_source.clone());
+    if let Some(max_cost) = args.max_cost {
+        orchestrator = orchestrator.with_budget(nitpik::cost::Budget {
+            max_cost,
+            action: args.budget_action,
+        });
+    }
+
+    // Batch mode: submit before the live display starts so the ID stays
+    // visible, then wait for the results to land in the cache.
+    let mut batch_id = args.resume_batch.clone();
+    if args.batch {
+        batch_id = orchestrator
+            .submit_batch(&review_context, &agent_defs)
+            .await
+            .context("batch submission failed")?;
+        if let Some(id) = &batch_id {
+            eprintln!(
+                "Submitted batch {id}. If interrupted, collect it later with --resume-batch {id}."
+            );
+        }
+    }
+
+    progress.start();
+
+    let batch = match &batch_id {
+        Some(id) => Some(orchestrator.collect_batch(id).await.with_context(|| {
+            format!("batch collection failed; collect it later with --resume-batch {id}")
+        })?),
+        None => None,
+    };
+
     let review_result = orchestrator
         .run(
             &review_context,
@@ -482,6 +1034,45 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
     // Finalize the live progress display before printing threat scanner status.
     progress.finish();
 
+    if let Some(batch) = &batch {
+        if !args.quiet {
+            eprintln!(
+                "Collected {} result(s) from batch {} ({} input + {} output tokens).",
+                batch.collected,
+                batch_id.as_deref().unwrap_or_default(),
+                batch.usage.input_tokens,
+                batch.usage.output_tokens
+            );
+        }
+        if batch.failed > 0 {
+            eprintln!(
+                "Warning: {} batch request(s) failed and were reviewed live",
+                batch.failed
+            );
+        }
+    }
+
+    if review_result.dropped_tasks > 0 {
+        eprintln!(
+            "Warning: skipped {} review task(s) to stay within the ${:.2} budget",
+            review_result.dropped_tasks,
+            args.max_cost.unwrap_or_default()
+        );
+    }
+    if !review_result.partly_reviewed.is_empty() {
+        eprintln!(
+            "Warning: only some agents reviewed {} to stay within the budget",
+            review_result.partly_reviewed.join(", ")
+        );
+    }
+
+    for location in &review_result.missing_suppression_reasons {
+        eprintln!(
+            "Warning: nitpik-ignore at {location} has no reason and was not applied \
+             (use `nitpik-ignore[target]: reason`)"
+        );
+    }
+
     // Threat scanning (pattern scan then optional LLM triage)
     let threat_findings = if scan_threats {
         let mut threat_rules = threat::rules::default_rules();
@@ -578,6 +1169,34 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
             .then(a.line.cmp(&b.line))
     });
 
+    // Known findings are recorded or suppressed before anything is rendered
+    let finding_files = findings.iter().map(|f| f.file.as_str());
+    let sources =
+        context::files::load_full_files(repo_root_path, finding_files, &content_source).await;
+    if let ReviewMode::CreateBaseline(output) = mode {
+        if review_result.failed_tasks > 0 {
+            bail!(
+                "{} review task(s) failed after retries — not writing an incomplete baseline",
+                review_result.failed_tasks
+            );
+        }
+        let path = output.unwrap_or_else(|| repo_root_path.join(constants::BASELINE_FILENAME));
+        let known = baseline::Baseline::from_findings(&findings, &sources);
+        known.save(&path).await?;
+        eprintln!(
+            "Recorded {} finding(s) in {}.",
+            known.entries.len(),
+            path.display()
+        );
+        return Ok(());
+    }
+    let (findings, baseline_suppressed) =
+        apply_baseline(&args, repo_root_path, diffs, findings, &sources).await?;
+    let suppressed = models::finding::SuppressedCounts {
+        inline: review_result.suppressed,
+        baseline: baseline_suppressed,
+    };
+
     let fail_on_severity: Option<Severity> = if args.no_fail {
         None
     } else {
@@ -585,7 +1204,56 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
             .or(config.review.fail_on)
             .or(Some(Severity::Error))
     };
-    render_and_output(&args.format, &findings, fail_on_severity).await;
+    // `--fail-on` applies everywhere; otherwise per-path overrides take precedence
+    let fail_on_for_path = |path: &str| {
+        if args.no_fail || args.fail_on.is_some() {
+            fail_on_severity
+        } else {
+            config
+                .review
+                .settings_for(path)
+                .fail_on
+                .or(fail_on_severity)
+        }
+    };
+    render_and_output(
+        &args.format,
+        &findings,
+        suppressed,
+        &review_result.usage,
+        diffs,
+        !failing_findings(&findings, fail_on_for_path).is_empty(),
+    )
+    .await;
+    if args.history || config.history.enabled {
+        let run = history::RunRecord {
+            branch: review_scope,
+            commit: reviewed_commit(&input_mode, &diff_source, repo_root_path).await,
+            agents: all_agents.iter().map(|a| a.profile.name.clone()).collect(),
+            files: diffs.iter().map(|d| d.path().to_string()).collect(),
+            findings: history::RecordedFinding::from_findings(&findings, &sources),
+            failed_tasks: review_result.failed_tasks,
+            usage: review_result.usage.clone(),
+            ..history::RunRecord::now()
+        };
+        let recorded = match history_store(&config, repo_root_path) {
+            Ok(store) => store.record(&run).await.map_err(anyhow::Error::from),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = recorded {
+            eprintln!("Warning: failed to record review history: {e}");
+        }
+    }
+
+    let usage = &review_result.usage;
+    if args.format == OutputFormat::Terminal
+        && !args.quiet
+        && usage.total.requests + usage.total.cache_hits > 0
+    {
+        use colored::Colorize;
+        eprintln!();
+        eprint!("{}", usage.render().dimmed());
+    }
 
     // Ensure the telemetry POST completes before the runtime shuts down.
     if let Some(h) = heartbeat {
@@ -594,12 +1262,62 @@ async fn run_review(args: cli::args::ReviewArgs, no_telemetry: bool) -> Result<(
 
     determine_exit(
         &findings,
-        fail_on_severity,
+        fail_on_for_path,
         &args.format,
         review_result.failed_tasks,
     )
 }
 
+/// Suppress findings recorded in the baseline file, if there is one.
+///
+/// An explicit `--baseline` must exist; the default file in the repo
+/// root is optional. Prints how many findings were suppressed and how
+/// many baseline entries no longer match anything, and returns the
+/// remaining findings with the suppressed count.
+async fn apply_baseline(
+    args: &cli::args::ReviewArgs,
+    repo_root: &Path,
+    diffs: &[models::FileDiff<'_>],
+    findings: Vec<models::finding::Finding>,
+    sources: &indexmap::IndexMap<String, String>,
+) -> Result<(Vec<models::finding::Finding>, usize)> {
+    if args.no_baseline {
+        return Ok((findings, 0));
+    }
+    let path = match &args.baseline {
+        Some(path) => path.clone(),
+        None => {
+            let default = repo_root.join(constants::BASELINE_FILENAME);
+            if !default.exists() {
+                return Ok((findings, 0));
+            }
+            default
+        }
+    };
+
+    let known = baseline::Baseline::load(&path).await?;
+    let reviewed: std::collections::HashSet<&str> = diffs.iter().map(|d| d.path()).collect();
+    let result = known.suppress(findings, sources, &reviewed);
+
+    if !args.quiet && (result.suppressed > 0 || result.stale > 0) {
+        use colored::Colorize;
+        let mut note = format!(
+            "Baseline: {} known finding(s) suppressed",
+            result.suppressed
+        );
+        if result.stale > 0 {
+            note.push_str(&format!(
+                ", {} entr{} no longer match — run `nitpik baseline create` to refresh",
+                result.stale,
+                if result.stale == 1 { "y" } else { "ies" }
+            ));
+        }
+        eprintln!("{}", note.dimmed());
+    }
+
+    Ok((result.findings, result.suppressed))
+}
+
 /// Verify the license key from config, returning claims and optional
 /// days-until-expiry. Exits the process if the license has expired.
 fn verify_license(config: &Config) -> Option<(license::LicenseClaims, Option<i64>)> {
@@ -731,33 +1449,40 @@ fn setup_progress(
     progress
 }
 
-/// Check findings against the fail-on threshold and task failures.
+/// Findings at or above their file's fail-on threshold.
+fn failing_findings(
+    findings: &[models::finding::Finding],
+    fail_on: impl Fn(&str) -> Option<Severity>,
+) -> Vec<&models::finding::Finding> {
+    findings
+        .iter()
+        .filter(|f| fail_on(&f.file).is_some_and(|threshold| f.severity >= threshold))
+        .collect()
+}
+
+/// Check findings against their file's fail-on threshold and task failures.
 fn determine_exit(
     findings: &[models::finding::Finding],
-    fail_on: Option<Severity>,
+    fail_on: impl Fn(&str) -> Option<Severity>,
     format: &OutputFormat,
     failed_tasks: usize,
 ) -> Result<()> {
-    if let Some(threshold) = fail_on {
-        let failing: Vec<_> = findings
-            .iter()
-            .filter(|f| f.severity >= threshold)
-            .collect();
-        if !failing.is_empty() {
-            if *format == OutputFormat::Terminal {
-                eprintln!();
-            } else {
-                let summary = models::finding::Summary::from_findings(findings);
-                eprintln!(
-                    "\nReview complete: {} error(s), {} warning(s), {} info — failing on {threshold}+",
-                    summary.errors, summary.warnings, summary.info,
-                );
-            }
-            bail!(
-                "found {} finding(s) at or above {threshold} threshold",
-                failing.len(),
+    let failing = failing_findings(findings, &fail_on);
+    // Report the lowest threshold that tripped, since overrides may differ per file
+    if let Some(threshold) = failing.iter().filter_map(|f| fail_on(&f.file)).min() {
+        if *format == OutputFormat::Terminal {
+            eprintln!();
+        } else {
+            let summary = models::finding::Summary::from_findings(findings);
+            eprintln!(
+                "\nReview complete: {} error(s), {} warning(s), {} info — failing on {threshold}+",
+                summary.errors, summary.warnings, summary.info,
             );
         }
+        bail!(
+            "found {} finding(s) at or above {threshold} threshold",
+            failing.len(),
+        );
     }
     if failed_tasks > 0 {
         bail!("{failed_tasks} review task(s) failed after retries — results are incomplete");
@@ -830,6 +1555,27 @@ async fn resolve_agents(
     Ok(agent_defs)
 }
 
+/// Resolve the profiles referenced by `[[review.overrides]]` blocks that
+/// aren't already part of the default selection.
+async fn resolve_path_agents(
+    args: &cli::args::ReviewArgs,
+    config: &Config,
+    agent_defs: &[models::AgentDefinition],
+) -> Result<Vec<models::AgentDefinition>> {
+    let names: Vec<String> = config
+        .review
+        .override_profiles()
+        .into_iter()
+        .filter(|name| !agent_defs.iter().any(|a| a.profile.name == *name))
+        .collect();
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    agents::resolve_profiles(&names, args.profile_dir.as_deref())
+        .await
+        .context("failed to resolve profiles for review overrides")
+}
+
 /// Build review context, optionally scanning and redacting secrets.
 fn build_review_context<'a>(
     args: &cli::args::ReviewArgs,
@@ -887,6 +1633,7 @@ fn build_review_context<'a>(
             file_contents: redacted_contents,
             project_docs: baseline.project_docs.clone(),
             commit_log: baseline.commit_log.clone(),
+            pr_description: baseline.pr_description.clone(),
         },
         repo_root: repo_root.to_string(),
         is_path_scan,
@@ -899,11 +1646,14 @@ fn build_review_context<'a>(
 async fn render_and_output(
     format: &OutputFormat,
     findings: &[models::finding::Finding],
-    fail_on: Option<Severity>,
+    suppressed: models::finding::SuppressedCounts,
+    usage: &nitpik::cost::UsageReport,
+    diffs: &[models::FileDiff<'_>],
+    failed: bool,
 ) {
     use std::io::Write;
 
-    let rendered = format.render(findings);
+    let rendered = format.render_report(findings, suppressed, Some(usage));
     print!("{rendered}");
 
     // Flush stdout so all findings appear before any stderr messages (summary,
@@ -913,8 +1663,8 @@ async fn render_and_output(
 
     let env = Env::real();
 
-    // Publish to external APIs where applicable (Bitbucket, Forgejo)
-    if let Err(e) = format.publish(findings, fail_on, &env).await {
+    // Publish to external APIs where applicable (GitHub, GitLab, Bitbucket, Forgejo)
+    if let Err(e) = format.publish(findings, diffs, failed, &env).await {
         eprintln!("Warning: failed to publish findings: {e}");
     }
 }
@@ -21,6 +21,12 @@ pub struct AgentProfile {
     /// Optional model override for this agent.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
+    /// Optional provider for this agent: the name of a
+    /// `[providers.<name>]` table in the config. When unset, the
+    /// top-level `[provider]` is used. A `[review.profile_providers]`
+    /// entry for this profile takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
     /// Tags for categorization and selection via `--tag`.
     ///
     /// When `--tag` is passed on the CLI, all loaded profiles whose tags
@@ -15,6 +15,9 @@ pub struct BaselineContext {
     /// Commit summaries between the diff base and HEAD (reverse chronological).
     /// Empty when the input is not a git ref diff (e.g. stdin, file, scan).
     pub commit_log: Vec<String>,
+    /// Title and description of the pull request under review, capped in
+    /// length. `None` outside pull requests.
+    pub pr_description: Option<String>,
 }
 
 /// The complete context for a single review request.
@@ -5,6 +5,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use super::ProviderName;
+
 /// Severity level of a finding.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, ValueEnum, JsonSchema,
@@ -91,6 +93,15 @@ impl Severity {
         }
     }
 
+    /// SARIF result level.
+    pub fn as_sarif_level(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        }
+    }
+
     /// Emoji indicator for comment-based renderers.
     pub fn emoji(&self) -> &'static str {
         match self {
@@ -135,6 +146,37 @@ pub struct Finding {
     pub suggestion: Option<String>,
     /// The agent that produced this finding.
     pub agent: String,
+    /// Machine-applicable replacement, when the agent could express one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+    /// The provider and model that produced this finding, which may be a
+    /// fallback rather than the configured provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub served_by: Option<ServedBy>,
+}
+
+/// The provider and model that served a review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServedBy {
+    pub provider: ProviderName,
+    pub model: String,
+}
+
+/// A structured replacement of a line range in the finding's file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Fix {
+    /// First line to replace (1-based, inclusive).
+    pub start_line: u32,
+    /// Last line to replace (1-based, inclusive).
+    pub end_line: u32,
+    /// Text that replaces the whole range. Empty deletes the lines.
+    pub replacement: String,
+    /// The lines being replaced, captured from the reviewed file so a
+    /// later apply can detect that the file has changed since.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub original: Option<String>,
 }
 
 /// Summary statistics for a review run.
@@ -144,6 +186,18 @@ pub struct Summary {
     pub errors: usize,
     pub warnings: usize,
     pub info: usize,
+    /// Findings removed before output (not included in `total`).
+    #[serde(default)]
+    pub suppressed: SuppressedCounts,
+}
+
+/// Number of findings removed before output, by mechanism.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuppressedCounts {
+    /// Silenced by `nitpik-ignore` comments in the code.
+    pub inline: usize,
+    /// Known findings matched by the baseline file.
+    pub baseline: usize,
 }
 
 impl Summary {
@@ -199,6 +253,8 @@ mod tests {
                 message: "m".into(),
                 suggestion: None,
                 agent: "test".into(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "b.rs".into(),
@@ -209,6 +265,8 @@ mod tests {
                 message: "m".into(),
                 suggestion: None,
                 agent: "test".into(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "c.rs".into(),
@@ -219,6 +277,8 @@ mod tests {
                 message: "m".into(),
                 suggestion: None,
                 agent: "test".into(),
+                fix: None,
+                served_by: None,
             },
         ];
         let s = Summary::from_findings(&findings);
@@ -12,7 +12,9 @@ pub mod context;
 pub mod diff;
 pub mod finding;
 
+use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +26,10 @@ pub use finding::Severity;
 /// Default agent profile name used when no profile is specified.
 pub const DEFAULT_PROFILE: &str = "general";
 
+/// Provider reference naming the top-level `[provider]` table, used by
+/// profiles that don't select a named `[providers.<name>]` entry.
+pub const DEFAULT_PROVIDER: &str = "default";
+
 /// The resolved input mode for the review.
 #[derive(Debug, Clone)]
 pub enum InputMode {
@@ -35,6 +41,68 @@ pub enum InputMode {
     GitBase(String),
     /// Directly scan a file or directory.
     DirectPath(PathBuf),
+    /// Review the changes staged in the git index.
+    Staged,
+    /// Review the unstaged changes in the working tree.
+    WorkingTree,
+    /// Review the changes introduced by a single commit.
+    Commit(String),
+    /// Review the changes between two revisions.
+    Range(GitRange),
+    /// Review a pull request fetched from a forge's REST API.
+    PullRequest { number: u64, forge: Forge },
+}
+
+/// Code forges whose pull requests can be reviewed with `--pr`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum Forge {
+    Github,
+    Gitlab,
+    Forgejo,
+    Bitbucket,
+}
+
+/// A git revision range: `base..head`, or `base...head` to diff `head`
+/// against its merge base with `base`. An empty side means `HEAD`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRange {
+    pub base: String,
+    pub head: String,
+    /// Whether the range was written with three dots.
+    pub merge_base: bool,
+}
+
+impl FromStr for GitRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (base, head, merge_base) = if let Some((base, head)) = s.split_once("...") {
+            (base, head, true)
+        } else if let Some((base, head)) = s.split_once("..") {
+            (base, head, false)
+        } else {
+            return Err(format!(
+                "invalid range '{s}': expected A..B, or A...B for merge-base semantics"
+            ));
+        };
+        let side = |rev: &str| match rev.trim() {
+            "" => "HEAD".to_string(),
+            rev => rev.to_string(),
+        };
+        Ok(Self {
+            base: side(base),
+            head: side(head),
+            merge_base,
+        })
+    }
+}
+
+impl fmt::Display for GitRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dots = if self.merge_base { "..." } else { ".." };
+        write!(f, "{}{dots}{}", self.base, self.head)
+    }
 }
 
 /// Supported LLM provider backends.
@@ -143,6 +211,22 @@ impl ProviderName {
 mod tests {
     use super::*;
 
+    #[test]
+    fn git_range_parses_both_forms() {
+        let range: GitRange = "main...feature".parse().unwrap();
+        assert_eq!(range.base, "main");
+        assert_eq!(range.head, "feature");
+        assert!(range.merge_base);
+        assert_eq!(range.to_string(), "main...feature");
+
+        let range: GitRange = "v1.0..".parse().unwrap();
+        assert_eq!(range.head, "HEAD");
+        assert!(!range.merge_base);
+        assert_eq!(range.to_string(), "v1.0..HEAD");
+
+        assert!("main".parse::<GitRange>().is_err());
+    }
+
     #[test]
     fn provider_name_display() {
         assert_eq!(ProviderName::Anthropic.to_string(), "anthropic");
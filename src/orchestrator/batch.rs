@@ -0,0 +1,286 @@
+//! Batch-mode dispatch: submit uncached tasks as one provider batch job
+//! and collect the results into the cache.
+//!
+//! Once a batch is collected, [`ReviewOrchestrator::run`] finds every
+//! task in the cache, so batch results flow through the same dedup,
+//! suppression and output pipeline as live reviews. Requests that
+//! failed inside the batch stay uncached and are reviewed live.
+//!
+//! Each submitted batch is recorded under the cache directory so that
+//! `--resume-batch <id>` can map results back to their tasks after the
+//! process that submitted them has exited.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::KeyComponents;
+use crate::constants::BATCHES_DIR;
+use crate::cost::TokenUsage;
+use crate::models::context::ReviewContext;
+use crate::models::{AgentDefinition, DEFAULT_PROVIDER};
+use crate::progress::TaskStatus;
+use crate::providers::BatchRequest;
+
+use super::{OrchestratorError, ReviewOrchestrator, prompt_with_prior};
+
+/// A submitted batch job and the tasks it covers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BatchRecord {
+    id: String,
+    review_scope: String,
+    tasks: Vec<BatchTask>,
+}
+
+/// One task in a batch, keyed by the request's custom ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct BatchTask {
+    custom_id: String,
+    cache_key: String,
+    /// Absent in records written before key components were tracked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_components: Option<KeyComponents>,
+    file: String,
+    agent: String,
+    model: String,
+}
+
+impl BatchRecord {
+    fn path(dir: &Path, id: &str) -> Result<PathBuf, OrchestratorError> {
+        // IDs come from the provider or the command line; keep them to a
+        // single path component.
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(OrchestratorError::Batch(format!("invalid batch id '{id}'")));
+        }
+        Ok(dir.join(format!("{id}.json")))
+    }
+
+    async fn save(&self, dir: &Path) -> Result<(), OrchestratorError> {
+        let path = Self::path(dir, &self.id)?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| OrchestratorError::Batch(e.to_string()))?;
+        let write = async {
+            tokio::fs::create_dir_all(dir).await?;
+            tokio::fs::write(&path, json).await
+        };
+        write.await.map_err(|e| {
+            OrchestratorError::Batch(format!("failed to write {}: {e}", path.display()))
+        })
+    }
+
+    async fn load(dir: &Path, id: &str) -> Result<Self, OrchestratorError> {
+        let path = Self::path(dir, id)?;
+        let json = tokio::fs::read_to_string(&path).await.map_err(|_| {
+            OrchestratorError::Batch(format!("no pending batch '{id}' in {}", dir.display()))
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|e| OrchestratorError::Batch(format!("invalid {}: {e}", path.display())))
+    }
+}
+
+/// Results collected from a finished batch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchCollection {
+    /// Tasks whose findings were written to the cache.
+    pub collected: usize,
+    /// Tasks the batch did not complete; they are reviewed live.
+    pub failed: usize,
+    /// Tokens reported for the collected tasks.
+    pub usage: TokenUsage,
+}
+
+impl ReviewOrchestrator {
+    /// Submit every uncached task as one batch job.
+    ///
+    /// Returns the batch ID, or `None` when every task is already cached.
+    /// Batch mode is single-turn, so tasks are planned without agentic
+    /// tools. Only tasks for the default provider are batched; profiles
+    /// on a named provider are reviewed live.
+    pub async fn submit_batch(
+        &self,
+        context: &ReviewContext<'_>,
+        agents: &[AgentDefinition],
+    ) -> Result<Option<String>, OrchestratorError> {
+        if context.diffs.is_empty() {
+            return Err(OrchestratorError::NoDiffs);
+        }
+        let dir = self.batch_dir()?;
+
+        let mut requests = Vec::new();
+        let mut tasks = Vec::new();
+        for task in self.plan(context, agents, false) {
+            if task.provider != DEFAULT_PROVIDER || self.cache.get(&task.cache_key).await.is_some()
+            {
+                continue;
+            }
+            let prompt = if self.no_prior_context {
+                task.prompt.clone()
+            } else {
+                prompt_with_prior(
+                    &self.cache,
+                    task.file(),
+                    &task.agent.profile.name,
+                    &task.model,
+                    &task.cache_key,
+                    &self.review_scope,
+                    &task.prompt,
+                    self.max_prior_findings,
+                )
+                .await
+            };
+            let custom_id = format!("task-{}", requests.len());
+            requests.push(BatchRequest {
+                custom_id: custom_id.clone(),
+                model: task.model.clone(),
+                context: prompt.context_block(),
+                prompt: prompt.task_block(&task.agent.system_prompt),
+            });
+            tasks.push(BatchTask {
+                custom_id,
+                cache_key: task.cache_key.clone(),
+                key_components: Some(task.key_components.clone()),
+                file: task.file().to_string(),
+                agent: task.agent.profile.name.clone(),
+                model: task.model.clone(),
+            });
+        }
+        if requests.is_empty() {
+            return Ok(None);
+        }
+
+        let id = self.default_provider().submit_batch(&requests).await?;
+        BatchRecord {
+            id: id.clone(),
+            review_scope: self.review_scope.clone(),
+            tasks,
+        }
+        .save(&dir)
+        .await?;
+        Ok(Some(id))
+    }
+
+    /// Wait for a submitted batch to finish and cache its results.
+    ///
+    /// The batch must have been submitted from this machine, with its
+    /// record still in the cache directory. The record is removed once
+    /// the results are collected.
+    pub async fn collect_batch(
+        &self,
+        batch_id: &str,
+    ) -> Result<BatchCollection, OrchestratorError> {
+        let dir = self.batch_dir()?;
+        let record = BatchRecord::load(&dir, batch_id).await?;
+        for task in &record.tasks {
+            self.progress.update(&task.file, TaskStatus::InProgress);
+        }
+
+        let results = self.default_provider().collect_batch(batch_id).await?;
+        let mut collection = BatchCollection::default();
+        for result in results {
+            let Some(task) = record
+                .tasks
+                .iter()
+                .find(|t| t.custom_id == result.custom_id)
+            else {
+                continue;
+            };
+            let Ok(output) = result.output else {
+                continue;
+            };
+            self.cache.put(&task.cache_key, &output.findings).await;
+            match &task.key_components {
+                Some(components) => {
+                    self.cache
+                        .record_key(
+                            &task.file,
+                            &task.agent,
+                            &task.model,
+                            &record.review_scope,
+                            components,
+                        )
+                        .await
+                }
+                None => {
+                    self.cache
+                        .put_sidecar(
+                            &task.file,
+                            &task.agent,
+                            &task.model,
+                            &task.cache_key,
+                            &record.review_scope,
+                        )
+                        .await
+                }
+            }
+            collection.collected += 1;
+            collection.usage += output.usage;
+            self.batch_usage
+                .lock()
+                .unwrap()
+                .insert(task.cache_key.clone(), output.usage);
+        }
+        collection.failed = record.tasks.len() - collection.collected;
+
+        let _ = tokio::fs::remove_file(BatchRecord::path(&dir, batch_id)?).await;
+        Ok(collection)
+    }
+
+    /// Directory holding pending batch records.
+    ///
+    /// Batch results reach the output pipeline through the cache, so
+    /// batch mode requires it.
+    fn batch_dir(&self) -> Result<PathBuf, OrchestratorError> {
+        self.cache
+            .path()
+            .filter(|_| self.cache.is_enabled())
+            .map(|p| p.join(BATCHES_DIR))
+            .ok_or_else(|| {
+                OrchestratorError::Batch("batch mode needs the result cache".to_string())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_path_rejects_unsafe_ids() {
+        let dir = Path::new("/tmp/batches");
+        assert_eq!(
+            BatchRecord::path(dir, "msgbatch_01-abc").unwrap(),
+            dir.join("msgbatch_01-abc.json")
+        );
+        assert!(BatchRecord::path(dir, "../config").is_err());
+        assert!(BatchRecord::path(dir, "").is_err());
+    }
+
+    #[tokio::test]
+    async fn record_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let record = BatchRecord {
+            id: "batch_1".to_string(),
+            review_scope: "main".to_string(),
+            tasks: vec![BatchTask {
+                custom_id: "task-0".to_string(),
+                cache_key: "abc".to_string(),
+                key_components: Some(KeyComponents::new().with("diff", "+x")),
+                file: "src/main.rs".to_string(),
+                agent: "backend".to_string(),
+                model: "gpt-4o".to_string(),
+            }],
+        };
+        record.save(dir.path()).await.unwrap();
+        assert_eq!(
+            BatchRecord::load(dir.path(), "batch_1").await.unwrap(),
+            record
+        );
+
+        let err = BatchRecord::load(dir.path(), "batch_2").await.unwrap_err();
+        assert!(err.to_string().contains("no pending batch"));
+    }
+}
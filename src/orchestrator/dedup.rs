@@ -155,9 +155,8 @@ fn extract_code_symbols(text: &str) -> Vec<String> {
     symbols
 }
 
-/// Legacy compatibility alias — still used in tests.
-#[allow(dead_code)]
-fn titles_similar(a: &str, b: &str) -> bool {
+/// Returns `true` if more than half the words of two titles are shared.
+pub(crate) fn titles_similar(a: &str, b: &str) -> bool {
     word_overlap(a, b) > 0.5
 }
 
@@ -176,6 +175,8 @@ mod tests {
             message: "msg".into(),
             suggestion: None,
             agent: agent.into(),
+            fix: None,
+            served_by: None,
         }
     }
 
@@ -195,6 +196,8 @@ mod tests {
             message: message.into(),
             suggestion: None,
             agent: agent.into(),
+            fix: None,
+            served_by: None,
         }
     }
 
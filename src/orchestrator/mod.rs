@@ -3,36 +3,55 @@
 //! # Bounded Context: Review Orchestration
 //!
 //! Owns task scheduling (parallel `JoinSet` + semaphore), prompt
-//! assembly, retry logic, deduplication, and result aggregation.
+//! assembly, retry and provider fallback logic, deduplication, and
+//! result aggregation.
 //! Coordinates `providers`, `agents`, `cache`, and `progress` but
 //! delegates all domain work to them.
 //!
-//! Prompt construction lives in [`prompt`], diff-scope filtering in [`scope`].
+//! Task planning and cost estimation live in [`plan`], batch submission
+//! and collection in [`batch`], prompt construction in [`prompt`],
+//! diff-scope filtering in [`scope`], and `nitpik-ignore` comment
+//! handling in [`suppress`].
 
+pub mod batch;
 pub mod dedup;
+pub mod plan;
 pub mod prompt;
 pub mod scope;
+pub mod suppress;
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
+use indexmap::IndexMap;
 use thiserror::Error;
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
-use crate::cache::{self, CacheEngine};
+use crate::cache::{CacheEngine, KeyComponents};
 use crate::config::Config;
-use crate::diff::chunker;
-use crate::models::AgentDefinition;
+use crate::constants::{ESTIMATED_OUTPUT_TOKENS, FALLBACK_AFTER_OVERLOADS};
+use crate::context::files::{ContentSource, load_full_files};
+use crate::cost::{
+    Budget, BudgetAction, Estimate, TaskUsage, TokenUsage, UsageReport, estimate_tokens,
+    select_within_budget,
+};
 use crate::models::context::ReviewContext;
-use crate::models::finding::Finding;
+use crate::models::finding::{Finding, ServedBy};
+use crate::models::{AgentDefinition, DEFAULT_PROVIDER, ProviderName};
 use crate::progress::{ProgressReporter, TaskStatus};
-use crate::providers::ReviewProvider;
-use crate::providers::response::{classify_error, is_retryable, retry_backoff};
+use crate::providers::response::{
+    classify_error, is_overloaded, is_retryable, retry_backoff, validate_fixes,
+};
+use crate::providers::{ReviewOutput, ReviewPrompt, ReviewProvider};
 
 use crate::constants::MAX_RETRIES;
 
-use prompt::{build_prompt, build_prompt_with_prior};
+use plan::{PlannedTask, estimate_plan, plan_tasks};
+use prompt::build_prompt_with_prior;
 use scope::filter_to_diff_scope;
+use suppress::{InlineSuppression, apply_inline_suppressions};
 
 /// Errors from the orchestrator.
 #[derive(Error, Debug)]
@@ -42,6 +61,24 @@ pub enum OrchestratorError {
 
     #[error("no diffs to review")]
     NoDiffs,
+
+    #[error(
+        "estimated cost ${estimated:.4} exceeds the ${max:.4} budget \
+         (use --budget-action drop to review what fits)"
+    )]
+    OverBudget { estimated: f64, max: f64 },
+
+    #[error(
+        "no price known for {} — add it under [pricing] in .nitpik.toml to enforce --max-cost",
+        .0.join(", ")
+    )]
+    UnpricedModels(Vec<String>),
+
+    #[error("batch error: {0}")]
+    Batch(String),
+
+    #[error("profile '{profile}' uses provider '{provider}', which is not configured")]
+    UnknownProvider { profile: String, provider: String },
 }
 
 /// Result of a review run, including partial results from failed tasks.
@@ -51,11 +88,40 @@ pub struct ReviewResult {
     pub findings: Vec<Finding>,
     /// Number of file×agent tasks that failed after retries.
     pub failed_tasks: usize,
+    /// Number of findings silenced by `nitpik-ignore` comments.
+    pub suppressed: usize,
+    /// `file:line` of `nitpik-ignore` comments ignored for lacking a reason.
+    pub missing_suppression_reasons: Vec<String>,
+    /// Number of tasks skipped to stay within the cost budget.
+    pub dropped_tasks: usize,
+    /// Files reviewed by only some of their tasks because the others
+    /// were skipped to stay within the cost budget.
+    pub partly_reviewed: Vec<String>,
+    /// Tokens and cost per agent, per file and in total.
+    pub usage: UsageReport,
+}
+
+/// A provider and the model to request from it, as one link in a
+/// task's fallback chain.
+#[derive(Clone)]
+pub struct ChainedProvider {
+    pub provider: Arc<dyn ReviewProvider>,
+    pub name: ProviderName,
+    pub model: String,
+}
+
+/// A provider tasks can be routed to, with the providers it falls back to.
+#[derive(Clone)]
+struct ProviderChain {
+    primary: ChainedProvider,
+    fallbacks: Vec<ChainedProvider>,
 }
 
 /// Orchestrates parallel review execution across agents and files.
 pub struct ReviewOrchestrator {
-    provider: Arc<dyn ReviewProvider>,
+    /// Providers by name; the one passed to [`new`](Self::new) is
+    /// registered as [`DEFAULT_PROVIDER`].
+    providers: IndexMap<String, ProviderChain>,
     config: Config,
     cache: Arc<CacheEngine>,
     progress: Arc<dyn ProgressReporter>,
@@ -65,6 +131,17 @@ pub struct ReviewOrchestrator {
     max_prior_findings: Option<usize>,
     /// Branch / PR scope for sidecar isolation.
     review_scope: String,
+    /// Profiles selected by `[[review.overrides]]` blocks, looked up by
+    /// name when a file's override picks its own reviewers.
+    path_agents: Vec<AgentDefinition>,
+    /// Optional spending cap checked against the estimate before dispatch.
+    budget: Option<Budget>,
+    /// Where the reviewed files are read from when checking fixes and
+    /// suppression comments.
+    content_source: ContentSource,
+    /// Tokens reported for results collected from a batch, by cache key,
+    /// so [`run`](Self::run) reports them as reviewed rather than cached.
+    batch_usage: Mutex<HashMap<String, TokenUsage>>,
 }
 
 impl ReviewOrchestrator {
@@ -78,19 +155,137 @@ impl ReviewOrchestrator {
         max_prior_findings: Option<usize>,
         review_scope: String,
     ) -> Self {
-        Self {
+        let primary = ChainedProvider {
             provider,
+            name: config.provider.name,
+            model: config.provider.resolved_model().to_string(),
+        };
+        let providers = IndexMap::from([(
+            DEFAULT_PROVIDER.to_string(),
+            ProviderChain {
+                primary,
+                fallbacks: Vec::new(),
+            },
+        )]);
+        Self {
+            providers,
             config: config.clone(),
             cache: Arc::new(cache),
             progress,
             no_prior_context,
             max_prior_findings,
             review_scope,
+            path_agents: Vec::new(),
+            budget: None,
+            content_source: ContentSource::WorkingTree,
+            batch_usage: Mutex::default(),
         }
     }
 
+    /// Provide the agents referenced by per-path profile overrides.
+    ///
+    /// Overrides naming a profile that is neither here nor in the
+    /// default selection passed to [`run`](Self::run) are skipped.
+    pub fn with_path_agents(mut self, agents: Vec<AgentDefinition>) -> Self {
+        self.path_agents = agents;
+        self
+    }
+
+    /// Cap the estimated cost of a run.
+    ///
+    /// When the estimate exceeds the budget, [`run`](Self::run) either
+    /// fails with [`OrchestratorError::OverBudget`] or drops the
+    /// lowest-priority tasks, depending on the budget's action.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Read the reviewed files from `source` instead of the working tree.
+    ///
+    /// Fixes and `nitpik-ignore` comments are checked against these
+    /// files, so they must hold the same revision the diff was taken from.
+    pub fn with_content_source(mut self, source: ContentSource) -> Self {
+        self.content_source = source;
+        self
+    }
+
+    /// Fall back to these providers, in order, when a task's provider
+    /// exhausts its retries or keeps reporting rate limits or overload.
+    ///
+    /// Results are cached under the model that produced them, and each
+    /// finding records it in [`Finding::served_by`].
+    pub fn with_fallbacks(mut self, fallbacks: Vec<ChainedProvider>) -> Self {
+        if let Some(chain) = self.providers.get_mut(DEFAULT_PROVIDER) {
+            chain.fallbacks = fallbacks;
+        }
+        self
+    }
+
+    /// Register a named provider for profiles that select it, either in
+    /// their frontmatter or via `[review.profile_providers]`.
+    ///
+    /// `fallbacks` play the same role as in
+    /// [`with_fallbacks`](Self::with_fallbacks), for this provider only.
+    pub fn with_provider(
+        mut self,
+        name: impl Into<String>,
+        provider: ChainedProvider,
+        fallbacks: Vec<ChainedProvider>,
+    ) -> Self {
+        self.providers.insert(
+            name.into(),
+            ProviderChain {
+                primary: provider,
+                fallbacks,
+            },
+        );
+        self
+    }
+
+    /// The provider registered as [`DEFAULT_PROVIDER`].
+    fn default_provider(&self) -> &Arc<dyn ReviewProvider> {
+        &self.providers[DEFAULT_PROVIDER].primary.provider
+    }
+
+    /// The task's provider, requested with the task's model, followed by
+    /// that provider's fallbacks.
+    fn chain_for(&self, task: &PlannedTask<'_>) -> Result<Vec<ChainedProvider>, OrchestratorError> {
+        let registered = self.providers.get(&task.provider).ok_or_else(|| {
+            OrchestratorError::UnknownProvider {
+                profile: task.agent.profile.name.clone(),
+                provider: task.provider.clone(),
+            }
+        })?;
+        let mut chain = vec![ChainedProvider {
+            model: task.model.clone(),
+            ..registered.primary.clone()
+        }];
+        chain.extend(registered.fallbacks.iter().cloned());
+        Ok(chain)
+    }
+
+    /// Plan every chunk×agent task for a review, in priority order.
+    pub fn plan<'a>(
+        &self,
+        context: &ReviewContext<'a>,
+        agents: &[AgentDefinition],
+        agentic: bool,
+    ) -> Vec<PlannedTask<'a>> {
+        plan_tasks(context, agents, &self.path_agents, &self.config, agentic)
+    }
+
+    /// Estimate the tokens and cost of planned tasks.
+    pub async fn estimate(&self, tasks: &[PlannedTask<'_>]) -> Estimate {
+        estimate_plan(tasks, &self.config, &self.cache).await
+    }
+
     /// Run all agents across all files and return deduplicated findings.
     ///
+    /// `agents` is the default selection; files matched by a
+    /// `[[review.overrides]]` block are reviewed by that block's profiles
+    /// instead, and ignored files are skipped.
+    ///
     /// Returns a `ReviewResult` containing findings from successful reviews
     /// and a count of tasks that failed. Callers should check `failed_tasks`
     /// to decide whether to fail the pipeline.
@@ -110,60 +305,51 @@ impl ReviewOrchestrator {
         let semaphore = Arc::new(Semaphore::new(max_concurrent));
         let mut join_set = JoinSet::new();
 
-        // Collect all (chunk, agent) tasks, then sort smallest-first so
-        // short tasks fill gaps while large tasks are still running.
-        struct Task<'a> {
-            chunk: crate::models::diff::FileDiff<'a>,
-            agent: AgentDefinition,
-            line_count: usize,
-        }
-        let mut tasks: Vec<Task<'_>> = Vec::new();
-        for agent in agents {
-            for diff in &context.diffs {
-                if diff.is_binary {
-                    continue;
-                }
-                let chunks = chunker::chunk_diff(diff, None);
-                for chunk in chunks {
-                    let line_count: usize = chunk.hunks.iter().map(|h| h.lines.len()).sum();
-                    tasks.push(Task {
-                        chunk,
-                        agent: agent.clone(),
-                        line_count,
-                    });
-                }
-            }
-        }
-        tasks.sort_by_key(|t| t.line_count);
+        let mut tasks = self.plan(context, agents, agentic);
+        let (dropped_tasks, partly_reviewed) = self.apply_budget(&mut tasks).await?;
+
+        // Sort smallest-first so short tasks fill gaps while large tasks
+        // are still running.
+        tasks.sort_by_key(|t| t.line_count());
+        let tasks = tasks
+            .into_iter()
+            .map(|task| Ok((self.chain_for(&task)?, task)))
+            .collect::<Result<Vec<_>, OrchestratorError>>()?;
 
-        for Task { chunk, agent, .. } in tasks {
-            let provider = Arc::clone(&self.provider);
+        for (
+            chain,
+            PlannedTask {
+                chunk,
+                agent,
+                model,
+                prompt: base_prompt,
+                key_components,
+                ..
+            },
+        ) in tasks
+        {
             let sem = Arc::clone(&semaphore);
             let cache = Arc::clone(&self.cache);
             let progress = Arc::clone(&self.progress);
             let no_prior_context = self.no_prior_context;
             let max_prior_findings = self.max_prior_findings;
             let review_scope = self.review_scope.clone();
-            let model = agent
-                .profile
-                .model
-                .as_deref()
-                .unwrap_or_else(|| self.config.provider.resolved_model())
-                .to_string();
             let file_path = chunk.path().to_string();
-
-            let base_prompt = build_prompt(&chunk, context, &agent, agents, None, agentic);
-            let cache_key = cache::cache_key(&base_prompt, &agent.profile.name, &model);
+            let batch_usage = self
+                .batch_usage
+                .lock()
+                .unwrap()
+                .remove(&key_components.key());
 
             join_set.spawn(execute_review_task(ReviewTaskParams {
-                provider,
+                chain,
                 cache,
                 progress,
                 sem,
                 file_path,
                 agent,
                 model,
-                cache_key,
+                key_components,
                 review_scope,
                 base_prompt,
                 no_prior_context,
@@ -171,19 +357,24 @@ impl ReviewOrchestrator {
                 agentic,
                 max_turns,
                 max_tool_calls,
+                batch_usage,
             }));
         }
 
         // Collect results from all tasks
         let mut all_findings: Vec<Finding> = Vec::new();
         let mut failed_count: usize = 0;
+        let mut task_usage: Vec<TaskUsage> = Vec::new();
+        let mut failed_files: Vec<String> = Vec::new();
         while let Some(result) = join_set.join_next().await {
             match result {
-                Ok((findings, failed)) => {
-                    all_findings.extend(findings);
-                    if failed {
+                Ok(outcome) => {
+                    all_findings.extend(outcome.findings);
+                    if outcome.failed {
                         failed_count += 1;
+                        failed_files.push(outcome.usage.file.clone());
                     }
+                    task_usage.push(outcome.usage);
                 }
                 Err(e) => {
                     eprintln!("Warning: review task panicked: {e}");
@@ -192,9 +383,37 @@ impl ReviewOrchestrator {
             }
         }
 
+        // Files whose remaining tasks all succeeded still lack the dropped reviews
+        for file in partly_reviewed.iter().filter(|f| !failed_files.contains(f)) {
+            self.progress
+                .update(file, TaskStatus::Partial("over budget".to_string()));
+        }
+
+        // Fixes and suppression comments are checked against the complete
+        // reviewed files — the baseline context may be excerpted or redacted.
+        let finding_paths = context
+            .diffs
+            .iter()
+            .map(|d| d.path())
+            .filter(|path| all_findings.iter().any(|f| f.file == *path));
+        let sources = load_full_files(
+            Path::new(&context.repo_root),
+            finding_paths,
+            &self.content_source,
+        )
+        .await;
+        let all_findings = validate_fixes(all_findings, &sources);
+
         // Deduplicate findings
         let deduped = dedup::deduplicate(all_findings);
 
+        // Drop findings silenced by `nitpik-ignore` comments
+        let InlineSuppression {
+            findings: deduped,
+            suppressed,
+            missing_reason,
+        } = apply_inline_suppressions(deduped, &sources);
+
         // Filter out findings outside diff boundaries (skip for path-based scans
         // where all file content is in scope)
         let scoped = if context.is_path_scan {
@@ -206,40 +425,113 @@ impl ReviewOrchestrator {
         Ok(ReviewResult {
             findings: scoped,
             failed_tasks: failed_count,
+            suppressed,
+            missing_suppression_reasons: missing_reason,
+            dropped_tasks,
+            partly_reviewed,
+            usage: UsageReport::new(&task_usage, &self.config.pricing),
         })
     }
+
+    /// Enforce the budget, if any, on planned tasks.
+    ///
+    /// Returns the number of tasks dropped to fit and the files that lost
+    /// only some of their tasks, or an error when the budget's action is to
+    /// abort. Files that lost every task are marked skipped. Any uncached task whose model has no
+    /// known price is an error, since its cost can't be held to the budget.
+    async fn apply_budget(
+        &self,
+        tasks: &mut Vec<PlannedTask<'_>>,
+    ) -> Result<(usize, Vec<String>), OrchestratorError> {
+        let Some(budget) = self.budget else {
+            return Ok((0, Vec::new()));
+        };
+        let estimate = self.estimate(tasks).await;
+        let unpriced = estimate.unpriced_models();
+        if !unpriced.is_empty() {
+            return Err(OrchestratorError::UnpricedModels(
+                unpriced.into_iter().map(String::from).collect(),
+            ));
+        }
+        let estimated = estimate.total_cost();
+        if estimated <= budget.max_cost {
+            return Ok((0, Vec::new()));
+        }
+        if budget.action == BudgetAction::Abort {
+            return Err(OrchestratorError::OverBudget {
+                estimated,
+                max: budget.max_cost,
+            });
+        }
+        let keep = select_within_budget(&estimate.tasks, budget.max_cost);
+        // (dropped, total) tasks per file
+        let mut per_file: IndexMap<String, (usize, usize)> = IndexMap::new();
+        for (task, &kept) in tasks.iter().zip(&keep) {
+            let counts = per_file.entry(task.file().to_string()).or_default();
+            counts.1 += 1;
+            if !kept {
+                counts.0 += 1;
+            }
+        }
+        let mut keep = keep.into_iter();
+        tasks.retain(|_| keep.next().unwrap_or(true));
+
+        let mut dropped = 0;
+        let mut partly_reviewed = Vec::new();
+        for (file, (dropped_here, total)) in per_file {
+            dropped += dropped_here;
+            if dropped_here == total {
+                self.progress
+                    .update(&file, TaskStatus::Skipped("over budget".to_string()));
+            } else if dropped_here > 0 {
+                partly_reviewed.push(file);
+            }
+        }
+        Ok((dropped, partly_reviewed))
+    }
 }
 
 /// Parameters for a single file×agent review task.
 struct ReviewTaskParams {
-    provider: Arc<dyn ReviewProvider>,
+    /// The task's provider followed by its fallbacks.
+    chain: Vec<ChainedProvider>,
     cache: Arc<CacheEngine>,
     progress: Arc<dyn ProgressReporter>,
     sem: Arc<Semaphore>,
     file_path: String,
     agent: AgentDefinition,
     model: String,
-    cache_key: String,
+    key_components: KeyComponents,
     review_scope: String,
-    base_prompt: String,
+    base_prompt: ReviewPrompt,
     no_prior_context: bool,
     max_prior_findings: Option<usize>,
     agentic: bool,
     max_turns: usize,
     max_tool_calls: usize,
+    /// Tokens reported by the batch the cached result was collected from.
+    batch_usage: Option<TokenUsage>,
+}
+
+/// Outcome of a single file×agent review task.
+struct TaskOutcome {
+    findings: Vec<Finding>,
+    /// Whether the task failed after retries.
+    failed: bool,
+    usage: TaskUsage,
 }
 
 /// Execute a single file×agent review task with caching and retries.
-async fn execute_review_task(params: ReviewTaskParams) -> (Vec<Finding>, bool) {
+async fn execute_review_task(params: ReviewTaskParams) -> TaskOutcome {
     let ReviewTaskParams {
-        provider,
+        chain,
         cache,
         progress,
         sem,
         file_path,
         agent,
         model,
-        cache_key,
+        key_components,
         review_scope,
         base_prompt,
         no_prior_context,
@@ -247,107 +539,214 @@ async fn execute_review_task(params: ReviewTaskParams) -> (Vec<Finding>, bool) {
         agentic,
         max_turns,
         max_tool_calls,
+        batch_usage,
     } = params;
+    let mut usage = TaskUsage {
+        file: file_path.clone(),
+        agent: agent.profile.name.clone(),
+        model: model.clone(),
+        usage: TokenUsage::default(),
+        cached: false,
+        saved: TokenUsage::default(),
+    };
+
     // Check cache first
+    let cache_key = key_components.key();
     if let Some(cached) = cache.get(&cache_key).await {
         cache
-            .put_sidecar(
+            .record_key(
                 &file_path,
                 &agent.profile.name,
                 &model,
-                &cache_key,
                 &review_scope,
+                &key_components,
             )
             .await;
         progress.update(&file_path, TaskStatus::Done);
-        return (cached, false);
+        match batch_usage {
+            Some(batch) => usage.usage = batch,
+            None => {
+                usage.cached = true;
+                usage.saved = TokenUsage {
+                    input_tokens: estimate_tokens(&agent.system_prompt)
+                        + estimate_tokens(&base_prompt.text()),
+                    output_tokens: ESTIMATED_OUTPUT_TOKENS,
+                    ..TokenUsage::default()
+                };
+            }
+        }
+        return TaskOutcome {
+            findings: cached,
+            failed: false,
+            usage,
+        };
     }
 
     // Cache miss — resolve prior findings for the prompt
     let prompt = if no_prior_context {
         base_prompt.clone()
     } else {
-        let prior = cache
-            .get_previous(
-                &file_path,
-                &agent.profile.name,
-                &model,
-                &cache_key,
-                &review_scope,
-            )
-            .await;
-        match prior {
-            Some(mut findings) if !findings.is_empty() => {
-                findings.sort_by(|a, b| b.severity.cmp(&a.severity));
-                if let Some(cap) = max_prior_findings {
-                    findings.truncate(cap);
-                }
-                build_prompt_with_prior(&base_prompt, &findings)
-            }
-            _ => base_prompt.clone(),
-        }
+        prompt_with_prior(
+            &cache,
+            &file_path,
+            &agent.profile.name,
+            &model,
+            &cache_key,
+            &review_scope,
+            &base_prompt,
+            max_prior_findings,
+        )
+        .await
     };
 
     progress.update(&file_path, TaskStatus::InProgress);
     let _permit = sem.acquire().await.expect("semaphore closed");
 
-    match with_retry(
-        &provider,
-        &agent,
-        &prompt,
-        agentic,
-        max_turns,
-        max_tool_calls,
-        &progress,
-        &file_path,
-    )
-    .await
-    {
-        Ok(findings) => {
-            cache.put(&cache_key, &findings).await;
-            cache
-                .put_sidecar(
-                    &file_path,
-                    &agent.profile.name,
-                    &model,
-                    &cache_key,
-                    &review_scope,
-                )
-                .await;
-            progress.update(&file_path, TaskStatus::Done);
-            (findings, false)
+    let mut error = String::new();
+    for (position, link) in chain.iter().enumerate() {
+        let has_fallback = position + 1 < chain.len();
+        // For the primary link this is the model the task was planned
+        // with; for fallbacks it replaces any per-profile model.
+        let mut link_agent = agent.clone();
+        link_agent.profile.model = Some(link.model.clone());
+
+        match with_retry(
+            &link.provider,
+            &link_agent,
+            &prompt,
+            agentic,
+            max_turns,
+            max_tool_calls,
+            &progress,
+            &file_path,
+            has_fallback,
+        )
+        .await
+        {
+            Ok(ReviewOutput {
+                mut findings,
+                usage: tokens,
+            }) => {
+                let served_by = ServedBy {
+                    provider: link.name,
+                    model: link.model.clone(),
+                };
+                for finding in &mut findings {
+                    finding.served_by = Some(served_by.clone());
+                }
+                // Cache under the model that actually produced the result.
+                let served_components = key_components.clone().with("model", &link.model);
+                cache.put(&served_components.key(), &findings).await;
+                cache
+                    .record_key(
+                        &file_path,
+                        &agent.profile.name,
+                        &link.model,
+                        &review_scope,
+                        &served_components,
+                    )
+                    .await;
+                progress.update(&file_path, TaskStatus::Done);
+                usage.model = link.model.clone();
+                usage.usage = tokens;
+                return TaskOutcome {
+                    findings,
+                    failed: false,
+                    usage,
+                };
+            }
+            Err(failure) => {
+                error = failure.message;
+                if !failure.fall_back {
+                    break;
+                }
+            }
         }
-        Err(err_msg) => {
-            progress.update(&file_path, TaskStatus::Failed(err_msg));
-            (Vec::new(), true)
+    }
+
+    progress.update(&file_path, TaskStatus::Failed(error));
+    TaskOutcome {
+        findings: Vec::new(),
+        failed: true,
+        usage,
+    }
+}
+
+/// Add the findings from the previous review of the same file×agent×model
+/// to the body of `base_prompt`, if its cache entry was invalidated by a
+/// change.
+#[allow(clippy::too_many_arguments)] // Shared by live and batch dispatch; mirrors the sidecar lookup key.
+async fn prompt_with_prior(
+    cache: &CacheEngine,
+    file_path: &str,
+    agent_name: &str,
+    model: &str,
+    cache_key: &str,
+    review_scope: &str,
+    base_prompt: &ReviewPrompt,
+    max_prior_findings: Option<usize>,
+) -> ReviewPrompt {
+    let prior = cache
+        .get_previous(file_path, agent_name, model, cache_key, review_scope)
+        .await;
+    match prior {
+        Some(mut findings) if !findings.is_empty() => {
+            findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+            if let Some(cap) = max_prior_findings {
+                findings.truncate(cap);
+            }
+            ReviewPrompt {
+                context: base_prompt.context.clone(),
+                body: build_prompt_with_prior(&base_prompt.body, &findings),
+            }
         }
+        _ => base_prompt.clone(),
     }
 }
 
+/// Why a provider gave up on a task.
+struct RetryFailure {
+    message: String,
+    /// Whether the next provider in the chain should be tried: retries
+    /// were exhausted or the provider kept reporting overload.
+    fall_back: bool,
+}
+
 /// Retry a provider review call with exponential backoff.
 ///
-/// Returns `Ok(findings)` on success or `Err(message)` when retries
-/// are exhausted or a non-retryable error is encountered.
+/// Returns the findings and usage of the successful attempt, or a
+/// failure when retries are exhausted or a non-retryable error is
+/// encountered. When `has_fallback` is set, repeated rate-limit or
+/// overload errors give up early so the next provider can take over.
 #[allow(clippy::too_many_arguments)] // Thin extraction from spawn closure; a one-shot struct adds noise.
 async fn with_retry(
     provider: &Arc<dyn ReviewProvider>,
     agent: &AgentDefinition,
-    prompt: &str,
+    prompt: &ReviewPrompt,
     agentic: bool,
     max_turns: usize,
     max_tool_calls: usize,
     progress: &Arc<dyn ProgressReporter>,
     file_path: &str,
-) -> Result<Vec<Finding>, String> {
+    has_fallback: bool,
+) -> Result<ReviewOutput, RetryFailure> {
     let mut last_err = None;
+    let mut overloads = 0;
 
     for attempt in 0..=MAX_RETRIES {
         match provider
             .review(agent, prompt, agentic, max_turns, max_tool_calls)
             .await
         {
-            Ok(findings) => return Ok(findings),
+            Ok(output) => return Ok(output),
             Err(ref e) if is_retryable(e) && attempt < MAX_RETRIES => {
+                overloads = if is_overloaded(e) { overloads + 1 } else { 0 };
+                if has_fallback && overloads >= FALLBACK_AFTER_OVERLOADS {
+                    return Err(RetryFailure {
+                        message: format!("{e}"),
+                        fall_back: true,
+                    });
+                }
                 let backoff = retry_backoff(attempt);
                 let reason = classify_error(e).unwrap_or("Transient error").to_string();
                 progress.update(
@@ -367,10 +766,16 @@ async fn with_retry(
                 let short = classify_error(&e)
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| format!("{e}"));
-                return Err(short);
+                return Err(RetryFailure {
+                    message: short,
+                    fall_back: is_retryable(&e),
+                });
             }
         }
     }
 
-    Err(last_err.unwrap_or_else(|| "max retries exhausted".to_string()))
+    Err(RetryFailure {
+        message: last_err.unwrap_or_else(|| "max retries exhausted".to_string()),
+        fall_back: true,
+    })
 }
@@ -0,0 +1,388 @@
+//! Task planning: which agent reviews which chunk, and with what prompt.
+//!
+//! Planning is independent of the LLM provider so that cost estimates
+//! (`--estimate`) can be produced without an API key.
+
+use crate::cache::{CacheEngine, KeyComponents};
+use crate::config::Config;
+use crate::config::loader::CacheInvalidation;
+use crate::cost::{Estimate, TaskEstimate};
+use crate::diff::chunker;
+use crate::models::AgentDefinition;
+use crate::models::context::ReviewContext;
+use crate::models::diff::FileDiff;
+use crate::providers::ReviewPrompt;
+
+use super::prompt::{build_prompt, render_diff, render_instructions};
+
+/// A single chunk×agent review task, with its prompt assembled.
+pub struct PlannedTask<'a> {
+    pub chunk: FileDiff<'a>,
+    pub agent: AgentDefinition,
+    /// Name of the provider the task will be sent to (see
+    /// [`Config::provider_ref`]).
+    pub provider: String,
+    /// Model the task will be sent to.
+    pub model: String,
+    /// Prompt, before any prior findings are injected.
+    pub prompt: ReviewPrompt,
+    /// What the cache key is built from (see [`key_components`]).
+    pub key_components: KeyComponents,
+    pub cache_key: String,
+}
+
+impl PlannedTask<'_> {
+    /// Path of the file under review.
+    pub fn file(&self) -> &str {
+        self.chunk.path()
+    }
+
+    /// Number of diff lines in the chunk, used for scheduling.
+    pub fn line_count(&self) -> usize {
+        self.chunk.hunks.iter().map(|h| h.lines.len()).sum()
+    }
+}
+
+/// Build every chunk×agent task for a review, in priority order.
+///
+/// `agents` is the default selection; files matched by a
+/// `[[review.overrides]]` block are reviewed by that block's profiles,
+/// resolved from `path_agents` (or `agents`), and ignored files are
+/// skipped. Tasks are ordered by agent — earlier profiles first, then
+/// override-only profiles — and by file within each agent.
+pub fn plan_tasks<'a>(
+    context: &ReviewContext<'a>,
+    agents: &[AgentDefinition],
+    path_agents: &[AgentDefinition],
+    config: &Config,
+    agentic: bool,
+) -> Vec<PlannedTask<'a>> {
+    let mut tasks: Vec<PlannedTask<'a>> = Vec::new();
+    for diff in &context.diffs {
+        if diff.is_binary || config.review.is_ignored(diff.path()) {
+            continue;
+        }
+        // Every agent reviewing this file, for the coordination note
+        let peers = agents_for(config, diff.path(), agents, path_agents);
+        let chunk_lines = config.review.settings_for(diff.path()).context.chunk_lines;
+        let chunks = chunker::chunk_diff(diff, chunk_lines);
+        for agent in &peers {
+            let provider = config.provider_ref(&agent.profile);
+            let model = agent
+                .profile
+                .model
+                .as_deref()
+                .unwrap_or_else(|| {
+                    config
+                        .named_provider(provider)
+                        .unwrap_or(&config.provider)
+                        .resolved_model()
+                })
+                .to_string();
+            for chunk in &chunks {
+                let prompt = build_prompt(chunk, context, agent, &peers, None, agentic);
+                let key_components = key_components(chunk, context, agent, &peers, agentic, config)
+                    .with("model", &model);
+                tasks.push(PlannedTask {
+                    chunk: chunk.clone(),
+                    agent: agent.clone(),
+                    provider: provider.to_string(),
+                    model: model.clone(),
+                    prompt,
+                    cache_key: key_components.key(),
+                    key_components,
+                });
+            }
+        }
+    }
+
+    let rank = |task: &PlannedTask<'_>| {
+        agents
+            .iter()
+            .chain(path_agents)
+            .position(|a| a.profile.name == task.agent.profile.name)
+            .unwrap_or(usize::MAX)
+    };
+    tasks.sort_by_key(rank);
+    tasks
+}
+
+/// The cache key components of a chunk×agent task, without the model.
+///
+/// Only inputs that shape this file's review are part of the key: the
+/// diff hunks, the file's full content, the agent definition, the
+/// rendered instructions (which name the peer reviewers) and whether the
+/// review is agentic. Context shared by every task — project docs and
+/// the commit log — is left out unless `[cache] invalidate_on` lists
+/// it, so adding a commit or editing `CONTRIBUTING.md` keeps the cache.
+pub fn key_components(
+    chunk: &FileDiff<'_>,
+    context: &ReviewContext<'_>,
+    agent: &AgentDefinition,
+    peers: &[AgentDefinition],
+    agentic: bool,
+    config: &Config,
+) -> KeyComponents {
+    let path = chunk.path();
+    let mut components = KeyComponents::new()
+        .with("diff", &render_diff(chunk))
+        .with(
+            "file",
+            context
+                .baseline
+                .file_contents
+                .get(path)
+                .map_or("", String::as_str),
+        )
+        .with("agent", &serde_json::to_string(agent).unwrap_or_default())
+        .with("instructions", &render_instructions(path, agent, peers))
+        .with("mode", if agentic { "agentic" } else { "standard" });
+    for invalidation in config.cache.invalidate_on.iter().flatten() {
+        components = match invalidation {
+            CacheInvalidation::ProjectDocs => components.with(
+                "project_docs",
+                &serde_json::to_string(&context.baseline.project_docs).unwrap_or_default(),
+            ),
+            CacheInvalidation::CommitLog => {
+                components.with("commit_log", &context.baseline.commit_log.join("\n"))
+            }
+            CacheInvalidation::PrDescription => components.with(
+                "pr_description",
+                context
+                    .baseline
+                    .pr_description
+                    .as_deref()
+                    .unwrap_or_default(),
+            ),
+        };
+    }
+    components
+}
+
+/// Estimate the tokens and cost of planned tasks.
+///
+/// Tasks whose result is already cached count as free.
+pub async fn estimate_plan(
+    tasks: &[PlannedTask<'_>],
+    config: &Config,
+    cache: &CacheEngine,
+) -> Estimate {
+    let mut estimates = Vec::with_capacity(tasks.len());
+    for task in tasks {
+        let cached = cache.get(&task.cache_key).await.is_some();
+        let text = format!("{}\n{}", task.agent.system_prompt, task.prompt.text());
+        estimates.push(TaskEstimate::new(
+            task.file(),
+            &task.agent.profile.name,
+            &task.model,
+            &text,
+            cached,
+            &config.pricing,
+        ));
+    }
+    Estimate { tasks: estimates }
+}
+
+/// Select the agents that review `path`.
+///
+/// Files matched by an override with `profiles` get exactly those
+/// profiles; everything else gets the default selection.
+fn agents_for(
+    config: &Config,
+    path: &str,
+    defaults: &[AgentDefinition],
+    path_agents: &[AgentDefinition],
+) -> Vec<AgentDefinition> {
+    match config.review.settings_for(path).profiles {
+        Some(names) => names
+            .iter()
+            .filter_map(|name| {
+                path_agents
+                    .iter()
+                    .chain(defaults)
+                    .find(|a| a.profile.name == *name)
+                    .cloned()
+            })
+            .collect(),
+        None => defaults.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PathOverride;
+    use crate::models::context::BaselineContext;
+    use crate::models::diff::{DiffLine, DiffLineType, Hunk};
+
+    fn make_diff(path: &str) -> FileDiff<'static> {
+        FileDiff {
+            old_path: path.to_string(),
+            new_path: path.to_string(),
+            is_new: false,
+            is_deleted: false,
+            is_rename: false,
+            is_binary: false,
+            hunks: vec![Hunk {
+                old_start: 1,
+                old_count: 0,
+                new_start: 1,
+                new_count: 1,
+                header: None,
+                lines: vec![DiffLine {
+                    line_type: DiffLineType::Added,
+                    content: "let x = 1;".into(),
+                    old_line_no: None,
+                    new_line_no: Some(1),
+                }],
+            }],
+        }
+    }
+
+    fn context(paths: &[&str]) -> ReviewContext<'static> {
+        ReviewContext {
+            diffs: paths.iter().map(|p| make_diff(p)).collect(),
+            baseline: BaselineContext::default(),
+            repo_root: "/tmp/repo".to_string(),
+            is_path_scan: false,
+        }
+    }
+
+    fn agent(name: &str) -> AgentDefinition {
+        crate::agents::builtin::get_builtin(name).unwrap()
+    }
+
+    #[test]
+    fn plan_orders_by_agent_then_file() {
+        let ctx = context(&["a.rs", "b.rs"]);
+        let agents = vec![agent("security"), agent("backend")];
+        let tasks = plan_tasks(&ctx, &agents, &[], &Config::default(), false);
+        let order: Vec<(&str, &str)> = tasks
+            .iter()
+            .map(|t| (t.agent.profile.name.as_str(), t.file()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("security", "a.rs"),
+                ("security", "b.rs"),
+                ("backend", "a.rs"),
+                ("backend", "b.rs"),
+            ]
+        );
+        assert!(tasks[0].prompt.body.contains("a.rs"));
+        assert_eq!(tasks[0].model, "claude-sonnet-4-20250514");
+    }
+
+    #[test]
+    fn plan_applies_path_overrides() {
+        let ctx = context(&["web/app.ts", "src/main.rs", "gen/schema.rs"]);
+        let mut config = Config::default();
+        config.review.overrides = vec![
+            PathOverride {
+                paths: vec!["web/**".to_string()],
+                profiles: vec!["frontend".to_string()],
+                ..Default::default()
+            },
+            PathOverride {
+                paths: vec!["gen/**".to_string()],
+                ignore: true,
+                ..Default::default()
+            },
+        ];
+        let tasks = plan_tasks(
+            &ctx,
+            &[agent("backend")],
+            &[agent("frontend")],
+            &config,
+            false,
+        );
+        let order: Vec<(&str, &str)> = tasks
+            .iter()
+            .map(|t| (t.agent.profile.name.as_str(), t.file()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![("backend", "src/main.rs"), ("frontend", "web/app.ts")]
+        );
+    }
+
+    #[test]
+    fn plan_resolves_provider_per_profile() {
+        let ctx = context(&["a.rs"]);
+        let mut config = Config::default();
+        config.providers.insert(
+            "local".to_string(),
+            crate::config::ProviderConfig {
+                name: crate::models::ProviderName::Ollama,
+                model: Some("llama3".to_string()),
+                ..Default::default()
+            },
+        );
+        config
+            .review
+            .profile_providers
+            .insert("frontend".to_string(), "local".to_string());
+        let mut pinned = agent("backend");
+        pinned.profile.provider = Some("local".to_string());
+        pinned.profile.model = Some("qwen2.5-coder".to_string());
+
+        let tasks = plan_tasks(
+            &ctx,
+            &[agent("security"), agent("frontend"), pinned],
+            &[],
+            &config,
+            false,
+        );
+        let routes: Vec<(&str, &str)> = tasks
+            .iter()
+            .map(|t| (t.provider.as_str(), t.model.as_str()))
+            .collect();
+        assert_eq!(
+            routes,
+            vec![
+                ("default", "claude-sonnet-4-20250514"),
+                ("local", "llama3"),
+                ("local", "qwen2.5-coder"),
+            ]
+        );
+    }
+
+    #[test]
+    fn cache_key_ignores_shared_context_unless_configured() {
+        let key = |ctx: &ReviewContext<'_>, config: &Config| {
+            plan_tasks(ctx, &[agent("backend")], &[], config, false)[0]
+                .cache_key
+                .clone()
+        };
+        let ctx = context(&["a.rs"]);
+        let mut newer = context(&["a.rs"]);
+        newer.baseline.commit_log = vec!["abc1234 Unrelated change".to_string()];
+        newer
+            .baseline
+            .project_docs
+            .insert("CONTRIBUTING.md".to_string(), "Be nice.".to_string());
+        let config = Config::default();
+        assert_eq!(key(&ctx, &config), key(&newer, &config));
+
+        let mut edited = context(&["a.rs"]);
+        edited.diffs[0].hunks[0].lines[0].content = "let x = 2;".into();
+        assert_ne!(key(&ctx, &config), key(&edited, &config));
+
+        let mut strict = Config::default();
+        strict.cache.invalidate_on = Some(vec![CacheInvalidation::CommitLog]);
+        assert_ne!(key(&ctx, &strict), key(&newer, &strict));
+    }
+
+    #[tokio::test]
+    async fn estimate_plan_counts_every_task() {
+        let ctx = context(&["a.rs", "b.rs"]);
+        let config = Config::default();
+        let tasks = plan_tasks(&ctx, &[agent("backend")], &[], &config, false);
+        let estimate = estimate_plan(&tasks, &config, &CacheEngine::new(false)).await;
+        assert_eq!(estimate.tasks.len(), 2);
+        assert!(estimate.tasks.iter().all(|t| !t.cached));
+        assert!(estimate.tasks[0].input_tokens > 0);
+        assert!(estimate.total_cost() > 0.0);
+    }
+}
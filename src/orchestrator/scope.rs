@@ -81,6 +81,8 @@ mod tests {
             message: "test".into(),
             suggestion: None,
             agent: "test".into(),
+            fix: None,
+            served_by: None,
         }
     }
 
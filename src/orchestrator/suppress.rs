@@ -0,0 +1,286 @@
+//! Inline suppression comments for LLM findings.
+//!
+//! A finding is dropped when its first line, or the line before it,
+//! carries a directive such as:
+//!
+//! ```text
+//! // nitpik-ignore[security]: input is validated by the router
+//! # nitpik-ignore[hardcoded*]: test fixture, not a real credential
+//! <!-- nitpik-ignore[frontend, accessibility]: decorative image -->
+//! ```
+//!
+//! The directive must sit in a comment that runs to the end of the line;
+//! the same text inside a string literal is ignored.
+//!
+//! The bracketed targets are agent names or title patterns (matched
+//! case-insensitively as a substring, or as a whole-title glob when they
+//! contain `*`). The reason after the colon is required: a directive
+//! without one suppresses nothing and is reported instead.
+
+use std::sync::LazyLock;
+
+use indexmap::IndexMap;
+use regex::Regex;
+
+use crate::models::finding::Finding;
+
+/// Matches a `nitpik-ignore[...]` directive inside a line comment or a
+/// block comment (C-style, shell/Python, SQL/Lua, Lisp, TeX, HTML, Haskell)
+/// that ends the line. Group 1 is the comment token.
+static DIRECTIVE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(//|#|--|;|%|/\*|<!--|\{-|^\s*\*)\s*nitpik-ignore\[([^\]]*)\]\s*(?::\s*(.*?))?\s*(?:\*/|-->|-\})?\s*$",
+    )
+    .unwrap()
+});
+
+/// A parsed `nitpik-ignore` directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Directive {
+    /// Agent names or title patterns, as written.
+    targets: Vec<String>,
+    /// Justification; `None` when missing or blank.
+    reason: Option<String>,
+}
+
+impl Directive {
+    /// Parse the directive on a source line, if there is one.
+    fn parse(line: &str) -> Option<Self> {
+        let caps = DIRECTIVE_RE.captures(line)?;
+        let token = caps.get(1)?;
+        if in_string(&line[..token.start()], token.as_str()) {
+            return None;
+        }
+        let targets = caps[2]
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect();
+        let reason = caps
+            .get(3)
+            .map(|m| m.as_str().trim())
+            .filter(|r| !r.is_empty())
+            .map(String::from);
+        Some(Self { targets, reason })
+    }
+
+    /// Returns `true` if any target names the finding's agent or matches its title.
+    fn matches(&self, f: &Finding) -> bool {
+        self.targets
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(&f.agent) || title_matches(t, &f.title))
+    }
+}
+
+/// Returns `true` if `prefix` ends inside a string literal, so the comment
+/// token after it is part of the string rather than a comment.
+///
+/// Single quotes only count for `#` and `--` comments (shell, Python,
+/// SQL, Lua), where they delimit strings; elsewhere they also mark chars,
+/// lifetimes, quoted symbols or apostrophes in prose.
+fn in_string(prefix: &str, token: &str) -> bool {
+    let single_quoted = matches!(token, "#" | "--");
+    let mut open: Option<char> = None;
+    let mut chars = prefix.chars();
+    while let Some(c) = chars.next() {
+        match open {
+            Some(_) if c == '\\' => {
+                chars.next();
+            }
+            Some(q) if c == q => open = None,
+            Some(_) => {}
+            None if c == '"' || c == '`' || (c == '\'' && single_quoted) => open = Some(c),
+            None => {}
+        }
+    }
+    open.is_some()
+}
+
+/// Match a title pattern: whole-title glob with `*`, substring otherwise.
+fn title_matches(pattern: &str, title: &str) -> bool {
+    let title = title.to_lowercase();
+    let pattern = pattern.to_lowercase();
+    if !pattern.contains('*') {
+        return title.contains(&pattern);
+    }
+    let parts: Vec<String> = pattern.split('*').map(regex::escape).collect();
+    Regex::new(&format!("^{}$", parts.join(".*"))).is_ok_and(|re| re.is_match(&title))
+}
+
+/// Findings left after applying inline suppressions.
+#[derive(Debug, Default)]
+pub struct InlineSuppression {
+    /// Findings not silenced by a directive.
+    pub findings: Vec<Finding>,
+    /// Number of findings removed by a directive.
+    pub suppressed: usize,
+    /// `file:line` of matching directives ignored for lacking a reason.
+    pub missing_reason: Vec<String>,
+}
+
+/// Drop findings silenced by a `nitpik-ignore` comment.
+///
+/// `file_contents` must hold complete files keyed by repo-relative path
+/// so that line numbers line up; findings in other files are kept.
+pub fn apply_inline_suppressions(
+    findings: Vec<Finding>,
+    file_contents: &IndexMap<String, String>,
+) -> InlineSuppression {
+    let mut result = InlineSuppression::default();
+
+    for f in findings {
+        let lines: Vec<&str> = file_contents
+            .get(&f.file)
+            .map(|c| c.lines().collect())
+            .unwrap_or_default();
+        let candidates = [f.line, f.line.saturating_sub(1)];
+
+        let mut silenced = false;
+        for line_no in candidates.into_iter().filter(|&n| n >= 1) {
+            let Some(directive) = lines
+                .get(line_no as usize - 1)
+                .and_then(|l| Directive::parse(l))
+            else {
+                continue;
+            };
+            if !directive.matches(&f) {
+                continue;
+            }
+            if directive.reason.is_some() {
+                silenced = true;
+                break;
+            }
+            let location = format!("{}:{line_no}", f.file);
+            if !result.missing_reason.contains(&location) {
+                result.missing_reason.push(location);
+            }
+        }
+
+        if silenced {
+            result.suppressed += 1;
+        } else {
+            result.findings.push(f);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::Severity;
+
+    fn finding(line: u32, title: &str, agent: &str) -> Finding {
+        Finding {
+            file: "src/app.rs".into(),
+            line,
+            end_line: None,
+            severity: Severity::Warning,
+            title: title.into(),
+            message: "Details".into(),
+            suggestion: None,
+            agent: agent.into(),
+            fix: None,
+            served_by: None,
+        }
+    }
+
+    fn contents(content: &str) -> IndexMap<String, String> {
+        IndexMap::from([("src/app.rs".to_string(), content.to_string())])
+    }
+
+    #[test]
+    fn parse_across_comment_syntaxes() {
+        for line in [
+            "let x = 1; // nitpik-ignore[security]: validated upstream",
+            "x = 1  # nitpik-ignore[security]: validated upstream",
+            "SELECT 1; -- nitpik-ignore[security]: validated upstream",
+            "/* nitpik-ignore[security]: validated upstream */",
+            "  * nitpik-ignore[security]: validated upstream",
+            "<!-- nitpik-ignore[security]: validated upstream -->",
+            "{- nitpik-ignore[security]: validated upstream -}",
+        ] {
+            let d = Directive::parse(line).unwrap_or_else(|| panic!("no directive in {line:?}"));
+            assert_eq!(d.targets, vec!["security"]);
+            assert_eq!(d.reason.as_deref(), Some("validated upstream"), "{line:?}");
+        }
+    }
+
+    #[test]
+    fn parse_requires_comment_and_reads_missing_reason() {
+        assert!(Directive::parse(r#"let s = "nitpik-ignore[x]: y";"#).is_none());
+        assert!(Directive::parse(r#"let s = "// nitpik-ignore[x]: y";"#).is_none());
+        assert!(Directive::parse(r#"let s = "a"; f("// nitpik-ignore[x]: y")"#).is_none());
+        assert!(Directive::parse("q = 'SELECT 1 -- nitpik-ignore[x]: y'").is_none());
+        assert!(Directive::parse("let t = `# nitpik-ignore[x]: y`;").is_none());
+        assert!(Directive::parse("// nitpik-ignore[x]: y\nlet z = 1;").is_none());
+        assert!(Directive::parse(r#"f("a\"b"); // nitpik-ignore[x]: y"#).is_some());
+        assert!(Directive::parse("fn f(s: &'static str) {} // nitpik-ignore[x]: y").is_some());
+        let d = Directive::parse("// nitpik-ignore[backend, sql*]").unwrap();
+        assert_eq!(d.targets, vec!["backend", "sql*"]);
+        assert!(d.reason.is_none());
+        assert!(
+            Directive::parse("// nitpik-ignore[backend]:   ")
+                .unwrap()
+                .reason
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn suppresses_on_same_line_or_line_before() {
+        let src = "fn a() {}\n// nitpik-ignore[backend]: intentional\nlet x = y.unwrap();\nlet z = q.unwrap(); // nitpik-ignore[Unwrap]: checked above\nlet w = 1;\n";
+        let result = apply_inline_suppressions(
+            vec![
+                finding(3, "Unchecked unwrap", "backend"),
+                finding(4, "Unwrap may panic", "security"),
+                finding(5, "Magic number", "backend"),
+                finding(1, "Unused fn", "backend"),
+            ],
+            &contents(src),
+        );
+        assert_eq!(result.suppressed, 2);
+        let titles: Vec<_> = result.findings.iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(titles, vec!["Magic number", "Unused fn"]);
+        assert!(result.missing_reason.is_empty());
+    }
+
+    #[test]
+    fn directive_in_string_literal_keeps_finding() {
+        let src = "let msg = \"// nitpik-ignore[backend]: not a comment\";\n";
+        let result =
+            apply_inline_suppressions(vec![finding(1, "Unwrap", "backend")], &contents(src));
+        assert_eq!(result.suppressed, 0);
+        assert_eq!(result.findings.len(), 1);
+    }
+
+    #[test]
+    fn non_matching_target_keeps_finding() {
+        let src = "// nitpik-ignore[frontend]: not relevant\nlet x = y.unwrap();\n";
+        let result =
+            apply_inline_suppressions(vec![finding(2, "Unwrap", "backend")], &contents(src));
+        assert_eq!(result.suppressed, 0);
+        assert_eq!(result.findings.len(), 1);
+    }
+
+    #[test]
+    fn missing_reason_is_reported_not_applied() {
+        let src = "let x = y.unwrap(); // nitpik-ignore[backend]\n";
+        let result =
+            apply_inline_suppressions(vec![finding(1, "Unwrap", "backend")], &contents(src));
+        assert_eq!(result.suppressed, 0);
+        assert_eq!(result.findings.len(), 1);
+        assert_eq!(result.missing_reason, vec!["src/app.rs:1"]);
+    }
+
+    #[test]
+    fn title_glob_patterns() {
+        assert!(title_matches("hardcoded*", "Hardcoded API key"));
+        assert!(title_matches("*injection", "Possible SQL injection"));
+        assert!(!title_matches("*injection", "Injection via header name"));
+        assert!(title_matches("sql", "Possible SQL injection"));
+        assert!(!title_matches("xss", "Possible SQL injection"));
+    }
+}
@@ -3,7 +3,7 @@
 //! Creates reports and annotations via the Bitbucket API using reqwest.
 
 use crate::env::Env;
-use crate::models::finding::{Finding, Severity, Summary};
+use crate::models::finding::{Finding, Summary};
 use crate::output::{OutputFormatter, OutputPublisher};
 use thiserror::Error;
 
@@ -55,14 +55,14 @@ impl OutputFormatter for BitbucketFormatter {
 ///
 /// Posts findings as reports and annotations to the Bitbucket API.
 pub struct BitbucketPublisher<'a> {
-    fail_on: Option<Severity>,
+    failed: bool,
     env: &'a Env,
 }
 
 impl<'a> BitbucketPublisher<'a> {
-    /// Create a new publisher with the given threshold and environment.
-    pub fn new(fail_on: Option<Severity>, env: &'a Env) -> Self {
-        Self { fail_on, env }
+    /// Create a new publisher with the run's pass/fail result and environment.
+    pub fn new(failed: bool, env: &'a Env) -> Self {
+        Self { failed, env }
     }
 }
 
@@ -71,7 +71,7 @@ impl OutputPublisher for BitbucketPublisher<'_> {
         &self,
         findings: &[Finding],
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        post_to_bitbucket(findings, self.fail_on, self.env).await?;
+        post_to_bitbucket(findings, self.failed, self.env).await?;
         Ok(())
     }
 }
@@ -86,12 +86,12 @@ impl OutputPublisher for BitbucketPublisher<'_> {
 ///
 /// Always requires: `BITBUCKET_WORKSPACE`, `BITBUCKET_REPO_SLUG`, `BITBUCKET_COMMIT`.
 ///
-/// `fail_on` controls the report result: if any finding meets or exceeds
-/// the threshold, the report is marked `FAILED`. When `None`, only errors
-/// cause a `FAILED` result.
+/// `failed` is the run's result against its fail-on thresholds (including
+/// per-path overrides), so the report agrees with the exit status: the
+/// report is marked `FAILED` when it is set and `PASSED` otherwise.
 pub async fn post_to_bitbucket(
     findings: &[Finding],
-    fail_on: Option<Severity>,
+    failed: bool,
     env: &Env,
 ) -> Result<(), BitbucketError> {
     let workspace = env
@@ -134,9 +134,7 @@ pub async fn post_to_bitbucket(
 
     // Step 1: Create/update the report
     let summary = Summary::from_findings(findings);
-    let threshold = fail_on.unwrap_or(Severity::Error);
-    let should_fail = findings.iter().any(|f| f.severity >= threshold);
-    let result = if should_fail { "FAILED" } else { "PASSED" };
+    let result = if failed { "FAILED" } else { "PASSED" };
 
     let report_body = serde_json::json!({
         "title": format!("{} Code Review", crate::constants::APP_NAME),
@@ -237,6 +235,8 @@ mod tests {
                 message: "A bug was found".to_string(),
                 suggestion: Some("Fix the bug".to_string()),
                 agent: "backend".to_string(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "src/lib.rs".to_string(),
@@ -247,6 +247,8 @@ mod tests {
                 message: "Style issue".to_string(),
                 suggestion: None,
                 agent: "backend".to_string(),
+                fix: None,
+                served_by: None,
             },
         ]
     }
@@ -272,6 +274,8 @@ mod tests {
                 message: "error".to_string(),
                 suggestion: None,
                 agent: "t".to_string(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "b.rs".to_string(),
@@ -282,6 +286,8 @@ mod tests {
                 message: "warning".to_string(),
                 suggestion: None,
                 agent: "t".to_string(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "c.rs".to_string(),
@@ -292,6 +298,8 @@ mod tests {
                 message: "info".to_string(),
                 suggestion: None,
                 agent: "t".to_string(),
+                fix: None,
+                served_by: None,
             },
         ];
         let output = BitbucketFormatter.format(&findings);
@@ -328,7 +336,7 @@ mod tests {
     async fn post_missing_env_vars_cascade() {
         // Missing BITBUCKET_WORKSPACE
         let env = Env::mock(Vec::<(&str, &str)>::new());
-        let result = post_to_bitbucket(&sample_findings(), None, &env).await;
+        let result = post_to_bitbucket(&sample_findings(), false, &env).await;
         assert!(result.is_err());
         assert!(
             result
@@ -340,7 +348,7 @@ mod tests {
 
         // Missing BITBUCKET_REPO_SLUG
         let env = Env::mock([("BITBUCKET_WORKSPACE", "test-ws")]);
-        let result = post_to_bitbucket(&sample_findings(), None, &env).await;
+        let result = post_to_bitbucket(&sample_findings(), false, &env).await;
         assert!(result.is_err());
         assert!(
             result
@@ -355,7 +363,7 @@ mod tests {
             ("BITBUCKET_WORKSPACE", "test-ws"),
             ("BITBUCKET_REPO_SLUG", "test-repo"),
         ]);
-        let result = post_to_bitbucket(&sample_findings(), None, &env).await;
+        let result = post_to_bitbucket(&sample_findings(), false, &env).await;
         assert!(result.is_err());
         assert!(
             result.unwrap_err().to_string().contains("BITBUCKET_COMMIT"),
@@ -368,7 +376,7 @@ mod tests {
             ("BITBUCKET_REPO_SLUG", "test-repo"),
             ("BITBUCKET_COMMIT", "abc123"),
         ]);
-        let result = post_to_bitbucket(&sample_findings(), None, &env).await;
+        let result = post_to_bitbucket(&sample_findings(), false, &env).await;
         assert!(result.is_err());
         assert!(
             result.unwrap_err().to_string().contains("BITBUCKET_TOKEN"),
@@ -387,7 +395,7 @@ mod tests {
             ("BITBUCKET_COMMIT", "abc123"),
             ("BITBUCKET_BUILD_NUMBER", "42"),
         ]);
-        let result = post_to_bitbucket(&sample_findings(), None, &env).await;
+        let result = post_to_bitbucket(&sample_findings(), false, &env).await;
         // Should fail with an API/connection error, not a missing env var error
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -8,9 +8,17 @@
 //! (`CI_FORGE_URL`, `CI_REPO_OWNER`, `CI_REPO_NAME`,
 //! `CI_COMMIT_PULL_REQUEST`, `CI_COMMIT_SHA`) are provided automatically.
 //! The user only needs to supply `FORGEJO_TOKEN`.
+//!
+//! Re-running on the same pull request edits the summary comment and only
+//! posts findings that are not already there.
+
+use std::collections::{HashMap, HashSet};
 
 use crate::env::Env;
+use crate::models::diff::FileDiff;
 use crate::models::finding::Finding;
+use crate::output::markdown::{comment_body, review_summary};
+use crate::output::reconcile::{self, ExistingComment};
 use crate::output::{OutputFormatter, OutputPublisher};
 use thiserror::Error;
 
@@ -40,7 +48,7 @@ impl OutputFormatter for ForgejoFormatter {
         let comments: Vec<serde_json::Value> = findings
             .iter()
             .map(|f| {
-                let body = format_comment_body(f);
+                let body = comment_body(f);
                 serde_json::json!({
                     "path": f.file,
                     "body": body,
@@ -50,23 +58,7 @@ impl OutputFormatter for ForgejoFormatter {
             })
             .collect();
 
-        let summary = crate::models::finding::Summary::from_findings(findings);
-        let body = format!(
-            "**{}** found {} {} ({} error{}, {} warning{}, {} info)\n\n_{}_",
-            crate::constants::APP_NAME,
-            summary.total,
-            if summary.total == 1 {
-                "finding"
-            } else {
-                "findings"
-            },
-            summary.errors,
-            if summary.errors == 1 { "" } else { "s" },
-            summary.warnings,
-            if summary.warnings == 1 { "" } else { "s" },
-            summary.info,
-            crate::constants::AI_DISCLOSURE,
-        );
+        let body = review_summary(findings);
 
         let review = serde_json::json!({
             "event": "COMMENT",
@@ -78,21 +70,6 @@ impl OutputFormatter for ForgejoFormatter {
     }
 }
 
-/// Format a single finding as a Markdown comment body.
-fn format_comment_body(f: &Finding) -> String {
-    let severity_emoji = f.severity.emoji();
-
-    let mut body = format!(
-        "{} **{}** ({})\n\n{}",
-        severity_emoji, f.title, f.severity, f.message
-    );
-    if let Some(ref suggestion) = f.suggestion {
-        body.push_str(&format!("\n\n**Suggestion:** {suggestion}"));
-    }
-    body.push_str(&format!("\n\n_— agent: {}_", f.agent));
-    body
-}
-
 /// Read a required environment variable or return a [`ForgejoError`].
 fn require_env(env: &Env, name: &str) -> Result<String, ForgejoError> {
     env.var(name)
@@ -101,15 +78,17 @@ fn require_env(env: &Env, name: &str) -> Result<String, ForgejoError> {
 
 /// Forgejo / Gitea pull request review publisher.
 ///
-/// Posts findings as a review via the Forgejo API.
+/// Posts findings as a review via the Forgejo API. Needs the reviewed
+/// diffs to fingerprint findings against their source lines.
 pub struct ForgejoPublisher<'a> {
     env: &'a Env,
+    diffs: &'a [FileDiff<'a>],
 }
 
 impl<'a> ForgejoPublisher<'a> {
-    /// Create a new publisher with the given environment.
-    pub fn new(env: &'a Env) -> Self {
-        Self { env }
+    /// Create a new publisher with the given environment and reviewed diffs.
+    pub fn new(env: &'a Env, diffs: &'a [FileDiff<'a>]) -> Self {
+        Self { env, diffs }
     }
 }
 
@@ -118,15 +97,84 @@ impl OutputPublisher for ForgejoPublisher<'_> {
         &self,
         findings: &[Finding],
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        post_to_forgejo(findings, self.env).await?;
+        post_to_forgejo(findings, self.diffs, self.env).await?;
         Ok(())
     }
 }
 
+/// Authenticated access to one repository's API endpoints.
+struct ForgejoApi {
+    client: reqwest::Client,
+    repo_url: String,
+    token: String,
+}
+
+impl ForgejoApi {
+    /// Start a request to `path` relative to the repository URL.
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{path}", self.repo_url))
+            .header("Authorization", format!("token {}", self.token))
+    }
+
+    /// Send a request, turning non-success statuses into errors.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        what: &str,
+    ) -> Result<reqwest::Response, ForgejoError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ForgejoError::ApiError(e.to_string()))?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<no body>".to_string());
+        Err(ForgejoError::ApiError(format!(
+            "{what} failed with HTTP {status}: {body}"
+        )))
+    }
+
+    /// Fetch every page of a list endpoint.
+    async fn list(&self, path: &str, what: &str) -> Result<Vec<serde_json::Value>, ForgejoError> {
+        let mut items = Vec::new();
+        for page in 1.. {
+            let request = self
+                .request(reqwest::Method::GET, path)
+                .query(&[("limit", LIST_PAGE_SIZE), ("page", page)]);
+            let batch: Vec<serde_json::Value> = self
+                .send(request, what)
+                .await?
+                .json()
+                .await
+                .map_err(|e| ForgejoError::ApiError(format!("invalid {what} response: {e}")))?;
+            let done = batch.len() < LIST_PAGE_SIZE;
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// Page size for list endpoints (Forgejo's default maximum).
+const LIST_PAGE_SIZE: usize = 50;
+
 /// Post findings to the Forgejo/Gitea Pull Request Review API.
 ///
-/// Creates a single review with `event: COMMENT` containing inline
-/// comments for every finding.
+/// Creates a review with `event: COMMENT` containing inline comments for
+/// findings not yet on the pull request, and keeps the summary in a
+/// single issue comment that is edited in place on every run.
+///
+/// Forgejo cannot delete individual review comments through the API, so
+/// findings that disappear are cleaned up per review: once none of a
+/// review's findings are reported any more, the whole review is deleted.
 ///
 /// # Required environment variables
 ///
@@ -138,7 +186,11 @@ impl OutputPublisher for ForgejoPublisher<'_> {
 /// | `CI_COMMIT_PULL_REQUEST` | Woodpecker built-in |
 /// | `CI_COMMIT_SHA` | Woodpecker built-in |
 /// | `FORGEJO_TOKEN` | User-provided API token |
-pub async fn post_to_forgejo(findings: &[Finding], env: &Env) -> Result<(), ForgejoError> {
+pub async fn post_to_forgejo(
+    findings: &[Finding],
+    diffs: &[FileDiff<'_>],
+    env: &Env,
+) -> Result<(), ForgejoError> {
     let forge_url = require_env(env, "CI_FORGE_URL")?;
     let owner = require_env(env, "CI_REPO_OWNER")?;
     let repo = require_env(env, "CI_REPO_NAME")?;
@@ -150,73 +202,111 @@ pub async fn post_to_forgejo(findings: &[Finding], env: &Env) -> Result<(), Forg
         .parse()
         .map_err(|_| ForgejoError::InvalidPrIndex(pr_index_str.clone()))?;
 
-    // Build the review payload
-    let comments: Vec<serde_json::Value> = findings
+    let api = ForgejoApi {
+        client: crate::http::build_client()
+            .map_err(|e| ForgejoError::ApiError(format!("failed to build HTTP client: {e}")))?,
+        repo_url: format!(
+            "{}/api/v1/repos/{owner}/{repo}",
+            forge_url.trim_end_matches('/')
+        ),
+        token,
+    };
+    let pulls = format!("/pulls/{pr_index}");
+
+    // Collect fingerprinted comments from earlier reviews, remembering
+    // which review each one belongs to for cleanup
+    let mut existing = Vec::new();
+    let mut review_of = HashMap::new();
+    let reviews = api
+        .list(&format!("{pulls}/reviews"), "review listing")
+        .await?;
+    for review in &reviews {
+        let Some(review_id) = review["id"].as_u64() else {
+            continue;
+        };
+        if review["comments_count"].as_u64() == Some(0) {
+            continue;
+        }
+        let comments = api
+            .list(
+                &format!("{pulls}/reviews/{review_id}/comments"),
+                "review comment listing",
+            )
+            .await?;
+        for c in &comments {
+            let (Some(id), Some(body)) = (c["id"].as_u64(), c["body"].as_str()) else {
+                continue;
+            };
+            if let Some(fingerprint) = reconcile::extract_fingerprint(body) {
+                existing.push(ExistingComment {
+                    id,
+                    fingerprint: fingerprint.to_string(),
+                    resolved: false,
+                });
+                review_of.insert(id, review_id);
+            }
+        }
+    }
+    let plan = reconcile::plan(findings, diffs, &existing);
+
+    let summary_comment = api
+        .list(&format!("/issues/{pr_index}/comments"), "comment listing")
+        .await?
         .iter()
-        .map(|f| {
-            let body = format_comment_body(f);
-            serde_json::json!({
-                "path": f.file,
-                "body": body,
-                "new_position": f.line,
-                "old_position": 0,
+        .find(|c| c["body"].as_str().is_some_and(reconcile::is_summary))
+        .and_then(|c| c["id"].as_u64());
+    let summary = serde_json::json!({ "body": reconcile::tagged_summary(findings) });
+    let request = match summary_comment {
+        Some(id) => api.request(reqwest::Method::PATCH, &format!("/issues/comments/{id}")),
+        None => api.request(
+            reqwest::Method::POST,
+            &format!("/issues/{pr_index}/comments"),
+        ),
+    };
+    api.send(request.json(&summary), "summary comment").await?;
+
+    if !plan.new.is_empty() {
+        let comments: Vec<serde_json::Value> = plan
+            .new
+            .iter()
+            .map(|(f, fp)| {
+                serde_json::json!({
+                    "path": f.file,
+                    "body": reconcile::tagged_comment_body(f, fp),
+                    "new_position": f.line,
+                    "old_position": 0,
+                })
             })
-        })
-        .collect();
-
-    let summary = crate::models::finding::Summary::from_findings(findings);
-    let review_body = format!(
-        "**{}** found {} {} ({} error{}, {} warning{}, {} info)\n\n_{}_",
-        crate::constants::APP_NAME,
-        summary.total,
-        if summary.total == 1 {
-            "finding"
-        } else {
-            "findings"
-        },
-        summary.errors,
-        if summary.errors == 1 { "" } else { "s" },
-        summary.warnings,
-        if summary.warnings == 1 { "" } else { "s" },
-        summary.info,
-        crate::constants::AI_DISCLOSURE,
-    );
-
-    let review_payload = serde_json::json!({
-        "event": "COMMENT",
-        "body": review_body,
-        "commit_id": commit_sha,
-        "comments": comments,
-    });
+            .collect();
+        let review_payload = serde_json::json!({
+            "event": "COMMENT",
+            "body": "",
+            "commit_id": commit_sha,
+            "comments": comments,
+        });
+        let request = api
+            .request(reqwest::Method::POST, &format!("{pulls}/reviews"))
+            .json(&review_payload);
+        api.send(request, "review creation").await?;
+    }
 
-    let url = format!(
-        "{}/api/v1/repos/{}/{}/pulls/{}/reviews",
-        forge_url.trim_end_matches('/'),
-        owner,
-        repo,
-        pr_index,
-    );
-
-    let client = crate::http::build_client()
-        .map_err(|e| ForgejoError::ApiError(format!("failed to build HTTP client: {e}")))?;
-    let response = client
-        .post(&url)
-        .header("Authorization", format!("token {token}"))
-        .header("Content-Type", "application/json")
-        .json(&review_payload)
-        .send()
-        .await
-        .map_err(|e| ForgejoError::ApiError(e.to_string()))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "<no body>".to_string());
-        return Err(ForgejoError::ApiError(format!(
-            "review creation failed with HTTP {status}: {body}"
-        )));
+    // Delete reviews whose fingerprinted comments are all stale
+    let stale: HashSet<u64> = plan.stale.into_iter().collect();
+    let mut obsolete: Vec<u64> = review_of.values().copied().collect();
+    obsolete.sort_unstable();
+    obsolete.dedup();
+    obsolete.retain(|review_id| {
+        review_of
+            .iter()
+            .filter(|(_, r)| *r == review_id)
+            .all(|(comment_id, _)| stale.contains(comment_id))
+    });
+    for review_id in obsolete {
+        let request = api.request(
+            reqwest::Method::DELETE,
+            &format!("{pulls}/reviews/{review_id}"),
+        );
+        api.send(request, "stale review deletion").await?;
     }
 
     Ok(())
@@ -226,6 +316,7 @@ pub async fn post_to_forgejo(findings: &[Finding], env: &Env) -> Result<(), Forg
 mod tests {
     use super::*;
     use crate::env::Env;
+    use crate::http::mock::{MockServer, Route};
     use crate::models::finding::{Finding, Severity};
 
     fn sample_findings() -> Vec<Finding> {
@@ -239,6 +330,8 @@ mod tests {
                 message: "A bug was found".to_string(),
                 suggestion: Some("Fix the bug".to_string()),
                 agent: "backend".to_string(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "src/lib.rs".to_string(),
@@ -249,6 +342,8 @@ mod tests {
                 message: "Style issue".to_string(),
                 suggestion: None,
                 agent: "backend".to_string(),
+                fix: None,
+                served_by: None,
             },
         ]
     }
@@ -329,6 +424,8 @@ mod tests {
             message: "M".to_string(),
             suggestion: None,
             agent: "a".to_string(),
+            fix: None,
+            served_by: None,
         }];
         let output = ForgejoFormatter.format(&findings);
         let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
@@ -337,29 +434,11 @@ mod tests {
         assert!(!parsed["body"].as_str().unwrap().contains("1 findings"));
     }
 
-    #[test]
-    fn format_comment_body_info_severity() {
-        let f = Finding {
-            file: "x.rs".to_string(),
-            line: 5,
-            end_line: None,
-            severity: Severity::Info,
-            title: "Note".to_string(),
-            message: "Just a note.".to_string(),
-            suggestion: None,
-            agent: "architect".to_string(),
-        };
-        let body = format_comment_body(&f);
-        assert!(body.contains("🔵"));
-        assert!(body.contains("info"));
-        assert!(body.contains("agent: architect"));
-    }
-
     #[tokio::test]
     async fn post_missing_env_vars_cascade() {
         // Missing CI_FORGE_URL
         let env = Env::mock(Vec::<(&str, &str)>::new());
-        let result = post_to_forgejo(&sample_findings(), &env).await;
+        let result = post_to_forgejo(&sample_findings(), &[], &env).await;
         assert!(result.is_err());
         assert!(
             result.unwrap_err().to_string().contains("CI_FORGE_URL"),
@@ -368,7 +447,7 @@ mod tests {
 
         // Missing CI_REPO_OWNER
         let env = Env::mock([("CI_FORGE_URL", "https://codeberg.org")]);
-        let result = post_to_forgejo(&sample_findings(), &env).await;
+        let result = post_to_forgejo(&sample_findings(), &[], &env).await;
         assert!(result.is_err());
         assert!(
             result.unwrap_err().to_string().contains("CI_REPO_OWNER"),
@@ -380,7 +459,7 @@ mod tests {
             ("CI_FORGE_URL", "https://codeberg.org"),
             ("CI_REPO_OWNER", "test-user"),
         ]);
-        let result = post_to_forgejo(&sample_findings(), &env).await;
+        let result = post_to_forgejo(&sample_findings(), &[], &env).await;
         assert!(result.is_err());
         assert!(
             result.unwrap_err().to_string().contains("CI_REPO_NAME"),
@@ -393,7 +472,7 @@ mod tests {
             ("CI_REPO_OWNER", "test-user"),
             ("CI_REPO_NAME", "test-repo"),
         ]);
-        let result = post_to_forgejo(&sample_findings(), &env).await;
+        let result = post_to_forgejo(&sample_findings(), &[], &env).await;
         assert!(result.is_err());
         assert!(
             result
@@ -410,7 +489,7 @@ mod tests {
             ("CI_REPO_NAME", "test-repo"),
             ("CI_COMMIT_PULL_REQUEST", "1"),
         ]);
-        let result = post_to_forgejo(&sample_findings(), &env).await;
+        let result = post_to_forgejo(&sample_findings(), &[], &env).await;
         assert!(result.is_err());
         assert!(
             result.unwrap_err().to_string().contains("CI_COMMIT_SHA"),
@@ -425,7 +504,7 @@ mod tests {
             ("CI_COMMIT_PULL_REQUEST", "1"),
             ("CI_COMMIT_SHA", "abc123"),
         ]);
-        let result = post_to_forgejo(&sample_findings(), &env).await;
+        let result = post_to_forgejo(&sample_findings(), &[], &env).await;
         assert!(result.is_err());
         assert!(
             result.unwrap_err().to_string().contains("FORGEJO_TOKEN"),
@@ -444,9 +523,145 @@ mod tests {
             ("FORGEJO_TOKEN", "tok"),
         ]);
 
-        let result = post_to_forgejo(&sample_findings(), &env).await;
+        let result = post_to_forgejo(&sample_findings(), &[], &env).await;
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
         assert!(err.contains("invalid pull request index"), "got: {err}");
     }
+
+    fn woodpecker_env(server: &MockServer) -> Env {
+        Env::mock([
+            ("CI_FORGE_URL", server.url.as_str()),
+            ("CI_REPO_OWNER", "user"),
+            ("CI_REPO_NAME", "repo"),
+            ("CI_COMMIT_PULL_REQUEST", "3"),
+            ("CI_COMMIT_SHA", "abc123"),
+            ("FORGEJO_TOKEN", "tok"),
+        ])
+    }
+
+    #[tokio::test]
+    async fn post_first_run_creates_summary_and_review() {
+        let server = MockServer::start(vec![
+            Route::new("GET", "/api/v1/repos/user/repo/pulls/3/reviews", 200, "[]"),
+            Route::new(
+                "GET",
+                "/api/v1/repos/user/repo/issues/3/comments",
+                200,
+                "[]",
+            ),
+            Route::new(
+                "POST",
+                "/api/v1/repos/user/repo/issues/3/comments",
+                201,
+                "{}",
+            ),
+            Route::new("POST", "/api/v1/repos/user/repo/pulls/3/reviews", 200, "{}"),
+        ])
+        .await;
+
+        post_to_forgejo(&sample_findings(), &[], &woodpecker_env(&server))
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0].header("authorization"), Some("token tok"));
+        let summary = requests[2].json();
+        assert!(reconcile::is_summary(summary["body"].as_str().unwrap()));
+        let review = requests[3].json();
+        assert_eq!(review["commit_id"], "abc123");
+        let comments = review["comments"].as_array().unwrap();
+        assert_eq!(comments.len(), 2);
+        assert!(reconcile::extract_fingerprint(comments[0]["body"].as_str().unwrap()).is_some());
+    }
+
+    #[tokio::test]
+    async fn post_rerun_skips_posted_and_deletes_obsolete_reviews() {
+        let findings = sample_findings();
+        let fp0 = reconcile::fingerprint(&findings[0], &[]);
+        let fp1 = reconcile::fingerprint(&findings[1], &[]);
+        let reviews = r#"[{"id":10,"comments_count":2},{"id":11,"comments_count":1}]"#;
+        let review_10 = serde_json::json!([
+            { "id": 1, "body": reconcile::marker(&fp0) },
+            { "id": 2, "body": reconcile::marker(&fp1) },
+        ])
+        .to_string();
+        let review_11 = serde_json::json!([
+            { "id": 3, "body": reconcile::marker("vanished") },
+        ])
+        .to_string();
+        let issue_comments = serde_json::json!([
+            { "id": 77, "body": format!("old\n\n{}", reconcile::SUMMARY_MARKER) },
+        ])
+        .to_string();
+        let server = MockServer::start(vec![
+            Route::new(
+                "GET",
+                "/api/v1/repos/user/repo/pulls/3/reviews",
+                200,
+                reviews,
+            ),
+            Route::new(
+                "GET",
+                "/api/v1/repos/user/repo/pulls/3/reviews/10/comments",
+                200,
+                &review_10,
+            ),
+            Route::new(
+                "GET",
+                "/api/v1/repos/user/repo/pulls/3/reviews/11/comments",
+                200,
+                &review_11,
+            ),
+            Route::new(
+                "GET",
+                "/api/v1/repos/user/repo/issues/3/comments",
+                200,
+                &issue_comments,
+            ),
+            Route::new(
+                "PATCH",
+                "/api/v1/repos/user/repo/issues/comments/77",
+                200,
+                "{}",
+            ),
+            Route::new(
+                "DELETE",
+                "/api/v1/repos/user/repo/pulls/3/reviews/11",
+                204,
+                "",
+            ),
+        ])
+        .await;
+
+        post_to_forgejo(&findings, &[], &woodpecker_env(&server))
+            .await
+            .unwrap();
+
+        let calls: Vec<(String, String)> = server
+            .requests()
+            .iter()
+            .map(|r| {
+                (
+                    r.method.clone(),
+                    r.path.split('?').next().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert!(calls.contains(&(
+            "PATCH".into(),
+            "/api/v1/repos/user/repo/issues/comments/77".into()
+        )));
+        assert!(calls.contains(&(
+            "DELETE".into(),
+            "/api/v1/repos/user/repo/pulls/3/reviews/11".into()
+        )));
+        // Nothing new to post, so no review is created
+        assert!(
+            !calls
+                .iter()
+                .any(|(m, p)| m == "POST" && p.ends_with("/reviews"))
+        );
+    }
 }
@@ -1,12 +1,42 @@
-//! GitHub Actions annotation renderer.
+//! GitHub Actions annotation renderer and pull request review publisher.
 //!
 //! Outputs `::warning::` and `::error::` workflow commands that
 //! GitHub Actions intercepts to create inline PR annotations.
+//!
+//! When `GITHUB_TOKEN` is available, findings are additionally posted as
+//! a single pull request review with inline comments via the GitHub REST
+//! API. The repository and pull request are taken from the Actions
+//! environment (`GITHUB_REPOSITORY`, `GITHUB_EVENT_PATH`). Re-running on
+//! the same pull request updates the existing review rather than
+//! posting a duplicate.
 
+use crate::env::Env;
+use crate::models::diff::FileDiff;
 use crate::models::finding::Finding;
-use crate::output::OutputFormatter;
 use crate::output::escape;
+use crate::output::reconcile::{self, ExistingComment};
+use crate::output::{OutputFormatter, OutputPublisher};
 use std::fmt::Write;
+use thiserror::Error;
+
+/// Default GitHub REST API base URL (overridden by `GITHUB_API_URL`).
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Errors from GitHub API calls.
+#[derive(Error, Debug)]
+pub enum GithubError {
+    #[error("missing environment variable: {0}")]
+    MissingEnvVar(String),
+
+    #[error("invalid GITHUB_REPOSITORY (expected owner/repo): {0}")]
+    InvalidRepository(String),
+
+    #[error("invalid event payload: {0}")]
+    InvalidEvent(String),
+
+    #[error("API request failed: {0}")]
+    ApiError(String),
+}
 
 /// GitHub Actions annotation renderer.
 pub struct GithubFormatter;
@@ -56,9 +86,283 @@ impl OutputFormatter for GithubFormatter {
     }
 }
 
+/// Read a required environment variable or return a [`GithubError`].
+fn require_env(env: &Env, name: &str) -> Result<String, GithubError> {
+    env.var(name)
+        .map_err(|_| GithubError::MissingEnvVar(name.into()))
+}
+
+/// GitHub pull request review publisher.
+///
+/// Posts findings as a review via the GitHub REST API. Needs the
+/// reviewed diffs to fingerprint findings against their source lines.
+pub struct GithubPublisher<'a> {
+    env: &'a Env,
+    diffs: &'a [FileDiff<'a>],
+}
+
+impl<'a> GithubPublisher<'a> {
+    /// Create a new publisher with the given environment and reviewed diffs.
+    pub fn new(env: &'a Env, diffs: &'a [FileDiff<'a>]) -> Self {
+        Self { env, diffs }
+    }
+}
+
+impl OutputPublisher for GithubPublisher<'_> {
+    async fn publish(
+        &self,
+        findings: &[Finding],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        post_to_github(findings, self.diffs, self.env).await?;
+        Ok(())
+    }
+}
+
+/// Returns `true` when the workflow was triggered by a pull request
+/// (`pull_request` or `pull_request_target`), the only events that can
+/// carry a review.
+pub fn is_pull_request_event(env: &Env) -> bool {
+    env.var("GITHUB_EVENT_NAME")
+        .is_ok_and(|name| name == "pull_request" || name == "pull_request_target")
+}
+
+/// Pull request number and head commit extracted from the event payload.
+#[derive(Debug, PartialEq)]
+struct PullRequestRef {
+    number: u64,
+    head_sha: String,
+}
+
+/// Extract the pull request reference from a GitHub Actions event payload.
+///
+/// Only `pull_request` and `pull_request_target` events carry the
+/// `pull_request` object; other events are rejected.
+fn parse_event_payload(payload: &str) -> Result<PullRequestRef, GithubError> {
+    let event: serde_json::Value =
+        serde_json::from_str(payload).map_err(|e| GithubError::InvalidEvent(e.to_string()))?;
+    let pr = event
+        .get("pull_request")
+        .ok_or_else(|| GithubError::InvalidEvent("not a pull_request event".into()))?;
+    let number = pr["number"]
+        .as_u64()
+        .ok_or_else(|| GithubError::InvalidEvent("missing pull_request.number".into()))?;
+    let head_sha = pr["head"]["sha"]
+        .as_str()
+        .ok_or_else(|| GithubError::InvalidEvent("missing pull_request.head.sha".into()))?
+        .to_string();
+    Ok(PullRequestRef { number, head_sha })
+}
+
+/// Build a review comment anchored to the finding's line range.
+///
+/// Multi-line findings use `start_line`/`line` so the comment spans the
+/// whole range; all comments target the new side of the diff.
+fn review_comment(f: &Finding, body: String) -> serde_json::Value {
+    let mut comment = serde_json::json!({
+        "path": f.file,
+        "body": body,
+        "side": "RIGHT",
+    });
+    match f.end_line {
+        Some(end) if end > f.line => {
+            comment["start_line"] = f.line.into();
+            comment["start_side"] = "RIGHT".into();
+            comment["line"] = end.into();
+        }
+        _ => comment["line"] = f.line.into(),
+    }
+    comment
+}
+
+/// Authenticated access to one repository's REST endpoints.
+struct GithubApi {
+    client: reqwest::Client,
+    repo_url: String,
+    token: String,
+}
+
+impl GithubApi {
+    /// Start a request to `path` relative to the repository URL.
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}{path}", self.repo_url))
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", crate::constants::APP_NAME)
+    }
+
+    /// Send a request, turning non-success statuses into errors.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        what: &str,
+    ) -> Result<reqwest::Response, GithubError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| GithubError::ApiError(e.to_string()))?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<no body>".to_string());
+        Err(GithubError::ApiError(format!(
+            "{what} failed with HTTP {status}: {body}"
+        )))
+    }
+
+    /// Fetch every page of a list endpoint.
+    async fn list(&self, path: &str, what: &str) -> Result<Vec<serde_json::Value>, GithubError> {
+        let mut items = Vec::new();
+        for page in 1.. {
+            let request = self
+                .request(reqwest::Method::GET, path)
+                .query(&[("per_page", LIST_PAGE_SIZE), ("page", page)]);
+            let batch: Vec<serde_json::Value> = self
+                .send(request, what)
+                .await?
+                .json()
+                .await
+                .map_err(|e| GithubError::ApiError(format!("invalid {what} response: {e}")))?;
+            let done = batch.len() < LIST_PAGE_SIZE;
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// Page size for list endpoints (GitHub's maximum).
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Post findings to the GitHub Pull Request Reviews API.
+///
+/// Publishing is idempotent across runs. The first run creates a single
+/// review with `event: COMMENT`, the summary as its body, and an inline
+/// comment per finding. Later runs edit that review's summary in place,
+/// add inline comments only for findings not already posted, and delete
+/// earlier comments whose finding is no longer reported. Comments are
+/// matched through the fingerprint markers from [`reconcile`].
+///
+/// # Required environment variables
+///
+/// | Variable | Source |
+/// |---|---|
+/// | `GITHUB_REPOSITORY` | Actions built-in (`owner/repo`) |
+/// | `GITHUB_EVENT_PATH` | Actions built-in (`pull_request` event payload) |
+/// | `GITHUB_API_URL` | Actions built-in (optional, defaults to `https://api.github.com`) |
+/// | `GITHUB_TOKEN` | Workflow token with `pull-requests: write` |
+pub async fn post_to_github(
+    findings: &[Finding],
+    diffs: &[FileDiff<'_>],
+    env: &Env,
+) -> Result<(), GithubError> {
+    let repository = require_env(env, "GITHUB_REPOSITORY")?;
+    let event_path = require_env(env, "GITHUB_EVENT_PATH")?;
+    let token = require_env(env, "GITHUB_TOKEN")?;
+    let api_url = env
+        .var("GITHUB_API_URL")
+        .unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+
+    let (owner, repo) = repository
+        .split_once('/')
+        .filter(|(o, r)| !o.is_empty() && !r.is_empty())
+        .ok_or_else(|| GithubError::InvalidRepository(repository.clone()))?;
+
+    let payload = std::fs::read_to_string(&event_path)
+        .map_err(|e| GithubError::InvalidEvent(format!("{event_path}: {e}")))?;
+    let pr = parse_event_payload(&payload)?;
+
+    let api = GithubApi {
+        client: crate::http::build_client()
+            .map_err(|e| GithubError::ApiError(format!("failed to build HTTP client: {e}")))?,
+        repo_url: format!("{}/repos/{owner}/{repo}", api_url.trim_end_matches('/')),
+        token,
+    };
+    let pulls = format!("/pulls/{}", pr.number);
+
+    let existing: Vec<ExistingComment<u64>> = api
+        .list(&format!("{pulls}/comments"), "comment listing")
+        .await?
+        .iter()
+        .filter_map(|c| {
+            let fingerprint = reconcile::extract_fingerprint(c["body"].as_str()?)?;
+            Some(ExistingComment {
+                id: c["id"].as_u64()?,
+                fingerprint: fingerprint.to_string(),
+                resolved: false,
+            })
+        })
+        .collect();
+    let plan = reconcile::plan(findings, diffs, &existing);
+
+    let summary_review = api
+        .list(&format!("{pulls}/reviews"), "review listing")
+        .await?
+        .iter()
+        .find(|r| r["body"].as_str().is_some_and(reconcile::is_summary))
+        .and_then(|r| r["id"].as_u64());
+
+    let summary = reconcile::tagged_summary(findings);
+    let new_comments = plan
+        .new
+        .iter()
+        .map(|(f, fp)| review_comment(f, reconcile::tagged_comment_body(f, fp)));
+
+    match summary_review {
+        None => {
+            let review_payload = serde_json::json!({
+                "commit_id": pr.head_sha,
+                "event": "COMMENT",
+                "body": summary,
+                "comments": new_comments.collect::<Vec<_>>(),
+            });
+            let request = api
+                .request(reqwest::Method::POST, &format!("{pulls}/reviews"))
+                .json(&review_payload);
+            api.send(request, "review creation").await?;
+        }
+        Some(review_id) => {
+            let request = api
+                .request(
+                    reqwest::Method::PUT,
+                    &format!("{pulls}/reviews/{review_id}"),
+                )
+                .json(&serde_json::json!({ "body": summary }));
+            api.send(request, "summary update").await?;
+
+            for mut comment in new_comments {
+                comment["commit_id"] = pr.head_sha.clone().into();
+                let request = api
+                    .request(reqwest::Method::POST, &format!("{pulls}/comments"))
+                    .json(&comment);
+                api.send(request, "comment creation").await?;
+            }
+        }
+    }
+
+    for comment_id in plan.stale {
+        let request = api.request(
+            reqwest::Method::DELETE,
+            &format!("/pulls/comments/{comment_id}"),
+        );
+        api.send(request, "stale comment deletion").await?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::env::Env;
+    use crate::http::mock::{MockServer, Route};
     use crate::models::finding::Severity;
     use crate::output::escape;
 
@@ -74,6 +378,8 @@ mod tests {
             message: "User input is interpolated directly into query string.".into(),
             suggestion: Some("Use parameterized queries.".into()),
             agent: "security".into(),
+            fix: None,
+            served_by: None,
         }];
 
         let output = renderer.format(&findings);
@@ -102,6 +408,8 @@ mod tests {
             message: "Details".into(),
             suggestion: None,
             agent: "backend".into(),
+            fix: None,
+            served_by: None,
         }];
 
         let output = renderer.format(&findings);
@@ -121,6 +429,8 @@ mod tests {
             message: "Consider this".into(),
             suggestion: None,
             agent: "backend".into(),
+            fix: None,
+            served_by: None,
         }];
 
         let output = renderer.format(&findings);
@@ -132,4 +442,261 @@ mod tests {
         let s = "line1\nline2";
         assert_eq!(escape::github_annotation(s), "line1%0Aline2");
     }
+
+    fn sample_findings() -> Vec<Finding> {
+        vec![
+            Finding {
+                file: "src/auth.rs".into(),
+                line: 42,
+                end_line: Some(45),
+                severity: Severity::Error,
+                title: "SQL injection".into(),
+                message: "User input reaches the query.".into(),
+                suggestion: Some("Use parameterized queries.".into()),
+                agent: "security".into(),
+                fix: None,
+                served_by: None,
+            },
+            Finding {
+                file: "src/lib.rs".into(),
+                line: 7,
+                end_line: None,
+                severity: Severity::Warning,
+                title: "Style".into(),
+                message: "Style issue".into(),
+                suggestion: None,
+                agent: "backend".into(),
+                fix: None,
+                served_by: None,
+            },
+        ]
+    }
+
+    fn write_event(dir: &std::path::Path, payload: &str) -> String {
+        let path = dir.join("event.json");
+        std::fs::write(&path, payload).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    const PR_EVENT: &str =
+        r#"{"action":"opened","pull_request":{"number":17,"head":{"sha":"deadbeef"}}}"#;
+
+    #[test]
+    fn parse_event_extracts_pr() {
+        let pr = parse_event_payload(PR_EVENT).unwrap();
+        assert_eq!(
+            pr,
+            PullRequestRef {
+                number: 17,
+                head_sha: "deadbeef".into()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_event_rejects_push_event() {
+        let err = parse_event_payload(r#"{"ref":"refs/heads/main"}"#).unwrap_err();
+        assert!(err.to_string().contains("not a pull_request event"));
+    }
+
+    #[test]
+    fn pull_request_event_detection() {
+        assert!(is_pull_request_event(&Env::mock([(
+            "GITHUB_EVENT_NAME",
+            "pull_request"
+        )])));
+        assert!(is_pull_request_event(&Env::mock([(
+            "GITHUB_EVENT_NAME",
+            "pull_request_target"
+        )])));
+        assert!(!is_pull_request_event(&Env::mock([(
+            "GITHUB_EVENT_NAME",
+            "push"
+        )])));
+        assert!(!is_pull_request_event(&Env::mock(
+            Vec::<(&str, &str)>::new()
+        )));
+    }
+
+    #[test]
+    fn review_comment_single_line() {
+        let comment = review_comment(&sample_findings()[1], "body".into());
+        assert_eq!(comment["line"], 7);
+        assert_eq!(comment["side"], "RIGHT");
+        assert!(comment.get("start_line").is_none());
+    }
+
+    #[test]
+    fn review_comment_multi_line_range() {
+        let comment = review_comment(&sample_findings()[0], "body".into());
+        assert_eq!(comment["start_line"], 42);
+        assert_eq!(comment["line"], 45);
+        assert_eq!(comment["start_side"], "RIGHT");
+    }
+
+    #[tokio::test]
+    async fn post_missing_env_vars_cascade() {
+        let env = Env::mock(Vec::<(&str, &str)>::new());
+        let err = post_to_github(&sample_findings(), &[], &env)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("GITHUB_REPOSITORY"), "got: {err}");
+
+        let env = Env::mock([("GITHUB_REPOSITORY", "o/r")]);
+        let err = post_to_github(&sample_findings(), &[], &env)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("GITHUB_EVENT_PATH"), "got: {err}");
+
+        let env = Env::mock([("GITHUB_REPOSITORY", "o/r"), ("GITHUB_EVENT_PATH", "/x")]);
+        let err = post_to_github(&sample_findings(), &[], &env)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("GITHUB_TOKEN"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn post_invalid_repository() {
+        let env = Env::mock([
+            ("GITHUB_REPOSITORY", "no-slash"),
+            ("GITHUB_EVENT_PATH", "/x"),
+            ("GITHUB_TOKEN", "tok"),
+        ]);
+        let err = post_to_github(&sample_findings(), &[], &env)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("owner/repo"), "got: {err}");
+    }
+
+    fn pr_env(server: &MockServer, event_path: &str) -> Env {
+        Env::mock([
+            ("GITHUB_REPOSITORY", "octo/widgets"),
+            ("GITHUB_EVENT_PATH", event_path),
+            ("GITHUB_TOKEN", "ghs_test"),
+            ("GITHUB_API_URL", server.url.as_str()),
+        ])
+    }
+
+    #[tokio::test]
+    async fn post_creates_review_with_inline_comments() {
+        let server = MockServer::start(vec![
+            Route::new("GET", "/repos/octo/widgets/pulls/17/comments", 200, "[]"),
+            Route::new("GET", "/repos/octo/widgets/pulls/17/reviews", 200, "[]"),
+            Route::new(
+                "POST",
+                "/repos/octo/widgets/pulls/17/reviews",
+                200,
+                r#"{"id":1}"#,
+            ),
+        ])
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let event_path = write_event(dir.path(), PR_EVENT);
+        let env = pr_env(&server, &event_path);
+
+        post_to_github(&sample_findings(), &[], &env).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        let req = &requests[2];
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.header("authorization"), Some("Bearer ghs_test"));
+        let body = req.json();
+        assert_eq!(body["commit_id"], "deadbeef");
+        assert_eq!(body["event"], "COMMENT");
+        let summary = body["body"].as_str().unwrap();
+        assert!(summary.contains("2 findings"));
+        assert!(reconcile::is_summary(summary));
+        let comments = body["comments"].as_array().unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0]["path"], "src/auth.rs");
+        assert_eq!(comments[0]["start_line"], 42);
+        assert_eq!(comments[0]["line"], 45);
+        let first = comments[0]["body"].as_str().unwrap();
+        assert!(first.contains("SQL injection"));
+        assert!(reconcile::extract_fingerprint(first).is_some());
+        assert_eq!(comments[1]["line"], 7);
+    }
+
+    #[tokio::test]
+    async fn post_rerun_updates_summary_and_syncs_comments() {
+        let findings = sample_findings();
+        let kept_fp = reconcile::fingerprint(&findings[0], &[]);
+        let existing_comments = serde_json::json!([
+            { "id": 101, "body": format!("old\n\n{}", reconcile::marker(&kept_fp)) },
+            { "id": 102, "body": format!("gone\n\n{}", reconcile::marker("vanished")) },
+            { "id": 103, "body": "a human reply" },
+        ])
+        .to_string();
+        let reviews = serde_json::json!([
+            { "id": 55, "body": format!("old summary\n\n{}", reconcile::SUMMARY_MARKER) },
+        ])
+        .to_string();
+        let server = MockServer::start(vec![
+            Route::new(
+                "GET",
+                "/repos/octo/widgets/pulls/17/comments",
+                200,
+                &existing_comments,
+            ),
+            Route::new("GET", "/repos/octo/widgets/pulls/17/reviews", 200, &reviews),
+            Route::new("PUT", "/repos/octo/widgets/pulls/17/reviews/55", 200, "{}"),
+            Route::new("POST", "/repos/octo/widgets/pulls/17/comments", 201, "{}"),
+            Route::new("DELETE", "/repos/octo/widgets/pulls/comments/102", 204, ""),
+        ])
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let event_path = write_event(dir.path(), PR_EVENT);
+        let env = pr_env(&server, &event_path);
+
+        post_to_github(&findings, &[], &env).await.unwrap();
+
+        let requests = server.requests();
+        let methods: Vec<(&str, &str)> = requests
+            .iter()
+            .map(|r| (r.method.as_str(), r.path.split('?').next().unwrap()))
+            .collect();
+        assert_eq!(
+            methods,
+            vec![
+                ("GET", "/repos/octo/widgets/pulls/17/comments"),
+                ("GET", "/repos/octo/widgets/pulls/17/reviews"),
+                ("PUT", "/repos/octo/widgets/pulls/17/reviews/55"),
+                ("POST", "/repos/octo/widgets/pulls/17/comments"),
+                ("DELETE", "/repos/octo/widgets/pulls/comments/102"),
+            ]
+        );
+        assert!(
+            requests[2].json()["body"]
+                .as_str()
+                .unwrap()
+                .contains("2 findings")
+        );
+        let added = requests[3].json();
+        assert_eq!(added["path"], "src/lib.rs");
+        assert_eq!(added["commit_id"], "deadbeef");
+    }
+
+    #[tokio::test]
+    async fn post_reports_api_failure() {
+        let server = MockServer::start(vec![
+            Route::new("GET", "/repos/octo/widgets/pulls/17/comments", 200, "[]"),
+            Route::new("GET", "/repos/octo/widgets/pulls/17/reviews", 200, "[]"),
+            Route::new(
+                "POST",
+                "/repos/octo/widgets/pulls/17/reviews",
+                422,
+                r#"{"message":"Unprocessable Entity"}"#,
+            ),
+        ])
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let event_path = write_event(dir.path(), PR_EVENT);
+        let env = pr_env(&server, &event_path);
+
+        let err = post_to_github(&sample_findings(), &[], &env)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("422"), "got: {err}");
+    }
 }
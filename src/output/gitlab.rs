@@ -1,4 +1,4 @@
-//! GitLab Code Quality report renderer.
+//! GitLab Code Quality report renderer and merge request discussion publisher.
 //!
 //! Outputs a JSON array conforming to the [CodeClimate spec](https://docs.gitlab.com/ee/ci/testing/code_quality.html)
 //! that GitLab ingests via `artifacts:reports:codequality`.
@@ -9,9 +9,31 @@
 //!   reports:
 //!     codequality: gl-code-quality-report.json
 //! ```
+//!
+//! When `GITLAB_TOKEN` is set in a merge request pipeline, findings are
+//! additionally opened as MR discussions positioned on the exact diff
+//! line, via [`GitlabPublisher`].
 
+use crate::env::Env;
+use crate::models::diff::{DiffLineType, FileDiff};
 use crate::models::finding::Finding;
-use crate::output::OutputFormatter;
+use crate::output::reconcile::{self, ExistingComment};
+use crate::output::{OutputFormatter, OutputPublisher};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Errors from GitLab API calls.
+#[derive(Error, Debug)]
+pub enum GitlabError {
+    #[error("missing environment variable: {0}")]
+    MissingEnvVar(String),
+
+    #[error("invalid merge request IID: {0}")]
+    InvalidMrIid(String),
+
+    #[error("API request failed: {0}")]
+    ApiError(String),
+}
 
 /// GitLab Code Quality renderer.
 ///
@@ -67,9 +89,281 @@ fn compute_fingerprint(f: &Finding) -> String {
     format!("{:x}", digest)
 }
 
+/// Read a required environment variable or return a [`GitlabError`].
+fn require_env(env: &Env, name: &str) -> Result<String, GitlabError> {
+    env.var(name)
+        .map_err(|_| GitlabError::MissingEnvVar(name.into()))
+}
+
+/// GitLab merge request discussion publisher.
+///
+/// Opens one discussion per finding via the GitLab API. Needs the
+/// reviewed diffs to translate finding lines into diff positions.
+pub struct GitlabPublisher<'a> {
+    env: &'a Env,
+    diffs: &'a [FileDiff<'a>],
+}
+
+impl<'a> GitlabPublisher<'a> {
+    /// Create a new publisher with the given environment and reviewed diffs.
+    pub fn new(env: &'a Env, diffs: &'a [FileDiff<'a>]) -> Self {
+        Self { env, diffs }
+    }
+}
+
+impl OutputPublisher for GitlabPublisher<'_> {
+    async fn publish(
+        &self,
+        findings: &[Finding],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        post_to_gitlab(findings, self.diffs, self.env).await?;
+        Ok(())
+    }
+}
+
+/// The base, start, and head commits of a merge request diff version.
+#[derive(Debug, Clone, Deserialize)]
+struct DiffRefs {
+    base_sha: String,
+    start_sha: String,
+    head_sha: String,
+}
+
+/// Subset of the merge request object needed for positioning.
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    diff_refs: DiffRefs,
+}
+
+/// Compute the discussion `position` for a finding from the parsed diff.
+///
+/// GitLab only accepts positions on lines that appear in the diff: added
+/// lines are addressed by `new_line` alone, unchanged context lines need
+/// both `old_line` and `new_line`. Returns `None` when the finding's line
+/// is not part of any hunk.
+fn diff_position(
+    f: &Finding,
+    diffs: &[FileDiff<'_>],
+    refs: &DiffRefs,
+) -> Option<serde_json::Value> {
+    let diff = diffs.iter().find(|d| d.path() == f.file)?;
+    let line = diff
+        .hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .find(|l| l.line_type != DiffLineType::Removed && l.new_line_no == Some(f.line))?;
+
+    let mut position = serde_json::json!({
+        "position_type": "text",
+        "base_sha": refs.base_sha,
+        "start_sha": refs.start_sha,
+        "head_sha": refs.head_sha,
+        "old_path": diff.old_path,
+        "new_path": diff.new_path,
+        "new_line": f.line,
+    });
+    if line.line_type == DiffLineType::Context {
+        position["old_line"] = line.old_line_no.into();
+    }
+    Some(position)
+}
+
+/// Post findings as GitLab merge request discussions.
+///
+/// Posts the summary as a merge request note, then opens one discussion
+/// per finding. Findings whose line is not part of the diff are opened
+/// as unpositioned discussions that reference `file:line` in the body.
+///
+/// Publishing is idempotent across pipelines: the summary note is edited
+/// in place, findings that already have a discussion are skipped, and
+/// discussions for findings that are no longer reported get resolved.
+///
+/// # Required environment variables
+///
+/// | Variable | Source |
+/// |---|---|
+/// | `CI_API_V4_URL` | GitLab CI built-in |
+/// | `CI_PROJECT_ID` | GitLab CI built-in |
+/// | `CI_MERGE_REQUEST_IID` | GitLab CI built-in (merge request pipelines) |
+/// | `GITLAB_TOKEN` | User-provided token with `api` scope |
+pub async fn post_to_gitlab(
+    findings: &[Finding],
+    diffs: &[FileDiff<'_>],
+    env: &Env,
+) -> Result<(), GitlabError> {
+    let api_url = require_env(env, "CI_API_V4_URL")?;
+    let project_id = require_env(env, "CI_PROJECT_ID")?;
+    let mr_iid_str = require_env(env, "CI_MERGE_REQUEST_IID")?;
+    let token = require_env(env, "GITLAB_TOKEN")?;
+
+    let mr_iid: u64 = mr_iid_str
+        .parse()
+        .map_err(|_| GitlabError::InvalidMrIid(mr_iid_str.clone()))?;
+
+    let mr_url = format!(
+        "{}/projects/{}/merge_requests/{}",
+        api_url.trim_end_matches('/'),
+        project_id,
+        mr_iid,
+    );
+
+    let client = crate::http::build_client()
+        .map_err(|e| GitlabError::ApiError(format!("failed to build HTTP client: {e}")))?;
+
+    // Diff refs pin each position to the MR version that was reviewed
+    let response = client
+        .get(&mr_url)
+        .header("PRIVATE-TOKEN", &token)
+        .send()
+        .await
+        .map_err(|e| GitlabError::ApiError(e.to_string()))?;
+    let response = check_status(response, "merge request lookup").await?;
+    let mr: MergeRequest = response
+        .json()
+        .await
+        .map_err(|e| GitlabError::ApiError(format!("invalid merge request response: {e}")))?;
+
+    let discussions = list_discussions(&client, &mr_url, &token).await?;
+    let mut summary_note = None;
+    let mut existing = Vec::new();
+    for d in &discussions {
+        let (Some(id), Some(note)) = (d["id"].as_str(), d["notes"].get(0)) else {
+            continue;
+        };
+        let body = note["body"].as_str().unwrap_or_default();
+        if reconcile::is_summary(body) {
+            summary_note = note["id"].as_u64();
+        } else if let Some(fingerprint) = reconcile::extract_fingerprint(body) {
+            existing.push(ExistingComment {
+                id: id.to_string(),
+                fingerprint: fingerprint.to_string(),
+                resolved: note["resolved"].as_bool().unwrap_or(false),
+            });
+        }
+    }
+    let plan = reconcile::plan(findings, diffs, &existing);
+
+    let summary = serde_json::json!({ "body": reconcile::tagged_summary(findings) });
+    let request = match summary_note {
+        Some(note_id) => client.put(format!("{mr_url}/notes/{note_id}")),
+        None => client.post(format!("{mr_url}/notes")),
+    };
+    let response = request
+        .header("PRIVATE-TOKEN", &token)
+        .json(&summary)
+        .send()
+        .await
+        .map_err(|e| GitlabError::ApiError(e.to_string()))?;
+    check_status(response, "summary note").await?;
+
+    let mut failures = Vec::new();
+    for (f, fp) in &plan.new {
+        let body = reconcile::tagged_comment_body(f, fp);
+        let payload = match diff_position(f, diffs, &mr.diff_refs) {
+            Some(position) => serde_json::json!({
+                "body": body,
+                "position": position,
+            }),
+            None => serde_json::json!({
+                "body": format!("`{}:{}`\n\n{}", f.file, f.line, body),
+            }),
+        };
+
+        let result = client
+            .post(format!("{mr_url}/discussions"))
+            .header("PRIVATE-TOKEN", &token)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| GitlabError::ApiError(e.to_string()));
+        let result = match result {
+            Ok(response) => check_status(response, "discussion").await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            failures.push(format!("{}:{}: {e}", f.file, f.line));
+        }
+    }
+
+    // Findings that disappeared are resolved rather than deleted so the
+    // conversation history stays visible on the merge request
+    for discussion_id in &plan.stale {
+        let response = client
+            .put(format!("{mr_url}/discussions/{discussion_id}"))
+            .header("PRIVATE-TOKEN", &token)
+            .json(&serde_json::json!({ "resolved": true }))
+            .send()
+            .await
+            .map_err(|e| GitlabError::ApiError(e.to_string()))?;
+        check_status(response, "discussion resolve").await?;
+    }
+
+    if !failures.is_empty() {
+        return Err(GitlabError::ApiError(format!(
+            "{} of {} discussions failed: {}",
+            failures.len(),
+            plan.new.len(),
+            failures.join("; ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Fetch every discussion on the merge request.
+async fn list_discussions(
+    client: &reqwest::Client,
+    mr_url: &str,
+    token: &str,
+) -> Result<Vec<serde_json::Value>, GitlabError> {
+    let mut discussions = Vec::new();
+    for page in 1.. {
+        let response = client
+            .get(format!("{mr_url}/discussions"))
+            .header("PRIVATE-TOKEN", token)
+            .query(&[("per_page", LIST_PAGE_SIZE), ("page", page)])
+            .send()
+            .await
+            .map_err(|e| GitlabError::ApiError(e.to_string()))?;
+        let batch: Vec<serde_json::Value> = check_status(response, "discussion listing")
+            .await?
+            .json()
+            .await
+            .map_err(|e| GitlabError::ApiError(format!("invalid discussion listing: {e}")))?;
+        let done = batch.len() < LIST_PAGE_SIZE;
+        discussions.extend(batch);
+        if done {
+            break;
+        }
+    }
+    Ok(discussions)
+}
+
+/// Page size for list endpoints (GitLab's maximum).
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Turn a non-success response into a [`GitlabError`] that includes the body.
+async fn check_status(
+    response: reqwest::Response,
+    what: &str,
+) -> Result<reqwest::Response, GitlabError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "<no body>".to_string());
+    Err(GitlabError::ApiError(format!(
+        "{what} failed with HTTP {status}: {body}"
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::mock::{MockServer, Route};
     use crate::models::finding::Severity;
 
     #[test]
@@ -83,6 +377,8 @@ mod tests {
             message: "User input interpolated into query.".into(),
             suggestion: Some("Use parameterized queries.".into()),
             agent: "security".into(),
+            fix: None,
+            served_by: None,
         }];
 
         let output = GitlabFormatter.format(&findings);
@@ -115,6 +411,8 @@ mod tests {
             message: "M".into(),
             suggestion: None,
             agent: "a".into(),
+            fix: None,
+            served_by: None,
         };
 
         let findings = vec![
@@ -141,6 +439,8 @@ mod tests {
             message: "M".into(),
             suggestion: None,
             agent: "a".into(),
+            fix: None,
+            served_by: None,
         }];
 
         let output = GitlabFormatter.format(&findings);
@@ -166,6 +466,8 @@ mod tests {
             message: "M".into(),
             suggestion: None,
             agent: "a".into(),
+            fix: None,
+            served_by: None,
         };
         assert_eq!(compute_fingerprint(&f), compute_fingerprint(&f));
     }
@@ -181,6 +483,8 @@ mod tests {
             message: "M1".into(),
             suggestion: None,
             agent: "a".into(),
+            fix: None,
+            served_by: None,
         };
         let f2 = Finding {
             file: "a.rs".into(),
@@ -191,6 +495,8 @@ mod tests {
             message: "M2".into(),
             suggestion: None,
             agent: "a".into(),
+            fix: None,
+            served_by: None,
         };
         assert_ne!(compute_fingerprint(&f1), compute_fingerprint(&f2));
     }
@@ -206,10 +512,300 @@ mod tests {
             message: "Just the message.".into(),
             suggestion: None,
             agent: "a".into(),
+            fix: None,
+            served_by: None,
         }];
 
         let output = GitlabFormatter.format(&findings);
         let parsed: Vec<serde_json::Value> = serde_json::from_str(&output).unwrap();
         assert_eq!(parsed[0]["description"], "Just the message.");
     }
+
+    const DIFF: &str = "\
+diff --git a/src/app.rs b/src/app.rs
+--- a/src/app.rs
++++ b/src/app.rs
+@@ -10,3 +10,4 @@
+ fn keep() {}
+-fn old() {}
++fn new() {}
++fn added() {}
+ fn tail() {}
+";
+
+    fn refs() -> DiffRefs {
+        DiffRefs {
+            base_sha: "base".into(),
+            start_sha: "start".into(),
+            head_sha: "head".into(),
+        }
+    }
+
+    fn finding_at(file: &str, line: u32) -> Finding {
+        Finding {
+            file: file.into(),
+            line,
+            end_line: None,
+            severity: Severity::Warning,
+            title: "Issue".into(),
+            message: "Details".into(),
+            suggestion: None,
+            agent: "backend".into(),
+            fix: None,
+            served_by: None,
+        }
+    }
+
+    #[test]
+    fn position_for_added_line_uses_new_line_only() {
+        let diffs = crate::diff::parser::parse_unified_diff(DIFF);
+        let pos = diff_position(&finding_at("src/app.rs", 11), &diffs, &refs()).unwrap();
+        assert_eq!(pos["new_line"], 11);
+        assert!(pos.get("old_line").is_none());
+        assert_eq!(pos["base_sha"], "base");
+        assert_eq!(pos["start_sha"], "start");
+        assert_eq!(pos["head_sha"], "head");
+        assert_eq!(pos["new_path"], "src/app.rs");
+    }
+
+    #[test]
+    fn position_for_context_line_includes_old_line() {
+        let diffs = crate::diff::parser::parse_unified_diff(DIFF);
+        let pos = diff_position(&finding_at("src/app.rs", 13), &diffs, &refs()).unwrap();
+        assert_eq!(pos["new_line"], 13);
+        assert_eq!(pos["old_line"], 12);
+    }
+
+    #[test]
+    fn position_none_outside_diff() {
+        let diffs = crate::diff::parser::parse_unified_diff(DIFF);
+        assert!(diff_position(&finding_at("src/app.rs", 99), &diffs, &refs()).is_none());
+        assert!(diff_position(&finding_at("other.rs", 11), &diffs, &refs()).is_none());
+    }
+
+    #[tokio::test]
+    async fn post_missing_env_vars_cascade() {
+        let findings = vec![finding_at("src/app.rs", 11)];
+
+        let env = Env::mock(Vec::<(&str, &str)>::new());
+        let err = post_to_gitlab(&findings, &[], &env).await.unwrap_err();
+        assert!(err.to_string().contains("CI_API_V4_URL"), "got: {err}");
+
+        let env = Env::mock([("CI_API_V4_URL", "https://gitlab.com/api/v4")]);
+        let err = post_to_gitlab(&findings, &[], &env).await.unwrap_err();
+        assert!(err.to_string().contains("CI_PROJECT_ID"), "got: {err}");
+
+        let env = Env::mock([
+            ("CI_API_V4_URL", "https://gitlab.com/api/v4"),
+            ("CI_PROJECT_ID", "42"),
+        ]);
+        let err = post_to_gitlab(&findings, &[], &env).await.unwrap_err();
+        assert!(
+            err.to_string().contains("CI_MERGE_REQUEST_IID"),
+            "got: {err}"
+        );
+
+        let env = Env::mock([
+            ("CI_API_V4_URL", "https://gitlab.com/api/v4"),
+            ("CI_PROJECT_ID", "42"),
+            ("CI_MERGE_REQUEST_IID", "7"),
+        ]);
+        let err = post_to_gitlab(&findings, &[], &env).await.unwrap_err();
+        assert!(err.to_string().contains("GITLAB_TOKEN"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn post_invalid_mr_iid() {
+        let env = Env::mock([
+            ("CI_API_V4_URL", "https://gitlab.com/api/v4"),
+            ("CI_PROJECT_ID", "42"),
+            ("CI_MERGE_REQUEST_IID", "abc"),
+            ("GITLAB_TOKEN", "tok"),
+        ]);
+        let err = post_to_gitlab(&[], &[], &env).await.unwrap_err();
+        assert!(
+            err.to_string().contains("invalid merge request IID"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn post_opens_positioned_discussions() {
+        let server = MockServer::start(vec![
+            Route::new(
+                "GET",
+                "/projects/42/merge_requests/7",
+                200,
+                r#"{"iid":7,"diff_refs":{"base_sha":"b1","start_sha":"s1","head_sha":"h1"}}"#,
+            ),
+            Route::new(
+                "GET",
+                "/projects/42/merge_requests/7/discussions",
+                200,
+                "[]",
+            ),
+            Route::new("POST", "/projects/42/merge_requests/7/notes", 201, "{}"),
+            Route::new(
+                "POST",
+                "/projects/42/merge_requests/7/discussions",
+                201,
+                "{}",
+            ),
+        ])
+        .await;
+        let env = Env::mock([
+            ("CI_API_V4_URL", server.url.as_str()),
+            ("CI_PROJECT_ID", "42"),
+            ("CI_MERGE_REQUEST_IID", "7"),
+            ("GITLAB_TOKEN", "glpat-test"),
+        ]);
+        let diffs = crate::diff::parser::parse_unified_diff(DIFF);
+        let findings = vec![finding_at("src/app.rs", 11), finding_at("src/app.rs", 99)];
+
+        post_to_gitlab(&findings, &diffs, &env).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].header("private-token"), Some("glpat-test"));
+        // Skip the discussion listing
+        let requests = &requests[1..];
+        assert!(
+            requests[1].json()["body"]
+                .as_str()
+                .unwrap()
+                .contains("2 findings")
+        );
+
+        let positioned = requests[2].json();
+        assert_eq!(positioned["position"]["new_line"], 11);
+        assert_eq!(positioned["position"]["head_sha"], "h1");
+        assert_eq!(positioned["position"]["start_sha"], "s1");
+
+        let general = requests[3].json();
+        assert!(general.get("position").is_none());
+        assert!(
+            general["body"]
+                .as_str()
+                .unwrap()
+                .starts_with("`src/app.rs:99`")
+        );
+    }
+
+    #[tokio::test]
+    async fn post_reports_failed_discussions() {
+        let server = MockServer::start(vec![
+            Route::new(
+                "GET",
+                "/projects/42/merge_requests/7",
+                200,
+                r#"{"diff_refs":{"base_sha":"b","start_sha":"s","head_sha":"h"}}"#,
+            ),
+            Route::new(
+                "GET",
+                "/projects/42/merge_requests/7/discussions",
+                200,
+                "[]",
+            ),
+            Route::new("POST", "/projects/42/merge_requests/7/notes", 201, "{}"),
+            Route::new(
+                "POST",
+                "/projects/42/merge_requests/7/discussions",
+                400,
+                r#"{"message":"line_code can't be blank"}"#,
+            ),
+        ])
+        .await;
+        let env = Env::mock([
+            ("CI_API_V4_URL", server.url.as_str()),
+            ("CI_PROJECT_ID", "42"),
+            ("CI_MERGE_REQUEST_IID", "7"),
+            ("GITLAB_TOKEN", "tok"),
+        ]);
+        let diffs = crate::diff::parser::parse_unified_diff(DIFF);
+
+        let err = post_to_gitlab(&[finding_at("src/app.rs", 11)], &diffs, &env)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("1 of 1 discussions failed"),
+            "got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn post_rerun_edits_summary_and_resolves_stale() {
+        let diffs = crate::diff::parser::parse_unified_diff(DIFF);
+        let findings = vec![finding_at("src/app.rs", 11), finding_at("src/app.rs", 12)];
+        let kept_fp = reconcile::fingerprint(&findings[0], &diffs);
+        let discussions = serde_json::json!([
+            { "id": "d-summary", "notes": [
+                { "id": 900, "body": format!("old\n\n{}", reconcile::SUMMARY_MARKER) }
+            ]},
+            { "id": "d-kept", "notes": [
+                { "id": 901, "resolved": false, "body": reconcile::marker(&kept_fp) }
+            ]},
+            { "id": "d-stale", "notes": [
+                { "id": 902, "resolved": false, "body": reconcile::marker("vanished") }
+            ]},
+            { "id": "d-done", "notes": [
+                { "id": 903, "resolved": true, "body": reconcile::marker("fixed-earlier") }
+            ]},
+        ])
+        .to_string();
+        let server = MockServer::start(vec![
+            Route::new(
+                "GET",
+                "/projects/42/merge_requests/7",
+                200,
+                r#"{"diff_refs":{"base_sha":"b","start_sha":"s","head_sha":"h"}}"#,
+            ),
+            Route::new(
+                "GET",
+                "/projects/42/merge_requests/7/discussions",
+                200,
+                &discussions,
+            ),
+            Route::new("PUT", "/projects/42/merge_requests/7/notes/900", 200, "{}"),
+            Route::new(
+                "POST",
+                "/projects/42/merge_requests/7/discussions",
+                201,
+                "{}",
+            ),
+            Route::new(
+                "PUT",
+                "/projects/42/merge_requests/7/discussions/d-stale",
+                200,
+                "{}",
+            ),
+        ])
+        .await;
+        let env = Env::mock([
+            ("CI_API_V4_URL", server.url.as_str()),
+            ("CI_PROJECT_ID", "42"),
+            ("CI_MERGE_REQUEST_IID", "7"),
+            ("GITLAB_TOKEN", "tok"),
+        ]);
+
+        post_to_gitlab(&findings, &diffs, &env).await.unwrap();
+
+        let requests = server.requests();
+        let calls: Vec<(&str, &str)> = requests
+            .iter()
+            .map(|r| (r.method.as_str(), r.path.split('?').next().unwrap()))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![
+                ("GET", "/projects/42/merge_requests/7"),
+                ("GET", "/projects/42/merge_requests/7/discussions"),
+                ("PUT", "/projects/42/merge_requests/7/notes/900"),
+                ("POST", "/projects/42/merge_requests/7/discussions"),
+                ("PUT", "/projects/42/merge_requests/7/discussions/d-stale"),
+            ]
+        );
+        assert_eq!(requests[3].json()["position"]["new_line"], 12);
+        assert_eq!(requests[4].json()["resolved"], true);
+    }
 }
@@ -1,8 +1,12 @@
 //! JSON output renderer.
 //!
-//! Outputs `{"findings": [...], "summary": {...}}` format.
+//! Outputs `{"findings": [...], "summary": {...}, "meta": {...}}` format.
+//! The summary counts findings by severity and records how many were
+//! suppressed by `nitpik-ignore` comments or the baseline file. The meta
+//! block carries token usage and cost when a review ran.
 
-use crate::models::finding::{Finding, Summary};
+use crate::cost::UsageReport;
+use crate::models::finding::{Finding, Summary, SuppressedCounts};
 use crate::output::OutputFormatter;
 
 /// JSON output renderer.
@@ -10,15 +14,43 @@ pub struct JsonFormatter;
 
 impl OutputFormatter for JsonFormatter {
     fn format(&self, findings: &[Finding]) -> String {
-        let summary = Summary::from_findings(findings);
+        self.format_with_suppressed(findings, SuppressedCounts::default())
+    }
+}
+
+impl JsonFormatter {
+    /// Render findings, reporting how many were suppressed in the summary.
+    pub fn format_with_suppressed(
+        &self,
+        findings: &[Finding],
+        suppressed: SuppressedCounts,
+    ) -> String {
+        self.format_report(findings, suppressed, None)
+    }
+
+    /// Render findings with suppression counts and, when given, the
+    /// run's token usage under `meta.usage`.
+    pub fn format_report(
+        &self,
+        findings: &[Finding],
+        suppressed: SuppressedCounts,
+        usage: Option<&UsageReport>,
+    ) -> String {
+        let mut summary = Summary::from_findings(findings);
+        summary.suppressed = suppressed;
+
+        let mut meta = serde_json::json!({
+            "notice": crate::constants::AI_DISCLOSURE,
+            "tool": crate::constants::APP_NAME,
+        });
+        if let Some(usage) = usage {
+            meta["usage"] = serde_json::to_value(usage).unwrap_or_default();
+        }
 
         let output = serde_json::json!({
             "findings": findings,
             "summary": summary,
-            "meta": {
-                "notice": crate::constants::AI_DISCLOSURE,
-                "tool": crate::constants::APP_NAME,
-            },
+            "meta": meta,
         });
 
         serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
@@ -42,6 +74,8 @@ mod tests {
             message: "Details".into(),
             suggestion: Some("Fix".into()),
             agent: "backend".into(),
+            fix: None,
+            served_by: None,
         }];
 
         let output = renderer.format(&findings);
@@ -60,4 +94,49 @@ mod tests {
         assert_eq!(parsed["findings"].as_array().unwrap().len(), 0);
         assert_eq!(parsed["summary"]["total"], 0);
     }
+
+    #[test]
+    fn render_suppressed_counts() {
+        let output = JsonFormatter.format_with_suppressed(
+            &[],
+            SuppressedCounts {
+                inline: 2,
+                baseline: 5,
+            },
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["summary"]["total"], 0);
+        assert_eq!(parsed["summary"]["suppressed"]["inline"], 2);
+        assert_eq!(parsed["summary"]["suppressed"]["baseline"], 5);
+        assert!(parsed["meta"].get("usage").is_none());
+    }
+
+    #[test]
+    fn render_usage_in_meta() {
+        use crate::cost::{TaskUsage, TokenUsage};
+
+        let usage = UsageReport::new(
+            &[TaskUsage {
+                file: "src/main.rs".into(),
+                agent: "backend".into(),
+                model: "gpt-4o".into(),
+                usage: TokenUsage {
+                    input_tokens: 1200,
+                    output_tokens: 300,
+                    ..TokenUsage::default()
+                },
+                cached: false,
+                saved: TokenUsage::default(),
+            }],
+            &Default::default(),
+        );
+        let output = JsonFormatter.format_report(&[], SuppressedCounts::default(), Some(&usage));
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let usage = &parsed["meta"]["usage"];
+        assert_eq!(usage["total"]["input_tokens"], 1200);
+        assert_eq!(usage["total"]["output_tokens"], 300);
+        assert_eq!(usage["by_agent"]["backend"]["requests"], 1);
+        assert_eq!(usage["by_file"]["src/main.rs"]["cache_hits"], 0);
+        assert!(usage["total"]["cost"].as_f64().unwrap() > 0.0);
+    }
 }
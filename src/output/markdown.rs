@@ -0,0 +1,95 @@
+//! Markdown bodies shared by the forge review publishers.
+//!
+//! GitHub, GitLab, and Forgejo all render review comments as Markdown,
+//! so the per-finding comment body and the review summary are built
+//! here once and reused by each publisher.
+
+use crate::models::finding::{Finding, Summary};
+
+/// Format a single finding as a Markdown comment body.
+pub fn comment_body(f: &Finding) -> String {
+    let severity_emoji = f.severity.emoji();
+
+    let mut body = format!(
+        "{} **{}** ({})\n\n{}",
+        severity_emoji, f.title, f.severity, f.message
+    );
+    if let Some(ref suggestion) = f.suggestion {
+        body.push_str(&format!("\n\n**Suggestion:** {suggestion}"));
+    }
+    body.push_str(&format!("\n\n_— agent: {}_", f.agent));
+    body
+}
+
+/// Format the top-level review summary with finding counts and the
+/// AI disclosure.
+pub fn review_summary(findings: &[Finding]) -> String {
+    let summary = Summary::from_findings(findings);
+    format!(
+        "**{}** found {} {} ({} error{}, {} warning{}, {} info)\n\n_{}_",
+        crate::constants::APP_NAME,
+        summary.total,
+        if summary.total == 1 {
+            "finding"
+        } else {
+            "findings"
+        },
+        summary.errors,
+        if summary.errors == 1 { "" } else { "s" },
+        summary.warnings,
+        if summary.warnings == 1 { "" } else { "s" },
+        summary.info,
+        crate::constants::AI_DISCLOSURE,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::Severity;
+
+    fn finding(severity: Severity) -> Finding {
+        Finding {
+            file: "x.rs".to_string(),
+            line: 5,
+            end_line: None,
+            severity,
+            title: "Note".to_string(),
+            message: "Just a note.".to_string(),
+            suggestion: None,
+            agent: "architect".to_string(),
+            fix: None,
+            served_by: None,
+        }
+    }
+
+    #[test]
+    fn comment_body_info_severity() {
+        let body = comment_body(&finding(Severity::Info));
+        assert!(body.contains("🔵"));
+        assert!(body.contains("info"));
+        assert!(body.contains("agent: architect"));
+    }
+
+    #[test]
+    fn comment_body_includes_suggestion() {
+        let mut f = finding(Severity::Error);
+        f.suggestion = Some("Fix it".to_string());
+        assert!(comment_body(&f).contains("**Suggestion:** Fix it"));
+    }
+
+    #[test]
+    fn review_summary_counts() {
+        let findings = vec![finding(Severity::Error), finding(Severity::Warning)];
+        let body = review_summary(&findings);
+        assert!(body.contains("2 findings (1 error, 1 warning, 0 info)"));
+        assert!(body.contains(crate::constants::AI_DISCLOSURE));
+    }
+
+    #[test]
+    fn review_summary_singular() {
+        let body = review_summary(&[finding(Severity::Info)]);
+        assert!(body.contains("1 finding "));
+        assert!(!body.contains("1 findings"));
+    }
+}
@@ -1,4 +1,4 @@
-//! Output formatters: terminal, JSON, GitHub Actions, GitLab Code Quality, Bitbucket, Checkstyle, Forgejo.
+//! Output formatters: terminal, JSON, GitHub Actions, GitLab Code Quality, Bitbucket, Checkstyle, Forgejo, SARIF.
 //!
 //! # Bounded Context: Rendering
 //!
@@ -13,6 +13,9 @@ pub mod forgejo;
 pub mod github;
 pub mod gitlab;
 pub mod json;
+pub mod markdown;
+pub mod reconcile;
+pub mod sarif;
 pub mod terminal;
 
 use crate::models::finding::Finding;
@@ -0,0 +1,247 @@
+//! Comment reconciliation for idempotent forge publishing.
+//!
+//! Every comment a publisher posts carries a hidden Markdown marker with
+//! a stable fingerprint of its finding. On the next run the publisher
+//! lists the comments already on the pull request, and [`plan`] decides
+//! which findings still need posting and which earlier comments belong
+//! to findings that no longer exist. The summary is tagged with its own
+//! marker so it can be edited in place instead of re-posted.
+
+use std::collections::HashSet;
+
+use sha2::{Digest, Sha256};
+
+use crate::models::diff::{DiffLineType, FileDiff};
+use crate::models::finding::Finding;
+use crate::output::markdown::{comment_body, review_summary};
+
+/// Hidden marker identifying the summary comment.
+pub const SUMMARY_MARKER: &str = "<!-- nitpik:summary -->";
+
+/// Prefix of the hidden per-finding fingerprint marker.
+const FINGERPRINT_PREFIX: &str = "<!-- nitpik:fp:";
+
+/// Compute a stable fingerprint for a finding.
+///
+/// Combines file, agent, and title with a hash of the normalized source
+/// lines the finding points at (taken from the diff's new side). Line
+/// numbers are deliberately excluded so that a finding keeps its identity
+/// when unrelated edits shift it up or down the file.
+pub fn fingerprint(f: &Finding, diffs: &[FileDiff<'_>]) -> String {
+    let content_hash = hex::encode(Sha256::digest(line_content(f, diffs).as_bytes()));
+
+    let mut hasher = Sha256::new();
+    hasher.update(f.file.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(f.agent.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(f.title.trim().to_lowercase().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(content_hash.as_bytes());
+    hex::encode(hasher.finalize())[..16].to_string()
+}
+
+/// Collect the whitespace-normalized new-side lines covered by a finding.
+///
+/// Returns an empty string when the finding's lines are not in the diff.
+fn line_content(f: &Finding, diffs: &[FileDiff<'_>]) -> String {
+    let end = f.end_line.unwrap_or(f.line).max(f.line);
+    let Some(diff) = diffs.iter().find(|d| d.path() == f.file) else {
+        return String::new();
+    };
+    diff.hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .filter(|l| l.line_type != DiffLineType::Removed)
+        .filter(|l| l.new_line_no.is_some_and(|n| n >= f.line && n <= end))
+        .map(|l| l.content.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format the hidden marker embedding a fingerprint.
+pub fn marker(fingerprint: &str) -> String {
+    format!("{FINGERPRINT_PREFIX}{fingerprint} -->")
+}
+
+/// Extract the fingerprint from a comment body, if it has one.
+pub fn extract_fingerprint(body: &str) -> Option<&str> {
+    let start = body.find(FINGERPRINT_PREFIX)? + FINGERPRINT_PREFIX.len();
+    let rest = &body[start..];
+    let end = rest.find(" -->")?;
+    Some(&rest[..end])
+}
+
+/// Returns `true` if the comment body is a nitpik summary.
+pub fn is_summary(body: &str) -> bool {
+    body.contains(SUMMARY_MARKER)
+}
+
+/// Markdown comment body for a finding, tagged with its fingerprint.
+pub fn tagged_comment_body(f: &Finding, fingerprint: &str) -> String {
+    format!("{}\n\n{}", comment_body(f), marker(fingerprint))
+}
+
+/// Review summary tagged with the summary marker.
+pub fn tagged_summary(findings: &[Finding]) -> String {
+    format!("{}\n\n{SUMMARY_MARKER}", review_summary(findings))
+}
+
+/// A previously posted comment that carries a fingerprint.
+#[derive(Debug, Clone)]
+pub struct ExistingComment<Id> {
+    /// Forge-specific identifier used to delete or resolve the comment.
+    pub id: Id,
+    /// Fingerprint extracted from the comment body.
+    pub fingerprint: String,
+    /// Whether the comment is already resolved (never reported as stale).
+    pub resolved: bool,
+}
+
+/// What a publisher has to do to bring the pull request up to date.
+#[derive(Debug)]
+pub struct Plan<'a, Id> {
+    /// Findings not yet posted, with their fingerprints.
+    pub new: Vec<(&'a Finding, String)>,
+    /// Comments whose finding is no longer reported.
+    pub stale: Vec<Id>,
+}
+
+/// Reconcile current findings against comments already on the forge.
+///
+/// Findings whose fingerprint is already present are skipped, and
+/// findings sharing a fingerprint within the current run are posted once.
+/// Unresolved comments with a fingerprint that no current finding
+/// produces are returned as stale.
+pub fn plan<'a, Id: Clone>(
+    findings: &'a [Finding],
+    diffs: &[FileDiff<'_>],
+    existing: &[ExistingComment<Id>],
+) -> Plan<'a, Id> {
+    let posted: HashSet<&str> = existing.iter().map(|c| c.fingerprint.as_str()).collect();
+
+    let mut current = HashSet::new();
+    let mut new = Vec::new();
+    for f in findings {
+        let fp = fingerprint(f, diffs);
+        if current.insert(fp.clone()) && !posted.contains(fp.as_str()) {
+            new.push((f, fp));
+        }
+    }
+
+    let stale = existing
+        .iter()
+        .filter(|c| !c.resolved && !current.contains(&c.fingerprint))
+        .map(|c| c.id.clone())
+        .collect();
+
+    Plan { new, stale }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::parser::parse_unified_diff;
+    use crate::models::finding::Severity;
+
+    const DIFF: &str = "\
+diff --git a/src/app.rs b/src/app.rs
+--- a/src/app.rs
++++ b/src/app.rs
+@@ -1,2 +1,3 @@
+ fn keep() {}
++let   x =  compute();
+ fn tail() {}
+";
+
+    const SHIFTED_DIFF: &str = "\
+diff --git a/src/app.rs b/src/app.rs
+--- a/src/app.rs
++++ b/src/app.rs
+@@ -1,2 +1,5 @@
+ fn keep() {}
++// new comment
++// another
++let x = compute();
+ fn tail() {}
+";
+
+    fn finding(line: u32, title: &str) -> Finding {
+        Finding {
+            file: "src/app.rs".into(),
+            line,
+            end_line: None,
+            severity: Severity::Warning,
+            title: title.into(),
+            message: "Details".into(),
+            suggestion: None,
+            agent: "backend".into(),
+            fix: None,
+            served_by: None,
+        }
+    }
+
+    fn existing(id: u64, fp: &str, resolved: bool) -> ExistingComment<u64> {
+        ExistingComment {
+            id,
+            fingerprint: fp.into(),
+            resolved,
+        }
+    }
+
+    #[test]
+    fn fingerprint_survives_line_shift_and_whitespace() {
+        let before = parse_unified_diff(DIFF);
+        let after = parse_unified_diff(SHIFTED_DIFF);
+        assert_eq!(
+            fingerprint(&finding(2, "Unchecked"), &before),
+            fingerprint(&finding(4, "Unchecked"), &after)
+        );
+    }
+
+    #[test]
+    fn fingerprint_differs_by_title_and_content() {
+        let diffs = parse_unified_diff(DIFF);
+        let base = fingerprint(&finding(2, "Unchecked"), &diffs);
+        assert_ne!(base, fingerprint(&finding(2, "Other"), &diffs));
+        assert_ne!(base, fingerprint(&finding(1, "Unchecked"), &diffs));
+    }
+
+    #[test]
+    fn marker_round_trip() {
+        let body = tagged_comment_body(&finding(2, "T"), "abc123");
+        assert_eq!(extract_fingerprint(&body), Some("abc123"));
+        assert_eq!(extract_fingerprint("plain comment"), None);
+    }
+
+    #[test]
+    fn summary_is_tagged() {
+        assert!(is_summary(&tagged_summary(&[])));
+        assert!(!is_summary("**nitpik** found 0 findings"));
+    }
+
+    #[test]
+    fn plan_skips_posted_and_reports_stale() {
+        let diffs = parse_unified_diff(DIFF);
+        let findings = vec![finding(2, "Kept"), finding(2, "New")];
+        let kept_fp = fingerprint(&findings[0], &diffs);
+        let existing = vec![
+            existing(1, &kept_fp, false),
+            existing(2, "gone", false),
+            existing(3, "gone-resolved", true),
+        ];
+
+        let plan = plan(&findings, &diffs, &existing);
+        assert_eq!(plan.new.len(), 1);
+        assert_eq!(plan.new[0].0.title, "New");
+        assert_eq!(plan.stale, vec![2]);
+    }
+
+    #[test]
+    fn plan_posts_duplicate_fingerprints_once() {
+        let diffs = parse_unified_diff(DIFF);
+        let findings = vec![finding(2, "Same"), finding(2, "Same")];
+        let plan = plan::<u64>(&findings, &diffs, &[]);
+        assert_eq!(plan.new.len(), 1);
+    }
+}
@@ -0,0 +1,351 @@
+//! SARIF 2.1.0 renderer.
+//!
+//! Outputs a [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! log that GitHub code scanning, IDEs, and security tooling ingest.
+//! Unlike Checkstyle, SARIF carries rule metadata and machine-readable
+//! fixes, so each agent, threat rule, and secret rule gets its own
+//! rule descriptor.
+//!
+//! ```yaml
+//! - run: nitpik review --format sarif > nitpik.sarif
+//! - uses: github/codeql-action/upload-sarif@v3
+//!   with:
+//!     sarif_file: nitpik.sarif
+//! ```
+
+use indexmap::IndexMap;
+
+use crate::constants::{APP_NAME, GITHUB_REPO, SECRET_SCANNER_AGENT, THREAT_SCANNER_AGENT};
+use crate::models::finding::Finding;
+use crate::output::OutputFormatter;
+
+/// JSON schema URI for SARIF 2.1.0 logs.
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Prefix of secret scanner finding titles, followed by the rule ID.
+const SECRET_TITLE_PREFIX: &str = "Potential secret detected: ";
+
+/// SARIF 2.1.0 renderer.
+///
+/// Emits a single run whose driver lists one rule per distinct rule ID
+/// found in the findings. Results reference their rule by ID and index.
+pub struct SarifFormatter;
+
+impl OutputFormatter for SarifFormatter {
+    fn format(&self, findings: &[Finding]) -> String {
+        // Rule ID → descriptor, in first-seen order so indices are stable
+        let mut rules: IndexMap<String, serde_json::Value> = IndexMap::new();
+
+        let results: Vec<serde_json::Value> = findings
+            .iter()
+            .map(|f| {
+                let (rule_id, descriptor) = rule_for(f);
+                let entry = rules.entry(rule_id.clone());
+                let rule_index = entry.index();
+                entry.or_insert(descriptor);
+
+                let mut region = serde_json::json!({ "startLine": f.line });
+                if let Some(end) = f.end_line.filter(|&end| end >= f.line) {
+                    region["endLine"] = end.into();
+                }
+
+                let mut message = format!("{}\n\n{}", f.title, f.message);
+                if let Some(ref suggestion) = f.suggestion {
+                    message.push_str(&format!("\n\nSuggestion: {suggestion}"));
+                }
+
+                let mut result = serde_json::json!({
+                    "ruleId": rule_id,
+                    "ruleIndex": rule_index,
+                    "level": f.severity.as_sarif_level(),
+                    "message": { "text": message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": f.file,
+                                "uriBaseId": "%SRCROOT%",
+                            },
+                            "region": region,
+                        },
+                    }],
+                    "properties": { "agent": f.agent },
+                });
+                if let Some(fix) = fix_for(f) {
+                    result["fixes"] = serde_json::json!([fix]);
+                }
+                result
+            })
+            .collect();
+
+        let log = serde_json::json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": APP_NAME,
+                        "semanticVersion": crate::constants::VERSION,
+                        "informationUri": format!("https://github.com/{GITHUB_REPO}"),
+                        "rules": rules.into_values().collect::<Vec<_>>(),
+                    },
+                },
+                "results": results,
+                "properties": { "notice": crate::constants::AI_DISCLOSURE },
+            }],
+        });
+
+        serde_json::to_string_pretty(&log).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+/// Derive the rule ID and descriptor for a finding.
+///
+/// Scanner findings map to their individual rule (the threat rule ID is
+/// embedded in the message, the secret rule ID in the title); LLM agent
+/// findings map to one rule per agent.
+fn rule_for(f: &Finding) -> (String, serde_json::Value) {
+    if f.agent == THREAT_SCANNER_AGENT
+        && let Some(rule) = threat_rule_id(&f.message)
+    {
+        let id = format!("{THREAT_SCANNER_AGENT}/{rule}");
+        let descriptor = rule_descriptor(&id, &f.title, "security");
+        return (id, descriptor);
+    }
+    if f.agent == SECRET_SCANNER_AGENT
+        && let Some(rule) = f.title.strip_prefix(SECRET_TITLE_PREFIX)
+    {
+        let id = format!("{SECRET_SCANNER_AGENT}/{rule}");
+        let description = f.message.split(". ").next().unwrap_or(&f.title);
+        let descriptor = rule_descriptor(&id, description, "security");
+        return (id, descriptor);
+    }
+    let description = format!("Findings reported by the {} review agent", f.agent);
+    let descriptor = rule_descriptor(&f.agent, &description, "review");
+    (f.agent.clone(), descriptor)
+}
+
+/// Build a SARIF `reportingDescriptor`.
+fn rule_descriptor(id: &str, description: &str, tag: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": id,
+        "shortDescription": { "text": description },
+        "properties": { "tags": [tag] },
+    })
+}
+
+/// Extract the rule ID from a threat finding message (`(rule: <id>, ...`).
+fn threat_rule_id(message: &str) -> Option<&str> {
+    let start = message.find("(rule: ")? + "(rule: ".len();
+    let rest = &message[start..];
+    let end = rest.find([',', ')'])?;
+    Some(&rest[..end])
+}
+
+/// Build a SARIF fix for a finding.
+///
+/// A structured [`Fix`](crate::models::finding::Fix) is used as-is. Otherwise
+/// a suggestion containing a fenced code block is treated as the
+/// replacement for the finding's line range. Prose-only suggestions are
+/// not machine-applicable and produce no fix.
+fn fix_for(f: &Finding) -> Option<serde_json::Value> {
+    let (start, end, mut text) = match f.fix.as_ref() {
+        Some(fix) => (fix.start_line, fix.end_line, fix.replacement.clone()),
+        None => {
+            let code = fenced_code(f.suggestion.as_deref()?)?;
+            let end = f.end_line.filter(|&end| end >= f.line).unwrap_or(f.line);
+            (f.line, end, code.to_string())
+        }
+    };
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+    let description = f.suggestion.as_deref().unwrap_or(&f.title);
+
+    Some(serde_json::json!({
+        "description": { "text": description },
+        "artifactChanges": [{
+            "artifactLocation": {
+                "uri": f.file,
+                "uriBaseId": "%SRCROOT%",
+            },
+            "replacements": [{
+                "deletedRegion": { "startLine": start, "endLine": end },
+                "insertedContent": { "text": text },
+            }],
+        }],
+    }))
+}
+
+/// Return the body of the first fenced (```) code block, if any.
+fn fenced_code(text: &str) -> Option<&str> {
+    let open = text.find("```")?;
+    let after_fence = &text[open + 3..];
+    // Skip the info string (language tag) up to the end of the line
+    let body_start = after_fence.find('\n')? + 1;
+    let body = &after_fence[body_start..];
+    let close = body.find("```")?;
+    Some(&body[..close])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::finding::Severity;
+
+    fn finding(agent: &str, title: &str, message: &str) -> Finding {
+        Finding {
+            file: "src/lib.rs".into(),
+            line: 10,
+            end_line: Some(12),
+            severity: Severity::Warning,
+            title: title.into(),
+            message: message.into(),
+            suggestion: None,
+            agent: agent.into(),
+            fix: None,
+            served_by: None,
+        }
+    }
+
+    fn render(findings: &[Finding]) -> serde_json::Value {
+        serde_json::from_str(&SarifFormatter.format(findings)).unwrap()
+    }
+
+    #[test]
+    fn render_log_envelope() {
+        let log = render(&[]);
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["$schema"], SARIF_SCHEMA);
+        let driver = &log["runs"][0]["tool"]["driver"];
+        assert_eq!(driver["name"], APP_NAME);
+        assert!(driver["rules"].as_array().unwrap().is_empty());
+        assert!(log["runs"][0]["results"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn render_maps_levels_and_region() {
+        let mut info = finding("backend", "Tip", "Consider this");
+        info.severity = Severity::Info;
+        info.end_line = None;
+        let log = render(&[finding("backend", "Issue", "Details"), info]);
+        let results = log["runs"][0]["results"].as_array().unwrap();
+
+        assert_eq!(results[0]["level"], "warning");
+        let region = &results[0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 10);
+        assert_eq!(region["endLine"], 12);
+
+        assert_eq!(results[1]["level"], "note");
+        let region = &results[1]["locations"][0]["physicalLocation"]["region"];
+        assert!(region.get("endLine").is_none());
+    }
+
+    #[test]
+    fn render_one_rule_per_agent() {
+        let log = render(&[
+            finding("backend", "A", "a"),
+            finding("security", "B", "b"),
+            finding("backend", "C", "c"),
+        ]);
+        let rules = log["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "backend");
+        assert_eq!(rules[1]["id"], "security");
+
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results[2]["ruleId"], "backend");
+        assert_eq!(results[2]["ruleIndex"], 0);
+        assert_eq!(results[1]["ruleIndex"], 1);
+    }
+
+    #[test]
+    fn render_threat_and_secret_rules() {
+        let threat = finding(
+            THREAT_SCANNER_AGENT,
+            "obfuscation: Base64 blob passed to eval",
+            "Threat pattern detected (rule: eval-base64, category: obfuscation). Matched: `eval(`",
+        );
+        let secret = finding(
+            SECRET_SCANNER_AGENT,
+            "Potential secret detected: aws-access-token",
+            "AWS access token. The secret has been redacted before sending to the LLM.",
+        );
+        let log = render(&[threat, secret]);
+        let rules = log["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules[0]["id"], "threat-scanner/eval-base64");
+        assert_eq!(
+            rules[0]["shortDescription"]["text"],
+            "obfuscation: Base64 blob passed to eval"
+        );
+        assert_eq!(rules[1]["id"], "secret-scanner/aws-access-token");
+        assert_eq!(rules[1]["shortDescription"]["text"], "AWS access token");
+    }
+
+    #[test]
+    fn code_suggestion_becomes_fix() {
+        let mut f = finding("backend", "Unwrap", "May panic");
+        f.suggestion = Some("Propagate the error:\n```rust\nlet v = parse(x)?;\n```".into());
+        let log = render(&[f]);
+        let fix = &log["runs"][0]["results"][0]["fixes"][0];
+        let replacement = &fix["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["deletedRegion"]["startLine"], 10);
+        assert_eq!(replacement["deletedRegion"]["endLine"], 12);
+        assert_eq!(
+            replacement["insertedContent"]["text"],
+            "let v = parse(x)?;\n"
+        );
+        assert_eq!(
+            fix["artifactChanges"][0]["artifactLocation"]["uri"],
+            "src/lib.rs"
+        );
+    }
+
+    #[test]
+    fn structured_fix_preferred_over_suggestion() {
+        let mut f = finding("backend", "Unwrap", "May panic");
+        f.suggestion = Some("```\nignored\n```".into());
+        f.fix = Some(crate::models::finding::Fix {
+            start_line: 11,
+            end_line: 11,
+            replacement: "let v = parse(x)?;".into(),
+            original: None,
+        });
+        let log = render(&[f]);
+        let replacement =
+            &log["runs"][0]["results"][0]["fixes"][0]["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["deletedRegion"]["startLine"], 11);
+        assert_eq!(replacement["deletedRegion"]["endLine"], 11);
+        assert_eq!(
+            replacement["insertedContent"]["text"],
+            "let v = parse(x)?;\n"
+        );
+    }
+
+    #[test]
+    fn prose_suggestion_has_no_fix() {
+        let mut f = finding("backend", "Naming", "Unclear name");
+        f.suggestion = Some("Rename the variable to something descriptive.".into());
+        let log = render(&[f]);
+        let result = &log["runs"][0]["results"][0];
+        assert!(result.get("fixes").is_none());
+        assert!(
+            result["message"]["text"]
+                .as_str()
+                .unwrap()
+                .contains("Suggestion: Rename")
+        );
+    }
+
+    #[test]
+    fn fenced_code_extraction() {
+        assert_eq!(fenced_code("```\nx\n```"), Some("x\n"));
+        assert_eq!(fenced_code("see ```py\na\nb\n``` ok"), Some("a\nb\n"));
+        assert_eq!(fenced_code("inline `code` only"), None);
+        assert_eq!(fenced_code("```\nunterminated"), None);
+    }
+}
@@ -113,6 +113,8 @@ mod tests {
                 message: "This is broken".into(),
                 suggestion: Some("Fix it".into()),
                 agent: "backend".into(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "src/main.rs".into(),
@@ -123,6 +125,8 @@ mod tests {
                 message: "Could be faster".into(),
                 suggestion: None,
                 agent: "backend".into(),
+                fix: None,
+                served_by: None,
             },
         ];
         let output = renderer.format(&findings);
@@ -146,6 +150,8 @@ mod tests {
                 message: "Problem in A".into(),
                 suggestion: None,
                 agent: "test".into(),
+                fix: None,
+                served_by: None,
             },
             Finding {
                 file: "src/b.rs".into(),
@@ -156,6 +162,8 @@ mod tests {
                 message: "Problem in B".into(),
                 suggestion: None,
                 agent: "test".into(),
+                fix: None,
+                served_by: None,
             },
         ];
         let output = renderer.format(&findings);
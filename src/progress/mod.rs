@@ -27,6 +27,11 @@ pub enum TaskStatus {
     Done,
     /// Failed after retries.
     Failed(String),
+    /// Not reviewed, with the reason (e.g. over budget).
+    Skipped(String),
+    /// Reviewed by only some of its agents, with the reason the others
+    /// were skipped.
+    Partial(String),
     /// Retrying after transient error.
     Retrying {
         attempt: u32,
@@ -95,7 +100,10 @@ impl ProgressTracker {
     /// the final status is always visible. For transient states, renders
     /// at most once per 100ms to avoid excessive terminal I/O.
     pub fn update(&self, file: &str, status: TaskStatus) {
-        let is_terminal_state = matches!(status, TaskStatus::Done | TaskStatus::Failed(_));
+        let is_terminal_state = matches!(
+            status,
+            TaskStatus::Done | TaskStatus::Failed(_) | TaskStatus::Partial(_)
+        );
         let mut state = self.inner.lock().unwrap();
         state.files.insert(file.to_string(), status);
         if self.enabled {
@@ -135,12 +143,18 @@ impl ProgressTracker {
             let icon = match status {
                 TaskStatus::Done => "✔".green().bold().to_string(),
                 TaskStatus::Failed(_) => "✖".red().bold().to_string(),
+                TaskStatus::Skipped(_) => "–".dimmed().to_string(),
+                TaskStatus::Partial(_) => "◐".yellow().bold().to_string(),
                 _ => "✔".green().bold().to_string(),
             };
             let file_display = file.dimmed();
             let status_text = match status {
                 TaskStatus::Done => "done".green().to_string(),
                 TaskStatus::Failed(reason) => format!("{}", reason.red()),
+                TaskStatus::Skipped(reason) => format!("skipped: {reason}").dimmed().to_string(),
+                TaskStatus::Partial(reason) => {
+                    format!("partly reviewed: {reason}").yellow().to_string()
+                }
                 _ => "done".green().to_string(),
             };
             let _ = writeln!(handle, "  {icon} {file_display} {status_text}");
@@ -221,6 +235,14 @@ impl ProgressTracker {
                 TaskStatus::Failed(reason) => {
                     ("✖".red().bold().to_string(), reason.red().to_string())
                 }
+                TaskStatus::Skipped(reason) => (
+                    "–".dimmed().to_string(),
+                    format!("skipped: {reason}").dimmed().to_string(),
+                ),
+                TaskStatus::Partial(reason) => (
+                    "◐".yellow().bold().to_string(),
+                    format!("partly reviewed: {reason}").yellow().to_string(),
+                ),
                 TaskStatus::Retrying {
                     attempt,
                     max,
@@ -0,0 +1,739 @@
+//! Native batch APIs for non-urgent reviews.
+//!
+//! Anthropic's Message Batches API and OpenAI's Batch API process a set
+//! of prompts asynchronously at roughly half the price of live calls.
+//! rig-core has no batch support, so this module talks to both APIs
+//! directly: submit every prompt as one job, poll until it ends, then
+//! download and parse the results.
+//!
+//! Live non-agentic Anthropic reviews also go through this client (see
+//! [`BatchClient::review_now`]): rig-core only marks the last system
+//! and message blocks for prompt caching, so it can't put a breakpoint
+//! on the shared review context.
+
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use crate::config::ProviderConfig;
+use crate::constants::{
+    BATCH_POLL_INTERVAL, BATCH_TRANSFER_TIMEOUT, MAX_BACKOFF, MAX_RETRIES, REVIEW_CALL_TIMEOUT,
+};
+use crate::cost::TokenUsage;
+use crate::models::ProviderName;
+
+use super::response::{is_retryable, parse_findings_response};
+use super::{ProviderError, REVIEW_SYSTEM_PROMPT, ReviewOutput};
+
+/// Anthropic API version header value.
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// A single review prompt submitted as part of a batch job.
+#[derive(Debug, Clone)]
+pub struct BatchRequest {
+    /// Caller-chosen ID used to match the result back to its task.
+    pub custom_id: String,
+    pub model: String,
+    /// Shared review context, sent as the first user block and marked for
+    /// prompt caching where the API supports it.
+    pub context: Option<String>,
+    /// Agent instructions and the per-task body, sent after the context.
+    pub prompt: String,
+}
+
+/// The outcome of one request in a finished batch.
+#[derive(Debug)]
+pub struct BatchResult {
+    pub custom_id: String,
+    /// Parsed findings and usage, or why the request failed.
+    pub output: Result<ReviewOutput, String>,
+}
+
+/// Which batch API a provider speaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BatchApi {
+    Anthropic,
+    OpenAI,
+}
+
+/// Progress of a submitted batch job.
+#[derive(Debug, PartialEq, Eq)]
+enum BatchState {
+    Running,
+    /// Finished; results can be downloaded from these URLs.
+    Ended(Vec<String>),
+}
+
+/// HTTP client for a provider's batch API.
+pub struct BatchClient {
+    http: reqwest::Client,
+    api: BatchApi,
+    base_url: String,
+    api_key: String,
+    /// Output token cap per request (required by Anthropic).
+    max_tokens: u64,
+    poll_interval: Duration,
+}
+
+impl BatchClient {
+    /// Create a client for the configured provider.
+    ///
+    /// Only the Anthropic and OpenAI providers have a batch API.
+    /// `base_url` in config overrides the public endpoint.
+    pub fn new(config: &ProviderConfig, max_tokens: u64) -> Result<Self, ProviderError> {
+        let (api, default_url) = match config.name {
+            ProviderName::Anthropic => (BatchApi::Anthropic, "https://api.anthropic.com"),
+            ProviderName::OpenAI => (BatchApi::OpenAI, "https://api.openai.com/v1"),
+            other => {
+                return Err(ProviderError::NotConfigured(format!(
+                    "batch mode requires the anthropic or openai provider, not '{other}'"
+                )));
+            }
+        };
+        let api_key = config
+            .api_key
+            .clone()
+            .ok_or_else(|| ProviderError::NotConfigured("missing API key".to_string()))?;
+        let http = crate::http::build_client()
+            .map_err(|e| ProviderError::ApiError(format!("failed to create HTTP client: {e}")))?;
+        let base_url = config
+            .base_url
+            .as_deref()
+            .unwrap_or(default_url)
+            .trim_end_matches('/')
+            .to_string();
+        Ok(Self {
+            http,
+            api,
+            base_url,
+            api_key,
+            max_tokens,
+            poll_interval: BATCH_POLL_INTERVAL,
+        })
+    }
+
+    /// Override how often [`wait`](Self::wait) polls the batch status.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Submit `requests` as one batch job and return its ID.
+    pub async fn submit(&self, requests: &[BatchRequest]) -> Result<String, ProviderError> {
+        let response = match self.api {
+            BatchApi::Anthropic => {
+                let requests: Vec<Value> = requests
+                    .iter()
+                    .map(|r| {
+                        json!({
+                            "custom_id": r.custom_id,
+                            "params": self.anthropic_params(r),
+                        })
+                    })
+                    .collect();
+                let request = self
+                    .http
+                    .post(format!("{}/v1/messages/batches", self.base_url))
+                    .json(&json!({ "requests": requests }));
+                self.send_json(request, BATCH_TRANSFER_TIMEOUT).await?
+            }
+            BatchApi::OpenAI => {
+                let file_id = self.upload_openai_input(requests).await?;
+                let request = self
+                    .http
+                    .post(format!("{}/batches", self.base_url))
+                    .json(&json!({
+                        "input_file_id": file_id,
+                        "endpoint": "/v1/chat/completions",
+                        "completion_window": "24h",
+                    }));
+                self.send_json(request, BATCH_TRANSFER_TIMEOUT).await?
+            }
+        };
+        response["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ProviderError::ParseError("batch response has no id".to_string()))
+    }
+
+    /// Send one request to Anthropic's Messages API without batching it.
+    ///
+    /// The request body is the one [`submit`](Self::submit) uses, so the
+    /// shared context gets its own cache breakpoint on live calls too.
+    pub async fn review_now(&self, request: &BatchRequest) -> Result<ReviewOutput, ProviderError> {
+        if self.api != BatchApi::Anthropic {
+            return Err(ProviderError::NotConfigured(
+                "direct review calls require the anthropic provider".to_string(),
+            ));
+        }
+        let request = self
+            .http
+            .post(format!("{}/v1/messages", self.base_url))
+            .json(&self.anthropic_params(request));
+        let message = self.send_json(request, REVIEW_CALL_TIMEOUT).await?;
+        parse_anthropic_message(&message).map_err(ProviderError::ParseError)
+    }
+
+    /// Messages API parameters for one request.
+    ///
+    /// The breakpoint goes on the context block, which every agent shares.
+    fn anthropic_params(&self, request: &BatchRequest) -> Value {
+        let mut content: Vec<Value> = request
+            .context
+            .iter()
+            .map(|context| {
+                json!({
+                    "type": "text",
+                    "text": context,
+                    "cache_control": {"type": "ephemeral"},
+                })
+            })
+            .collect();
+        content.push(json!({"type": "text", "text": request.prompt}));
+        json!({
+            "model": request.model,
+            "max_tokens": self.max_tokens,
+            "temperature": 0.0,
+            "system": REVIEW_SYSTEM_PROMPT,
+            "messages": [{"role": "user", "content": content}],
+        })
+    }
+
+    /// Poll until the batch ends, then download and parse its results.
+    ///
+    /// Requests that the batch didn't complete (errored, expired or
+    /// canceled) are returned with an error message. Transient HTTP errors
+    /// while polling or downloading are retried with backoff.
+    pub async fn wait(&self, batch_id: &str) -> Result<Vec<BatchResult>, ProviderError> {
+        let urls = loop {
+            match self.retrying(|| self.state(batch_id)).await? {
+                BatchState::Running => tokio::time::sleep(self.poll_interval).await,
+                BatchState::Ended(urls) => break urls,
+            }
+        };
+        let mut results = Vec::new();
+        for url in urls {
+            let body = self
+                .retrying(|| self.send_text(self.http.get(&url), BATCH_TRANSFER_TIMEOUT))
+                .await?;
+            results.extend(body.lines().filter_map(|line| self.parse_result(line)));
+        }
+        Ok(results)
+    }
+
+    /// Run `request`, retrying transient errors up to [`MAX_RETRIES`]
+    /// times. The backoff starts at the poll interval and doubles.
+    async fn retrying<T, F>(&self, request: impl Fn() -> F) -> Result<T, ProviderError>
+    where
+        F: Future<Output = Result<T, ProviderError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Err(e) if is_retryable(&e) && attempt < MAX_RETRIES => {
+                    let backoff = self
+                        .poll_interval
+                        .saturating_mul(2u32.saturating_pow(attempt))
+                        .min(MAX_BACKOFF.max(self.poll_interval));
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Upload the JSONL input file OpenAI batches are created from.
+    async fn upload_openai_input(
+        &self,
+        requests: &[BatchRequest],
+    ) -> Result<String, ProviderError> {
+        let mut jsonl = String::new();
+        for r in requests {
+            // OpenAI caches prompt prefixes automatically, so the context
+            // only needs to come first.
+            let content: Vec<Value> = r
+                .context
+                .iter()
+                .chain([&r.prompt])
+                .map(|text| json!({"type": "text", "text": text}))
+                .collect();
+            let line = json!({
+                "custom_id": r.custom_id,
+                "method": "POST",
+                "url": "/v1/chat/completions",
+                "body": {
+                    "model": r.model,
+                    "temperature": 0.0,
+                    "messages": [
+                        {"role": "system", "content": REVIEW_SYSTEM_PROMPT},
+                        {"role": "user", "content": content},
+                    ],
+                },
+            });
+            jsonl.push_str(&line.to_string());
+            jsonl.push('\n');
+        }
+        let file = reqwest::multipart::Part::text(jsonl)
+            .file_name("nitpik-batch.jsonl")
+            .mime_str("application/jsonl")
+            .map_err(|e| ProviderError::ApiError(format!("batch upload: {e}")))?;
+        let form = reqwest::multipart::Form::new()
+            .text("purpose", "batch")
+            .part("file", file);
+        let request = self
+            .http
+            .post(format!("{}/files", self.base_url))
+            .multipart(form);
+        let response = self.send_json(request, BATCH_TRANSFER_TIMEOUT).await?;
+        response["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ProviderError::ParseError("file upload response has no id".to_string()))
+    }
+
+    /// Check whether the batch has ended and where its results are.
+    async fn state(&self, batch_id: &str) -> Result<BatchState, ProviderError> {
+        match self.api {
+            BatchApi::Anthropic => {
+                let url = format!("{}/v1/messages/batches/{batch_id}", self.base_url);
+                let status = self
+                    .send_json(self.http.get(&url), BATCH_TRANSFER_TIMEOUT)
+                    .await?;
+                Ok(match status["processing_status"].as_str() {
+                    Some("ended") => BatchState::Ended(vec![format!("{url}/results")]),
+                    _ => BatchState::Running,
+                })
+            }
+            BatchApi::OpenAI => {
+                let url = format!("{}/batches/{batch_id}", self.base_url);
+                let status = self
+                    .send_json(self.http.get(url), BATCH_TRANSFER_TIMEOUT)
+                    .await?;
+                match status["status"].as_str() {
+                    Some("completed" | "expired" | "cancelled") => {
+                        // Successes and failures are written to separate files
+                        let urls = ["output_file_id", "error_file_id"]
+                            .iter()
+                            .filter_map(|key| status[key].as_str())
+                            .map(|id| format!("{}/files/{id}/content", self.base_url))
+                            .collect();
+                        Ok(BatchState::Ended(urls))
+                    }
+                    Some("failed") => {
+                        let reason = status["errors"]["data"][0]["message"]
+                            .as_str()
+                            .unwrap_or("unknown error");
+                        Err(ProviderError::ApiError(format!(
+                            "batch {batch_id} failed: {reason}"
+                        )))
+                    }
+                    _ => Ok(BatchState::Running),
+                }
+            }
+        }
+    }
+
+    /// Parse one JSONL result line; blank or malformed lines are skipped.
+    fn parse_result(&self, line: &str) -> Option<BatchResult> {
+        let value: Value = serde_json::from_str(line.trim()).ok()?;
+        let custom_id = value["custom_id"].as_str()?.to_string();
+        let output = match self.api {
+            BatchApi::Anthropic => parse_anthropic_result(&value["result"]),
+            BatchApi::OpenAI => parse_openai_result(&value),
+        };
+        Some(BatchResult { custom_id, output })
+    }
+
+    /// Send a request with auth headers and parse the JSON response.
+    async fn send_json(
+        &self,
+        request: reqwest::RequestBuilder,
+        timeout: Duration,
+    ) -> Result<Value, ProviderError> {
+        let body = self.send_text(request, timeout).await?;
+        serde_json::from_str(&body).map_err(|e| ProviderError::ParseError(e.to_string()))
+    }
+
+    /// Send a request with auth headers and return the response body.
+    async fn send_text(
+        &self,
+        request: reqwest::RequestBuilder,
+        timeout: Duration,
+    ) -> Result<String, ProviderError> {
+        let request = match self.api {
+            BatchApi::Anthropic => request
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", ANTHROPIC_VERSION),
+            BatchApi::OpenAI => request.bearer_auth(&self.api_key),
+        };
+        let response = request
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| ProviderError::ApiError(format!("request failed: {e}")))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| ProviderError::ApiError(format!("reading response: {e}")))?;
+        if !status.is_success() {
+            return Err(ProviderError::ApiError(format!(
+                "API returned {status}: {body}"
+            )));
+        }
+        Ok(body)
+    }
+}
+
+/// Parse an Anthropic `result` object.
+fn parse_anthropic_result(result: &Value) -> Result<ReviewOutput, String> {
+    if result["type"] != "succeeded" {
+        let reason = result["error"]["error"]["message"]
+            .as_str()
+            .or(result["type"].as_str())
+            .unwrap_or("unknown error");
+        return Err(reason.to_string());
+    }
+    parse_anthropic_message(&result["message"])
+}
+
+/// Parse an Anthropic Messages API response.
+fn parse_anthropic_message(message: &Value) -> Result<ReviewOutput, String> {
+    let text: String = message["content"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|block| block["type"] == "text")
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    let usage = &message["usage"];
+    let count = |field: &str| usage[field].as_u64().unwrap_or(0);
+    // Anthropic's `input_tokens` excludes tokens read from or written to
+    // the prompt cache.
+    let usage = TokenUsage {
+        input_tokens: count("input_tokens")
+            + count("cache_read_input_tokens")
+            + count("cache_creation_input_tokens"),
+        output_tokens: count("output_tokens"),
+        cached_input_tokens: count("cache_read_input_tokens"),
+        reasoning_tokens: 0,
+    };
+    let findings = parse_findings_response(&text).map_err(|e| e.to_string())?;
+    Ok(ReviewOutput { findings, usage })
+}
+
+/// Parse an OpenAI output or error file line.
+fn parse_openai_result(line: &Value) -> Result<ReviewOutput, String> {
+    let response = &line["response"];
+    if let Some(reason) = line["error"]["message"].as_str() {
+        return Err(reason.to_string());
+    }
+    let body = &response["body"];
+    if response["status_code"] != 200 {
+        let reason = body["error"]["message"]
+            .as_str()
+            .unwrap_or("request failed");
+        return Err(reason.to_string());
+    }
+    let text = body["choices"][0]["message"]["content"]
+        .as_str()
+        .unwrap_or_default();
+    let usage = &body["usage"];
+    let usage = TokenUsage {
+        input_tokens: usage["prompt_tokens"].as_u64().unwrap_or(0),
+        output_tokens: usage["completion_tokens"].as_u64().unwrap_or(0),
+        cached_input_tokens: usage["prompt_tokens_details"]["cached_tokens"]
+            .as_u64()
+            .unwrap_or(0),
+        reasoning_tokens: usage["completion_tokens_details"]["reasoning_tokens"]
+            .as_u64()
+            .unwrap_or(0),
+    };
+    let findings = parse_findings_response(text).map_err(|e| e.to_string())?;
+    Ok(ReviewOutput { findings, usage })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock::{MockServer, Route};
+
+    const FINDINGS: &str = r#"[{"file":"src/main.rs","line":2,"severity":"warning","title":"Unused","message":"x is unused","agent":"backend"}]"#;
+
+    fn config(name: ProviderName, base_url: &str) -> ProviderConfig {
+        ProviderConfig {
+            name,
+            model: None,
+            base_url: Some(base_url.to_string()),
+            api_key: Some("sk-test".to_string()),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    fn requests() -> Vec<BatchRequest> {
+        ["task-0", "task-1"]
+            .into_iter()
+            .map(|id| BatchRequest {
+                custom_id: id.to_string(),
+                model: "test-model".to_string(),
+                context: Some("## Full File Content: src/main.rs".to_string()),
+                prompt: "Review this.".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unsupported_provider_is_rejected() {
+        let err = BatchClient::new(&config(ProviderName::Gemini, "http://x"), 1000)
+            .err()
+            .expect("gemini has no batch API");
+        assert!(err.to_string().contains("anthropic or openai"));
+    }
+
+    #[tokio::test]
+    async fn anthropic_submit_poll_and_collect() {
+        let succeeded = json!({
+            "custom_id": "task-0",
+            "result": {
+                "type": "succeeded",
+                "message": {
+                    "content": [{"type": "text", "text": FINDINGS}],
+                    "usage": {
+                        "input_tokens": 200,
+                        "output_tokens": 80,
+                        "cache_read_input_tokens": 1000,
+                        "cache_creation_input_tokens": 0,
+                    },
+                },
+            },
+        });
+        let errored = json!({
+            "custom_id": "task-1",
+            "result": {
+                "type": "errored",
+                "error": {"type": "error", "error": {"type": "invalid_request_error", "message": "prompt is too long"}},
+            },
+        });
+        let results = format!("{succeeded}\n{errored}\n");
+        let server = MockServer::start(vec![
+            Route::new(
+                "POST",
+                "/v1/messages/batches",
+                200,
+                r#"{"id":"msgbatch_1","processing_status":"in_progress"}"#,
+            ),
+            Route::new(
+                "GET",
+                "/v1/messages/batches/msgbatch_1",
+                200,
+                r#"{"id":"msgbatch_1","processing_status":"in_progress"}"#,
+            ),
+            Route::new(
+                "GET",
+                "/v1/messages/batches/msgbatch_1",
+                200,
+                r#"{"id":"msgbatch_1","processing_status":"ended"}"#,
+            ),
+            Route::new(
+                "GET",
+                "/v1/messages/batches/msgbatch_1/results",
+                200,
+                &results,
+            ),
+        ])
+        .await;
+
+        let client = BatchClient::new(&config(ProviderName::Anthropic, &server.url), 4096)
+            .unwrap()
+            .with_poll_interval(Duration::from_millis(1));
+        let id = client.submit(&requests()).await.unwrap();
+        assert_eq!(id, "msgbatch_1");
+
+        let results = client.wait(&id).await.unwrap();
+        assert_eq!(results.len(), 2);
+        let output = results[0].output.as_ref().unwrap();
+        assert_eq!(results[0].custom_id, "task-0");
+        assert_eq!(output.findings.len(), 1);
+        assert_eq!(output.usage.input_tokens, 1200);
+        assert_eq!(output.usage.cached_input_tokens, 1000);
+        assert_eq!(
+            results[1].output.as_ref().unwrap_err(),
+            "prompt is too long"
+        );
+
+        let requests = server.requests();
+        let submit = &requests[0];
+        assert_eq!(submit.header("x-api-key"), Some("sk-test"));
+        assert_eq!(submit.header("anthropic-version"), Some(ANTHROPIC_VERSION));
+        let body = submit.json();
+        assert_eq!(body["requests"][1]["custom_id"], "task-1");
+        assert_eq!(body["requests"][0]["params"]["max_tokens"], 4096);
+        let params = &body["requests"][0]["params"];
+        assert_eq!(params["system"], REVIEW_SYSTEM_PROMPT);
+        let content = &params["messages"][0]["content"];
+        assert_eq!(content[0]["text"], "## Full File Content: src/main.rs");
+        assert_eq!(content[0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(content[1]["text"], "Review this.");
+        // submit, two polls, one download
+        assert_eq!(requests.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn openai_upload_submit_and_collect() {
+        let output_line = json!({
+            "custom_id": "task-0",
+            "response": {
+                "status_code": 200,
+                "body": {
+                    "choices": [{"message": {"content": FINDINGS}}],
+                    "usage": {
+                        "prompt_tokens": 900,
+                        "completion_tokens": 300,
+                        "prompt_tokens_details": {"cached_tokens": 0},
+                        "completion_tokens_details": {"reasoning_tokens": 120},
+                    },
+                },
+            },
+            "error": null,
+        });
+        let error_line = json!({
+            "custom_id": "task-1",
+            "response": {"status_code": 400, "body": {"error": {"message": "bad request"}}},
+            "error": null,
+        });
+        let server = MockServer::start(vec![
+            Route::new("POST", "/files", 200, r#"{"id":"file-in"}"#),
+            Route::new(
+                "POST",
+                "/batches",
+                200,
+                r#"{"id":"batch_1","status":"validating"}"#,
+            ),
+            Route::new(
+                "GET",
+                "/batches/batch_1",
+                200,
+                r#"{"id":"batch_1","status":"in_progress"}"#,
+            ),
+            Route::new(
+                "GET",
+                "/batches/batch_1",
+                200,
+                r#"{"id":"batch_1","status":"completed","output_file_id":"file-out","error_file_id":"file-err"}"#,
+            ),
+            Route::new("GET", "/files/file-out/content", 200, &output_line.to_string()),
+            Route::new("GET", "/files/file-err/content", 200, &error_line.to_string()),
+        ])
+        .await;
+
+        let client = BatchClient::new(&config(ProviderName::OpenAI, &server.url), 4096)
+            .unwrap()
+            .with_poll_interval(Duration::from_millis(1));
+        let id = client.submit(&requests()).await.unwrap();
+        assert_eq!(id, "batch_1");
+
+        let results = client.wait(&id).await.unwrap();
+        assert_eq!(results.len(), 2);
+        let output = results[0].output.as_ref().unwrap();
+        assert_eq!(output.findings[0].title, "Unused");
+        assert_eq!(output.usage.output_tokens, 300);
+        assert_eq!(output.usage.reasoning_tokens, 120);
+        assert_eq!(results[1].output.as_ref().unwrap_err(), "bad request");
+
+        let requests = server.requests();
+        let upload = &requests[0];
+        assert_eq!(upload.header("authorization"), Some("Bearer sk-test"));
+        assert!(upload.body.contains("name=\"purpose\""));
+        assert!(upload.body.contains(r#""custom_id":"task-1""#));
+        assert!(upload.body.contains(r#""url":"/v1/chat/completions""#));
+        let first = upload.body.find("Full File Content").unwrap();
+        assert!(first < upload.body.find("Review this.").unwrap());
+        assert_eq!(requests[1].json()["input_file_id"], "file-in");
+    }
+
+    #[tokio::test]
+    async fn anthropic_review_now_sends_one_message() {
+        let message = json!({
+            "content": [{"type": "text", "text": FINDINGS}],
+            "usage": {"input_tokens": 50, "output_tokens": 20, "cache_read_input_tokens": 900},
+        });
+        let server = MockServer::start(vec![Route::new(
+            "POST",
+            "/v1/messages",
+            200,
+            &message.to_string(),
+        )])
+        .await;
+        let client = BatchClient::new(&config(ProviderName::Anthropic, &server.url), 4096).unwrap();
+
+        let output = client.review_now(&requests()[0]).await.unwrap();
+        assert_eq!(output.findings.len(), 1);
+        assert_eq!(output.usage.input_tokens, 950);
+        assert_eq!(output.usage.cached_input_tokens, 900);
+
+        let body = server.requests()[0].json();
+        assert_eq!(body["system"], REVIEW_SYSTEM_PROMPT);
+        assert_eq!(
+            body["messages"][0]["content"][0]["cache_control"]["type"],
+            "ephemeral"
+        );
+    }
+
+    #[tokio::test]
+    async fn transient_errors_are_retried() {
+        let succeeded = json!({
+            "custom_id": "task-0",
+            "result": {
+                "type": "succeeded",
+                "message": {
+                    "content": [{"type": "text", "text": FINDINGS}],
+                    "usage": {"input_tokens": 200, "output_tokens": 80},
+                },
+            },
+        });
+        let server = MockServer::start(vec![
+            Route::new("GET", "/v1/messages/batches/msgbatch_1", 503, "unavailable"),
+            Route::new(
+                "GET",
+                "/v1/messages/batches/msgbatch_1",
+                200,
+                r#"{"id":"msgbatch_1","processing_status":"ended"}"#,
+            ),
+            Route::new(
+                "GET",
+                "/v1/messages/batches/msgbatch_1/results",
+                502,
+                "bad gateway",
+            ),
+            Route::new(
+                "GET",
+                "/v1/messages/batches/msgbatch_1/results",
+                200,
+                &succeeded.to_string(),
+            ),
+        ])
+        .await;
+        let client = BatchClient::new(&config(ProviderName::Anthropic, &server.url), 4096)
+            .unwrap()
+            .with_poll_interval(Duration::from_millis(1));
+        let results = client.wait("msgbatch_1").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].output.is_ok());
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn openai_failed_batch_is_an_error() {
+        let server = MockServer::start(vec![Route::new(
+            "GET",
+            "/batches/batch_1",
+            200,
+            r#"{"id":"batch_1","status":"failed","errors":{"data":[{"message":"invalid input file"}]}}"#,
+        )])
+        .await;
+        let client = BatchClient::new(&config(ProviderName::OpenAI, &server.url), 4096).unwrap();
+        let err = client.wait("batch_1").await.unwrap_err();
+        assert!(err.to_string().contains("invalid input file"));
+    }
+}
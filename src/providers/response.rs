@@ -12,6 +12,7 @@
 use crate::constants::{INITIAL_BACKOFF, MAX_BACKOFF};
 use crate::models::finding::Finding;
 use crate::providers::ProviderError;
+use indexmap::IndexMap;
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
 use std::time::Duration;
@@ -34,6 +35,18 @@ pub fn is_retryable(err: &ProviderError) -> bool {
     }
 }
 
+/// Check whether a provider error means the API is rate limiting or
+/// overloaded (429 / 529), as opposed to failing for another reason.
+///
+/// Repeated overloads are a signal to switch to a fallback provider
+/// rather than wait out the backoff.
+pub fn is_overloaded(err: &ProviderError) -> bool {
+    matches!(
+        classify_error(err),
+        Some("Rate limited by API" | "API overloaded")
+    )
+}
+
 /// Classifies a provider error into a short, user-friendly message.
 ///
 /// Returns `Some(message)` for transient/retryable errors, `None` otherwise.
@@ -114,14 +127,15 @@ pub fn parse_with_fallbacks<T: DeserializeOwned>(response: &str) -> Result<T, Pr
 /// Treats an empty response as zero findings (not an error). Otherwise
 /// runs [`parse_with_fallbacks`] for `Vec<Finding>` first, then for the
 /// `{"findings": [...]}` wrapper shape some providers emit. Trailing
-/// whitespace in string fields is trimmed before returning.
+/// whitespace in string fields is trimmed and fixes with an impossible
+/// line range are dropped before returning.
 pub fn parse_findings_response(response: &str) -> Result<Vec<Finding>, ProviderError> {
     if response.trim().is_empty() {
         return Ok(Vec::new());
     }
 
     if let Ok(findings) = parse_with_fallbacks::<Vec<Finding>>(response) {
-        return Ok(trim_finding_fields(findings));
+        return Ok(drop_malformed_fixes(trim_finding_fields(findings)));
     }
 
     #[derive(Deserialize)]
@@ -129,7 +143,62 @@ pub fn parse_findings_response(response: &str) -> Result<Vec<Finding>, ProviderE
         findings: Vec<Finding>,
     }
 
-    parse_with_fallbacks::<Wrapper>(response).map(|w| trim_finding_fields(w.findings))
+    parse_with_fallbacks::<Wrapper>(response)
+        .map(|w| drop_malformed_fixes(trim_finding_fields(w.findings)))
+}
+
+/// Drop fixes whose line range cannot exist in any file.
+///
+/// The finding itself is kept — only the unusable fix is removed.
+fn drop_malformed_fixes(findings: Vec<Finding>) -> Vec<Finding> {
+    findings
+        .into_iter()
+        .map(|mut f| {
+            if f.fix
+                .as_ref()
+                .is_some_and(|fix| fix.start_line == 0 || fix.end_line < fix.start_line)
+            {
+                f.fix = None;
+            }
+            f
+        })
+        .collect()
+}
+
+/// Check each finding's fix against the content of the file it edits.
+///
+/// `file_contents` must hold the complete, unredacted files keyed by
+/// repo-relative path. A fix is dropped when its file is missing, its
+/// range runs past the end of the file, it reproduces a secret
+/// redaction placeholder, or it leaves the lines unchanged. Surviving
+/// fixes record the lines they replace in `original` so a later
+/// `nitpik fix` can tell whether the file has changed since the review.
+pub fn validate_fixes(
+    findings: Vec<Finding>,
+    file_contents: &IndexMap<String, String>,
+) -> Vec<Finding> {
+    findings
+        .into_iter()
+        .map(|mut f| {
+            if let Some(mut fix) = f.fix.take() {
+                let original = file_contents.get(&f.file).and_then(|content| {
+                    let lines: Vec<&str> = content.lines().collect();
+                    let start = fix.start_line as usize;
+                    let end = fix.end_line as usize;
+                    (start >= 1 && end >= start && end <= lines.len())
+                        .then(|| lines[start - 1..end].join("\n"))
+                });
+                if let Some(original) = original
+                    && !fix.replacement.contains("[REDACTED:")
+                    && fix.replacement.trim_end_matches(['\r', '\n']) != original
+                {
+                    fix.original = Some(original);
+                    f.fix = Some(fix);
+                }
+            }
+            f
+        })
+        .collect()
 }
 
 /// Trim trailing whitespace from LLM-generated string fields.
@@ -311,6 +380,19 @@ mod tests {
         assert!(!is_retryable(&err));
     }
 
+    #[test]
+    fn overloaded_only_for_rate_limits_and_overload() {
+        let rate_limited = ProviderError::ApiError("status code 429 Too Many Requests".into());
+        let overloaded = ProviderError::ApiError("529 overloaded_error".into());
+        let unavailable = ProviderError::ApiError("503 Service Unavailable".into());
+        assert!(is_overloaded(&rate_limited));
+        assert!(is_overloaded(&overloaded));
+        assert!(!is_overloaded(&unavailable));
+        assert!(!is_overloaded(&ProviderError::ParseError(
+            "bad json".into()
+        )));
+    }
+
     #[test]
     fn backoff_is_exponential() {
         let b0 = retry_backoff(0);
@@ -465,4 +547,84 @@ That's all."#;
         assert_eq!(findings[0].message, "Description with trailing newline");
         assert_eq!(findings[0].suggestion.as_deref(), Some("Fix it"));
     }
+
+    #[test]
+    fn parse_structured_fix() {
+        let response = r#"[
+            {
+                "file": "src/main.rs",
+                "line": 2,
+                "severity": "warning",
+                "title": "Unwrap",
+                "message": "May panic",
+                "agent": "test",
+                "fix": {"start_line": 2, "end_line": 2, "replacement": "let x = y?;"}
+            },
+            {
+                "file": "src/main.rs",
+                "line": 5,
+                "severity": "warning",
+                "title": "Backwards range",
+                "message": "Bad fix",
+                "agent": "test",
+                "fix": {"start_line": 5, "end_line": 4, "replacement": ""}
+            }
+        ]"#;
+        let findings = parse_findings_response(response).unwrap();
+        let fix = findings[0].fix.as_ref().unwrap();
+        assert_eq!((fix.start_line, fix.end_line), (2, 2));
+        assert_eq!(fix.replacement, "let x = y?;");
+        assert!(fix.original.is_none());
+        assert_eq!(findings.len(), 2);
+        assert!(findings[1].fix.is_none());
+    }
+
+    fn finding_with_fix(start_line: u32, end_line: u32, replacement: &str) -> Finding {
+        Finding {
+            file: "src/main.rs".into(),
+            line: start_line,
+            end_line: None,
+            severity: crate::models::Severity::Warning,
+            title: "T".into(),
+            message: "M".into(),
+            suggestion: None,
+            agent: "test".into(),
+            fix: Some(crate::models::finding::Fix {
+                start_line,
+                end_line,
+                replacement: replacement.into(),
+                original: None,
+            }),
+            served_by: None,
+        }
+    }
+
+    #[test]
+    fn validate_fixes_records_original_lines() {
+        let mut contents = IndexMap::new();
+        contents.insert("src/main.rs".to_string(), "a\nb\nc\n".to_string());
+        let findings = validate_fixes(vec![finding_with_fix(2, 3, "B\nC")], &contents);
+        assert_eq!(
+            findings[0].fix.as_ref().unwrap().original.as_deref(),
+            Some("b\nc")
+        );
+    }
+
+    #[test]
+    fn validate_fixes_drops_unusable_fixes() {
+        let mut contents = IndexMap::new();
+        contents.insert("src/main.rs".to_string(), "a\nb\nc\n".to_string());
+        let findings = validate_fixes(
+            vec![
+                finding_with_fix(3, 4, "x"),
+                finding_with_fix(2, 2, "b\n"),
+                finding_with_fix(1, 1, "key = [REDACTED:api-key]"),
+            ],
+            &contents,
+        );
+        assert!(findings.iter().all(|f| f.fix.is_none()));
+
+        let findings = validate_fixes(vec![finding_with_fix(1, 1, "x")], &IndexMap::new());
+        assert!(findings[0].fix.is_none());
+    }
 }
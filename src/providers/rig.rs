@@ -7,23 +7,37 @@
 //!
 //! In agentic mode (`--agent`), tools are registered with the agent for
 //! multi-turn codebase exploration via rig-core's native tool calling.
+//!
+//! Every review shares one system prompt, and the user turn opens with
+//! the shared review context, so providers with automatic prefix caching
+//! (OpenAI, DeepSeek, Gemini) reuse the context across tasks and agents.
+//! Anthropic caches only marked blocks and rig-core can't mark the
+//! context block, so non-agentic Anthropic reviews go straight to the
+//! Messages API via [`BatchClient::review_now`]. Agentic Anthropic
+//! reviews get rig-core's breakpoints on the system prompt and the
+//! latest turn.
 
 use std::path::PathBuf;
 
 use async_trait::async_trait;
+use rig::agent::AgentBuilder;
 use rig::client::CompletionClient;
-use rig::completion::Prompt;
+use rig::completion::{CompletionModel, Prompt};
 use rig::providers;
 use schemars::JsonSchema;
 
 use crate::config::ProviderConfig;
+use crate::cost::TokenUsage;
 use crate::models::agent::CustomToolDefinition;
 use crate::models::finding::Finding;
 use crate::models::{AgentDefinition, ProviderName};
 use crate::providers::response::{parse_findings_response, parse_with_fallbacks};
 use crate::tools::{CustomCommandTool, ListDirectoryTool, ReadFileTool, SearchTextTool};
 
-use super::{ProviderError, ReviewProvider, TriageVerdict};
+use super::batch::{BatchClient, BatchRequest, BatchResult};
+use super::{
+    ProviderError, REVIEW_SYSTEM_PROMPT, ReviewOutput, ReviewPrompt, ReviewProvider, TriageVerdict,
+};
 
 /// Maximum tokens per LLM completion response.
 ///
@@ -68,6 +82,22 @@ struct CallArgs<'a> {
 
 /// Dispatch a single LLM call through a rig-core client.
 ///
+/// Uses the client's default completion model for `args.model`; see
+/// [`dispatch_model`].
+async fn dispatch_review<C: CompletionClient, T: JsonSchema>(
+    client: &C,
+    args: CallArgs<'_>,
+) -> Result<(String, TokenUsage), ProviderError>
+where
+    <C as CompletionClient>::CompletionModel: 'static,
+{
+    dispatch_model::<_, T>(client.completion_model(args.model), args).await
+}
+
+/// Dispatch a single LLM call through a rig-core completion model.
+///
+/// Returns the final response text and the usage summed over all turns.
+///
 /// In non-agentic mode the agent is built with `output_schema::<T>()`,
 /// so providers that support native structured output constrain the
 /// response server-side. In agentic mode the schema is **not** set
@@ -77,15 +107,12 @@ struct CallArgs<'a> {
 /// silently ignore it. The agentic prompt itself instructs the LLM to
 /// return JSON, and [`parse_with_fallbacks`] handles markdown-fenced
 /// or prose-prefixed responses.
-async fn dispatch_review<C: CompletionClient, T: JsonSchema>(
-    client: &C,
+async fn dispatch_model<M: CompletionModel + 'static, T: JsonSchema>(
+    completion_model: M,
     args: CallArgs<'_>,
-) -> Result<String, ProviderError>
-where
-    <C as CompletionClient>::CompletionModel: 'static,
-{
+) -> Result<(String, TokenUsage), ProviderError> {
     let CallArgs {
-        model,
+        model: _,
         system_prompt,
         user_prompt,
         label,
@@ -97,8 +124,7 @@ where
     // (NoToolConfig → WithBuilderTools), so the agentic and non-agentic
     // branches construct separate builders and await independently.
     let (agent_label, response) = if let Some(cfg) = agentic {
-        let mut builder = client
-            .agent(model)
+        let mut builder = AgentBuilder::new(completion_model)
             .preamble(system_prompt)
             .temperature(0.0)
             .max_tokens(max_tokens)
@@ -111,19 +137,44 @@ where
         }
 
         let agent = builder.default_max_turns(cfg.max_turns).build();
-        ("agentic error", agent.prompt(user_prompt).await)
+        (
+            "agentic error",
+            agent.prompt(user_prompt).extended_details().await,
+        )
     } else {
-        let agent = client
-            .agent(model)
+        let agent = AgentBuilder::new(completion_model)
             .preamble(system_prompt)
             .temperature(0.0)
             .max_tokens(max_tokens)
             .output_schema::<T>()
             .build();
-        ("API error", agent.prompt(user_prompt).await)
+        (
+            "API error",
+            agent.prompt(user_prompt).extended_details().await,
+        )
     };
 
-    response.map_err(|e| ProviderError::ApiError(format!("{label} {agent_label}: {e}")))
+    let response =
+        response.map_err(|e| ProviderError::ApiError(format!("{label} {agent_label}: {e}")))?;
+    Ok((response.output, token_usage(&response.usage)))
+}
+
+/// Convert rig-core's usage counts.
+///
+/// rig-core's input count excludes prompt-cache reads and writes for
+/// Anthropic but includes them for OpenAI; its total includes them for
+/// both, so input is derived from the total where that is larger.
+/// rig-core doesn't report reasoning tokens separately; providers that
+/// bill them include them in the output count.
+fn token_usage(usage: &rig::completion::Usage) -> TokenUsage {
+    TokenUsage {
+        input_tokens: usage
+            .input_tokens
+            .max(usage.total_tokens.saturating_sub(usage.output_tokens)),
+        output_tokens: usage.output_tokens,
+        cached_input_tokens: usage.cached_input_tokens,
+        reasoning_tokens: 0,
+    }
 }
 
 /// rig-core based review provider.
@@ -192,7 +243,10 @@ impl RigProvider {
     /// final response (e.g. `Vec<Finding>` for review,
     /// `Vec<TriageVerdict>` for triage). Each match arm constructs the
     /// concrete provider client and forwards to [`dispatch_review`].
-    async fn call<T: JsonSchema>(&self, args: CallArgs<'_>) -> Result<String, ProviderError> {
+    async fn call<T: JsonSchema>(
+        &self,
+        args: CallArgs<'_>,
+    ) -> Result<(String, TokenUsage), ProviderError> {
         // Ollama does not require an API key; all other providers do.
         let api_key = if self.config.name == ProviderName::Ollama {
             self.config.api_key.as_deref().unwrap_or("")
@@ -208,7 +262,9 @@ impl RigProvider {
                         .build(),
                     "Anthropic",
                 )?;
-                dispatch_review::<_, T>(&client, args).await
+                // Breakpoints on the system prompt and the latest turn.
+                let model = client.completion_model(args.model).with_prompt_caching();
+                dispatch_model::<_, T>(model, args).await
             }
             ProviderName::OpenAI => {
                 let client = self.build_openai_client(api_key)?;
@@ -326,11 +382,11 @@ impl ReviewProvider for RigProvider {
     async fn review(
         &self,
         agent: &AgentDefinition,
-        prompt: &str,
+        prompt: &ReviewPrompt,
         agentic: bool,
         max_turns: usize,
         _max_tool_calls: usize,
-    ) -> Result<Vec<Finding>, ProviderError> {
+    ) -> Result<ReviewOutput, ProviderError> {
         let model = agent
             .profile
             .model
@@ -338,7 +394,7 @@ impl ReviewProvider for RigProvider {
             .unwrap_or_else(|| self.config.resolved_model());
 
         let agentic_system_prompt;
-        let (system_prompt, agentic_cfg) = if agentic {
+        let (agent_prompt, agentic_cfg) = if agentic {
             let custom_tools: Vec<CustomCommandTool> = agent
                 .profile
                 .tools
@@ -369,19 +425,38 @@ impl ReviewProvider for RigProvider {
         } else {
             (agent.system_prompt.as_str(), None)
         };
+        let task = prompt.task_block(agent_prompt);
+
+        if agentic_cfg.is_none() && self.config.name == ProviderName::Anthropic {
+            return BatchClient::new(&self.config, MAX_TOKENS)?
+                .review_now(&BatchRequest {
+                    custom_id: agent.profile.name.clone(),
+                    model: model.to_string(),
+                    context: prompt.context_block(),
+                    prompt: task,
+                })
+                .await;
+        }
 
-        let response = self
+        let user_prompt = match prompt.context_block() {
+            Some(context) => format!("{context}\n\n{task}"),
+            None => task,
+        };
+        let (response, usage) = self
             .call::<Vec<Finding>>(CallArgs {
                 model,
-                system_prompt,
-                user_prompt: prompt,
+                system_prompt: REVIEW_SYSTEM_PROMPT,
+                user_prompt: &user_prompt,
                 label: "Review",
                 max_tokens: MAX_TOKENS,
                 agentic: agentic_cfg,
             })
             .await?;
 
-        parse_findings_response(&response)
+        Ok(ReviewOutput {
+            findings: parse_findings_response(&response)?,
+            usage,
+        })
     }
 
     async fn triage(
@@ -389,7 +464,7 @@ impl ReviewProvider for RigProvider {
         system_prompt: &str,
         user_prompt: &str,
     ) -> Result<Vec<TriageVerdict>, ProviderError> {
-        let response = self
+        let (response, _usage) = self
             .call::<Vec<TriageVerdict>>(CallArgs {
                 model: self.config.resolved_model(),
                 system_prompt,
@@ -406,6 +481,18 @@ impl ReviewProvider for RigProvider {
 
         parse_with_fallbacks::<Vec<TriageVerdict>>(&response)
     }
+
+    async fn submit_batch(&self, requests: &[BatchRequest]) -> Result<String, ProviderError> {
+        BatchClient::new(&self.config, MAX_TOKENS)?
+            .submit(requests)
+            .await
+    }
+
+    async fn collect_batch(&self, batch_id: &str) -> Result<Vec<BatchResult>, ProviderError> {
+        BatchClient::new(&self.config, MAX_TOKENS)?
+            .wait(batch_id)
+            .await
+    }
 }
 
 /// Enhance the system prompt for agentic mode.
@@ -443,13 +530,11 @@ fn build_agentic_system_prompt(
     );
 
     // Append custom tool guidance
-    let mut tool_number = 5;
-    for tool in custom_tools {
+    for (tool_number, tool) in (5..).zip(custom_tools) {
         prompt.push_str(&format!(
             "         {tool_number}. **Use `{}`** — {}\n",
             tool.name, tool.description
         ));
-        tool_number += 1;
     }
 
     prompt.push_str(
@@ -498,6 +583,41 @@ pub use super::response::{classify_error, is_retryable, retry_backoff};
 mod tests {
     use super::*;
 
+    #[test]
+    fn token_usage_counts_prompt_cache_as_input() {
+        // Anthropic: input excludes cache reads and writes, total includes them
+        let anthropic = rig::completion::Usage {
+            input_tokens: 200,
+            output_tokens: 50,
+            total_tokens: 1450,
+            cached_input_tokens: 1000,
+            cache_creation_input_tokens: 200,
+        };
+        let usage = token_usage(&anthropic);
+        assert_eq!(usage.input_tokens, 1400);
+        assert_eq!(usage.cached_input_tokens, 1000);
+
+        // OpenAI: input already includes cached tokens
+        let openai = rig::completion::Usage {
+            input_tokens: 1200,
+            output_tokens: 50,
+            total_tokens: 1250,
+            cached_input_tokens: 1000,
+            cache_creation_input_tokens: 0,
+        };
+        assert_eq!(token_usage(&openai).input_tokens, 1200);
+
+        // Providers that don't report a total
+        let bare = rig::completion::Usage {
+            input_tokens: 300,
+            output_tokens: 50,
+            total_tokens: 0,
+            cached_input_tokens: 0,
+            cache_creation_input_tokens: 0,
+        };
+        assert_eq!(token_usage(&bare).input_tokens, 300);
+    }
+
     #[test]
     fn new_provider_missing_api_key() {
         let config = ProviderConfig {
@@ -505,6 +625,7 @@ mod tests {
             model: Some("claude-sonnet-4-20250514".to_string()),
             base_url: None,
             api_key: None,
+            fallbacks: Vec::new(),
         };
         let result = RigProvider::new(config, PathBuf::from("/tmp"));
         match result {
@@ -520,6 +641,7 @@ mod tests {
             model: Some("claude-sonnet-4-20250514".to_string()),
             base_url: None,
             api_key: Some("sk-test-key".to_string()),
+            fallbacks: Vec::new(),
         };
         assert!(RigProvider::new(config, PathBuf::from("/tmp")).is_ok());
     }
@@ -531,6 +653,7 @@ mod tests {
             model: Some("llama3".to_string()),
             base_url: None,
             api_key: None,
+            fallbacks: Vec::new(),
         };
         assert!(
             RigProvider::new(config, PathBuf::from("/tmp")).is_ok(),
@@ -607,6 +730,7 @@ mod tests {
             model: Some("custom-model".to_string()),
             base_url: None,
             api_key: Some("key".to_string()),
+            fallbacks: Vec::new(),
         };
         let provider = RigProvider::new(config, PathBuf::from("/tmp")).unwrap();
         let result = provider.require_base_url();
@@ -624,6 +748,7 @@ mod tests {
             model: Some("custom-model".to_string()),
             base_url: Some("https://my-api.example.com".to_string()),
             api_key: Some("key".to_string()),
+            fallbacks: Vec::new(),
         };
         let provider = RigProvider::new(config, PathBuf::from("/tmp")).unwrap();
         assert_eq!(
@@ -11,6 +11,7 @@ pub mod entropy;
 pub mod rules;
 pub mod scanner;
 
+use crate::constants::SECRET_SCANNER_AGENT;
 use crate::models::finding::{Finding, Severity};
 
 /// Scan content for secrets, redact them, and produce findings.
@@ -28,7 +29,7 @@ pub fn scan_and_redact(
 
     // Process matches in reverse order to maintain string indices
     let mut sorted_matches = matches;
-    sorted_matches.sort_by(|a, b| b.start.cmp(&a.start));
+    sorted_matches.sort_by_key(|m| std::cmp::Reverse(m.start));
 
     for m in &sorted_matches {
         findings.push(Finding {
@@ -42,7 +43,9 @@ pub fn scan_and_redact(
                 m.rule_description.trim_end_matches('.')
             ),
             suggestion: Some("Remove the hardcoded secret and use environment variables or a secrets manager instead.".to_string()),
-            agent: "secret-scanner".to_string(),
+            agent: SECRET_SCANNER_AGENT.to_string(),
+            fix: None,
+            served_by: None,
         });
 
         // Redact the matched secret
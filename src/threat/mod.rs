@@ -39,6 +39,8 @@ pub fn match_to_finding(m: &ThreatMatch) -> Finding {
         ),
         suggestion: Some(suggestion_for_severity(m.severity)),
         agent: THREAT_SCANNER_AGENT.to_string(),
+        fix: None,
+        served_by: None,
     }
 }
 
@@ -285,6 +285,7 @@ pub fn load_rules_from_file(path: &Path) -> Result<Vec<ThreatRule>, ThreatRuleEr
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::glob;
 
     /// Compile the full ruleset once and share across tests.
     static DEFAULT_RULES: std::sync::LazyLock<Vec<ThreatRule>> =
@@ -323,6 +324,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn default_allowlist_paths_match_whole_segments() {
+        // (pattern, paths it allows, paths it must not allow). `**/` spans
+        // whole directories only, so `test` doesn't match inside `latest`.
+        let cases: &[(&str, &[&str], &[&str])] = &[
+            (
+                "**/test/**",
+                &["test/a.js", "src/test/fixtures/a.py"],
+                &["latest/a.js", "src/contest/a.py", "test.js"],
+            ),
+            (
+                "**/tests/**",
+                &["tests/a.rs", "crate/tests/a.rs"],
+                &["unittests/a.rs"],
+            ),
+            (
+                "**/spec/**",
+                &["spec/a.rb", "app/spec/a.rb"],
+                &["inspec/a.rb"],
+            ),
+            (
+                "**/test_*",
+                &["test_app.py", "pkg/test_app.py"],
+                &["pkg/latest_app.py", "test_dir/app.py"],
+            ),
+            ("**/*_test.*", &["a_test.go", "pkg/a_test.go"], &["a_test"]),
+            ("**/*_test.py", &["pkg/a_test.py"], &["pkg/a_test.pyc"]),
+            ("**/*.test.*", &["a.test.ts", "src/a.test.js"], &["a.test"]),
+            ("**/*.spec.*", &["src/a.spec.ts"], &["src/a.spec"]),
+            ("**/*.md", &["README.md", "docs/guide.md"], &["docs/md"]),
+            ("**/*.txt", &["notes.txt", "a/b.txt"], &["a/b.txt.js"]),
+            ("**/*.rst", &["docs/index.rst"], &["docs/index.rs"]),
+            ("**/*.po", &["locale/de.po"], &["locale/de.pot.js"]),
+            (
+                "**/*.pot",
+                &["locale/messages.pot"],
+                &["locale/messages.po"],
+            ),
+        ];
+
+        for rule in &*DEFAULT_RULES {
+            for pattern in &rule.allowlist_paths {
+                assert!(
+                    cases.iter().any(|(p, _, _)| p == pattern),
+                    "rule '{}' allowlist pattern '{pattern}' has no test case",
+                    rule.id
+                );
+            }
+        }
+        for (pattern, allowed, denied) in cases {
+            for path in *allowed {
+                assert!(
+                    glob::glob_match(pattern, path),
+                    "{pattern} should allow {path}"
+                );
+            }
+            for path in *denied {
+                assert!(
+                    !glob::glob_match(pattern, path),
+                    "{pattern} should not allow {path}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn load_custom_rules() {
         let toml_content = r#"
@@ -10,6 +10,7 @@ use std::sync::LazyLock;
 use indexmap::IndexMap;
 use regex::Regex;
 
+use crate::glob;
 use crate::models::diff::{DiffLineType, FileDiff};
 use crate::models::finding::Severity;
 use crate::security::entropy;
@@ -110,7 +111,7 @@ fn scan_line_scope(diffs: &[FileDiff<'_>], rules: &[ThreatRule]) -> Vec<ThreatMa
                         continue;
                     }
                     if !rule.allowlist_paths.is_empty()
-                        && path_matches_any_glob(path, &rule.allowlist_paths)
+                        && glob::matches_any(path, &rule.allowlist_paths)
                     {
                         continue;
                     }
@@ -281,9 +282,7 @@ fn scan_file_scope(
             if !language_matches(rule, &ext) {
                 continue;
             }
-            if !rule.allowlist_paths.is_empty()
-                && path_matches_any_glob(path, &rule.allowlist_paths)
-            {
+            if !rule.allowlist_paths.is_empty() && glob::matches_any(path, &rule.allowlist_paths) {
                 continue;
             }
             if !keyword_matches(&rule.keywords, content) {
@@ -356,45 +355,6 @@ fn keyword_matches(keywords: &[String], text: &str) -> bool {
     keywords.iter().any(|kw| lower.contains(kw.as_str()))
 }
 
-/// Check if a path matches any glob pattern.
-fn path_matches_any_glob(path: &str, patterns: &[String]) -> bool {
-    patterns.iter().any(|pat| glob_match(pat, path))
-}
-
-/// Minimal glob matching: supports `*` (single segment) and `**` (any depth).
-fn glob_match(pattern: &str, path: &str) -> bool {
-    // Convert glob to regex
-    let mut regex_str = String::from("^");
-    let mut chars = pattern.chars().peekable();
-    while let Some(c) = chars.next() {
-        match c {
-            '*' => {
-                if chars.peek() == Some(&'*') {
-                    chars.next(); // consume second *
-                    // Skip optional following /
-                    if chars.peek() == Some(&'/') {
-                        chars.next();
-                    }
-                    regex_str.push_str(".*");
-                } else {
-                    regex_str.push_str("[^/]*");
-                }
-            }
-            '?' => regex_str.push('.'),
-            '.' | '+' | '(' | ')' | '{' | '}' | '[' | ']' | '^' | '$' | '|' | '\\' => {
-                regex_str.push('\\');
-                regex_str.push(c);
-            }
-            _ => regex_str.push(c),
-        }
-    }
-    regex_str.push('$');
-
-    Regex::new(&regex_str)
-        .map(|re| re.is_match(path))
-        .unwrap_or(false)
-}
-
 /// Collect all new_line_no values from added lines in a diff.
 fn added_line_numbers(diff: &FileDiff<'_>) -> Vec<u32> {
     diff.hunks
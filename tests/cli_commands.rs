@@ -1,4 +1,4 @@
-//! Integration tests for the profiles, validate, and cache CLI commands.
+//! Integration tests for the profiles, validate, cache, fix, and history CLI commands.
 //!
 //! These tests exercise the library functions that back each command,
 //! using the public API from the nitpik crate.
@@ -6,6 +6,7 @@
 use nitpik::agents;
 use nitpik::agents::parser;
 use nitpik::cache::CacheEngine;
+use nitpik::context::files::ContentSource;
 use nitpik::models::finding::{Finding, Severity};
 
 // ---------------------------------------------------------------------------
@@ -154,6 +155,8 @@ fn sample_findings() -> Vec<Finding> {
         message: "A test finding".into(),
         suggestion: None,
         agent: "backend".into(),
+        fix: None,
+        served_by: None,
     }]
 }
 
@@ -217,6 +220,35 @@ async fn cache_clear_idempotent() {
     assert_eq!(stats.entries, 0);
 }
 
+#[tokio::test]
+async fn cache_export_import_hands_off_a_branch() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = dir.path().join("cache.tar.gz");
+
+    // First pipeline run reviews the branch and exports its cache.
+    let first = CacheEngine::new_with_dir(dir.path().join("run-1"));
+    first.put("key-1", &sample_findings()).await;
+    first
+        .put_sidecar("src/main.rs", "backend", "model", "key-1", "feature")
+        .await;
+    first.put("key-other", &sample_findings()).await;
+    first
+        .put_sidecar("src/main.rs", "backend", "model", "key-other", "main")
+        .await;
+    let exported = first.export_scope("feature", &archive).await.unwrap();
+    assert_eq!((exported.entries, exported.sidecars), (1, 1));
+
+    // The next run starts from an empty cache and imports the artifact.
+    let second = CacheEngine::new_with_dir(dir.path().join("run-2"));
+    second.import(&archive).await.unwrap();
+    assert!(second.get("key-1").await.is_some());
+    assert!(second.get("key-other").await.is_none());
+    let prior = second
+        .get_previous("src/main.rs", "backend", "model", "key-2", "feature")
+        .await;
+    assert!(prior.is_some());
+}
+
 #[test]
 fn cache_stats_human_size() {
     use nitpik::cache::store::CacheStats;
@@ -224,18 +256,21 @@ fn cache_stats_human_size() {
     let small = CacheStats {
         entries: 1,
         total_bytes: 42,
+        ..Default::default()
     };
     assert_eq!(small.human_size(), "42 B");
 
     let kib = CacheStats {
         entries: 5,
         total_bytes: 4096,
+        ..Default::default()
     };
     assert_eq!(kib.human_size(), "4.0 KiB");
 
     let mib = CacheStats {
         entries: 100,
         total_bytes: 3 * 1024 * 1024,
+        ..Default::default()
     };
     assert_eq!(mib.human_size(), "3.0 MiB");
 }
@@ -308,9 +343,16 @@ async fn no_project_docs_skips_all_docs() {
 
     let config = nitpik::config::Config::default();
     let diffs = vec![];
-    let ctx =
-        nitpik::context::build_baseline_context(dir.path(), &diffs, &config, true, &[], Vec::new())
-            .await;
+    let ctx = nitpik::context::build_baseline_context(
+        dir.path(),
+        &diffs,
+        &ContentSource::WorkingTree,
+        &config,
+        true,
+        &[],
+        Vec::new(),
+    )
+    .await;
     assert!(
         ctx.project_docs.is_empty(),
         "expected no docs with --no-project-docs"
@@ -328,6 +370,7 @@ async fn exclude_doc_filters_by_name() {
     let ctx = nitpik::context::build_baseline_context(
         dir.path(),
         &[],
+        &ContentSource::WorkingTree,
         &config,
         false,
         &exclude,
@@ -350,6 +393,7 @@ async fn exclude_doc_multiple_names() {
     let ctx = nitpik::context::build_baseline_context(
         dir.path(),
         &[],
+        &ContentSource::WorkingTree,
         &config,
         false,
         &exclude,
@@ -359,3 +403,88 @@ async fn exclude_doc_multiple_names() {
     assert_eq!(ctx.project_docs.len(), 1);
     assert!(ctx.project_docs.contains_key("CONVENTIONS.md"));
 }
+
+// ---------------------------------------------------------------------------
+// fix
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn fix_applies_fixes_from_json_report() {
+    use nitpik::fix::{self, FixStatus};
+    use nitpik::models::finding::Fix;
+    use nitpik::output::OutputFormatter;
+    use nitpik::output::json::JsonFormatter;
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("app.py"), "x = 1\ny = eval(s)\nz = 3\n").unwrap();
+
+    let mut with_fix = sample_findings().remove(0);
+    with_fix.file = "app.py".into();
+    with_fix.fix = Some(Fix {
+        start_line: 2,
+        end_line: 2,
+        replacement: "y = int(s)".into(),
+        original: Some("y = eval(s)".into()),
+    });
+    let report = dir.path().join("report.json");
+    std::fs::write(
+        &report,
+        JsonFormatter.format(&[sample_findings().remove(0), with_fix]),
+    )
+    .unwrap();
+
+    let findings = fix::load_report(&report).await.unwrap();
+    let selected = fix::select(&findings, &[]).unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].0, 2);
+
+    let plans = fix::plan(dir.path(), &selected).await;
+    assert_eq!(plans[0].outcomes[0].status, FixStatus::Ready);
+    assert!(plans[0].diff.contains("-y = eval(s)\n+y = int(s)\n"));
+
+    // Dry run leaves the file untouched until written
+    let before = std::fs::read_to_string(dir.path().join("app.py")).unwrap();
+    assert!(before.contains("eval"));
+    fix::write(dir.path(), &plans).await.unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("app.py")).unwrap(),
+        "x = 1\ny = int(s)\nz = 3\n"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// history
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn history_records_and_compares_runs() {
+    use indexmap::IndexMap;
+    use nitpik::history::{self, HistoryStore, RecordedFinding, RunRecord};
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = HistoryStore::new(dir.path().to_path_buf());
+    let contents = IndexMap::from([("test.rs".to_string(), "let x = 1;\n".repeat(20))]);
+    let record = |timestamp: u64, findings: &[Finding]| RunRecord {
+        timestamp,
+        branch: "main".into(),
+        agents: vec!["backend".into()],
+        files: vec!["test.rs".into()],
+        findings: RecordedFinding::from_findings(findings, &contents),
+        ..RunRecord::now()
+    };
+
+    let first = record(100, &sample_findings());
+    let second = record(200, &[]);
+    store.record(&second).await.unwrap();
+    store.record(&first).await.unwrap();
+
+    let runs = store.runs().await.unwrap();
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].id, first.id);
+    assert!(history::render_list(&runs).contains("main"));
+
+    let diff = history::diff_runs(&runs[0], &runs[1]);
+    assert_eq!(diff.fixed.len(), 1);
+    assert!(diff.new.is_empty() && diff.persisting.is_empty());
+    assert!(history::render_diff(&runs[0], &runs[1], &diff).contains("Fixed (1):"));
+}
@@ -28,6 +28,7 @@ use nitpik::agents;
 use nitpik::cache::CacheEngine;
 use nitpik::config::Config;
 use nitpik::context;
+use nitpik::context::files::ContentSource;
 use nitpik::diff;
 use nitpik::models::diff::FileDiff;
 use nitpik::models::finding::{Finding, Severity};
@@ -79,6 +80,7 @@ async fn get_diffs_owned(input: &InputMode, repo_root: &Path) -> Vec<FileDiff<'s
             })
             .collect(),
         diff::DiffSource::Scanned(diffs) => diffs,
+        diff::DiffSource::PullRequest(_) => unreachable!("tests don't fetch pull requests"),
     }
 }
 
@@ -240,8 +242,16 @@ async fn run_review(repo_path: &Path, profile_names: &[&str], config: &Config) -
     );
 
     // Build baseline context
-    let baseline =
-        context::build_baseline_context(repo_path, &diffs, config, false, &[], Vec::new()).await;
+    let baseline = context::build_baseline_context(
+        repo_path,
+        &diffs,
+        &ContentSource::WorkingTree,
+        config,
+        false,
+        &[],
+        Vec::new(),
+    )
+    .await;
 
     let review_context = ReviewContext {
         diffs,
@@ -570,8 +580,16 @@ async fn e2e_custom_profile() {
     let diffs = get_diffs_owned(&input, &repo).await;
     assert!(!diffs.is_empty());
 
-    let baseline =
-        context::build_baseline_context(&repo, &diffs, &config, false, &[], Vec::new()).await;
+    let baseline = context::build_baseline_context(
+        &repo,
+        &diffs,
+        &ContentSource::WorkingTree,
+        &config,
+        false,
+        &[],
+        Vec::new(),
+    )
+    .await;
     let review_context = ReviewContext {
         diffs,
         baseline,
@@ -689,8 +707,16 @@ async fn e2e_custom_tool_agentic() {
     let diffs = get_diffs_owned(&input, &repo).await;
     assert!(!diffs.is_empty(), "changeset should produce diffs");
 
-    let baseline =
-        context::build_baseline_context(&repo, &diffs, &config, false, &[], Vec::new()).await;
+    let baseline = context::build_baseline_context(
+        &repo,
+        &diffs,
+        &ContentSource::WorkingTree,
+        &config,
+        false,
+        &[],
+        Vec::new(),
+    )
+    .await;
     let review_context = ReviewContext {
         diffs,
         baseline,
@@ -869,8 +895,16 @@ async fn e2e_builtin_tool_agentic() {
         .await
         .expect("failed to resolve profiles");
 
-    let baseline =
-        context::build_baseline_context(&repo, &diffs, &config, false, &[], Vec::new()).await;
+    let baseline = context::build_baseline_context(
+        &repo,
+        &diffs,
+        &ContentSource::WorkingTree,
+        &config,
+        false,
+        &[],
+        Vec::new(),
+    )
+    .await;
     let review_context = ReviewContext {
         diffs,
         baseline,
@@ -1077,8 +1111,16 @@ async fn e2e_cache_prior_findings() {
         .await
         .expect("failed to resolve profiles");
 
-    let baseline_v1 =
-        context::build_baseline_context(&repo, &diffs_v1, &config, false, &[], Vec::new()).await;
+    let baseline_v1 = context::build_baseline_context(
+        &repo,
+        &diffs_v1,
+        &ContentSource::WorkingTree,
+        &config,
+        false,
+        &[],
+        Vec::new(),
+    )
+    .await;
     let review_context_v1 = ReviewContext {
         diffs: diffs_v1,
         baseline: baseline_v1,
@@ -1126,8 +1168,16 @@ async fn e2e_cache_prior_findings() {
     let diffs_v2 = get_diffs_owned(&input, &repo).await;
     assert!(!diffs_v2.is_empty(), "v2 should produce diffs");
 
-    let baseline_v2 =
-        context::build_baseline_context(&repo, &diffs_v2, &config, false, &[], Vec::new()).await;
+    let baseline_v2 = context::build_baseline_context(
+        &repo,
+        &diffs_v2,
+        &ContentSource::WorkingTree,
+        &config,
+        false,
+        &[],
+        Vec::new(),
+    )
+    .await;
     let review_context_v2 = ReviewContext {
         diffs: diffs_v2,
         baseline: baseline_v2,
@@ -1251,8 +1301,16 @@ async fn e2e_agentic_mode() {
         .await
         .expect("failed to resolve profiles");
 
-    let baseline =
-        context::build_baseline_context(&repo, &diffs, &config, false, &[], Vec::new()).await;
+    let baseline = context::build_baseline_context(
+        &repo,
+        &diffs,
+        &ContentSource::WorkingTree,
+        &config,
+        false,
+        &[],
+        Vec::new(),
+    )
+    .await;
     let review_context = ReviewContext {
         diffs,
         baseline,
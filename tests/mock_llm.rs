@@ -18,7 +18,7 @@ use nitpik::models::diff::{DiffLine, DiffLineType, FileDiff, Hunk};
 use nitpik::models::finding::{Finding, Severity};
 use nitpik::orchestrator::ReviewOrchestrator;
 use nitpik::progress::ProgressTracker;
-use nitpik::providers::{ProviderError, ReviewProvider, TriageVerdict};
+use nitpik::providers::{ProviderError, ReviewOutput, ReviewPrompt, ReviewProvider, TriageVerdict};
 
 /// A mock review provider that returns canned findings.
 struct MockProvider {
@@ -44,12 +44,12 @@ impl ReviewProvider for MockProvider {
     async fn review(
         &self,
         _agent: &AgentDefinition,
-        _prompt: &str,
+        _prompt: &ReviewPrompt,
         _agentic: bool,
         _max_turns: usize,
         _max_tool_calls: usize,
-    ) -> Result<Vec<Finding>, ProviderError> {
-        Ok(self.canned_findings.clone())
+    ) -> Result<ReviewOutput, ProviderError> {
+        Ok(self.canned_findings.clone().into())
     }
 
     async fn triage(
@@ -101,6 +101,7 @@ fn test_agent(name: &str) -> AgentDefinition {
             name: name.to_string(),
             description: format!("Test agent: {name}"),
             model: None,
+            provider: None,
             tags: vec![],
             tools: vec![],
             agentic_instructions: None,
@@ -123,6 +124,8 @@ fn test_findings(file: &str, agent: &str) -> Vec<Finding> {
             message: "The variable `x` is never used.".to_string(),
             suggestion: Some("Remove the variable or prefix with underscore.".to_string()),
             agent: agent.to_string(),
+            fix: None,
+            served_by: None,
         },
         Finding {
             file: file.to_string(),
@@ -133,6 +136,8 @@ fn test_findings(file: &str, agent: &str) -> Vec<Finding> {
             message: "This function lacks a doc comment.".to_string(),
             suggestion: None,
             agent: agent.to_string(),
+            fix: None,
+            served_by: None,
         },
     ]
 }
@@ -299,6 +304,8 @@ async fn orchestrator_handles_multiple_agents_and_files() {
         message: "Error found by agent A.".to_string(),
         suggestion: None,
         agent: "agent-a".to_string(),
+        fix: None,
+        served_by: None,
     }];
     let provider = Arc::new(MockProvider::new(findings_a));
     let config = Config::default();
@@ -356,11 +363,11 @@ impl ReviewProvider for FailingProvider {
     async fn review(
         &self,
         _agent: &AgentDefinition,
-        _prompt: &str,
+        _prompt: &ReviewPrompt,
         _agentic: bool,
         _max_turns: usize,
         _max_tool_calls: usize,
-    ) -> Result<Vec<Finding>, ProviderError> {
+    ) -> Result<ReviewOutput, ProviderError> {
         Err(ProviderError::ApiError("mock API failure".to_string()))
     }
 
@@ -411,6 +418,354 @@ async fn orchestrator_handles_provider_errors_gracefully() {
     assert!(result.failed_tasks > 0, "should report failed tasks");
 }
 
+#[tokio::test]
+async fn orchestrator_applies_inline_suppressions() {
+    let repo = tempfile::tempdir().unwrap();
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    std::fs::write(
+        repo.path().join("src/main.rs"),
+        "// existing\nlet x = 42; // nitpik-ignore[unused]: kept for the FFI layout\n",
+    )
+    .unwrap();
+
+    let findings = test_findings("src/main.rs", "test-agent");
+    let provider = Arc::new(MockProvider::new(findings));
+    let config = Config::default();
+    let progress = Arc::new(ProgressTracker::new(
+        &["src/main.rs".to_string()],
+        &["test-agent".to_string()],
+        false,
+    ));
+    let orchestrator = ReviewOrchestrator::new(
+        provider,
+        &config,
+        CacheEngine::new(false),
+        progress,
+        false,
+        None,
+        String::new(),
+    );
+
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 42;")],
+        baseline: BaselineContext::default(),
+        repo_root: repo.path().display().to_string(),
+        is_path_scan: false,
+    };
+
+    let result = orchestrator
+        .run(&context, &[test_agent("test-agent")], 4, false, 10, 50)
+        .await
+        .expect("orchestrator should succeed");
+
+    assert_eq!(result.suppressed, 1);
+    assert_eq!(result.findings.len(), 1);
+    assert_eq!(result.findings[0].title, "Consider documentation");
+    assert!(result.missing_suppression_reasons.is_empty());
+}
+
+#[tokio::test]
+async fn commit_review_checks_fixes_and_suppressions_against_the_commit() {
+    use nitpik::context::files::ContentSource;
+    use nitpik::diff::{DiffSource, get_diff_source, parser::parse_unified_diff};
+    use nitpik::models::InputMode;
+    use nitpik::models::finding::Fix;
+
+    let repo = tempfile::tempdir().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(repo.path())
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    };
+    git(&["init", "-q"]);
+    git(&["config", "user.email", "test@nitpik.dev"]);
+    git(&["config", "user.name", "Nitpik Test"]);
+    std::fs::create_dir(repo.path().join("src")).unwrap();
+    std::fs::write(repo.path().join("src/main.rs"), "fn main() {\n}\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-qm", "base"]);
+    std::fs::write(
+        repo.path().join("src/main.rs"),
+        "fn main() {\n    let x = 42; // nitpik-ignore[unused]: kept for the FFI layout\n    let y = a.unwrap();\n}\n",
+    )
+    .unwrap();
+    git(&["commit", "-qam", "reviewed"]);
+    // The working tree has since moved on
+    std::fs::write(
+        repo.path().join("src/main.rs"),
+        "// rewritten\nfn other() {}\nfn main() {}\n",
+    )
+    .unwrap();
+
+    let input = InputMode::Commit("HEAD".to_string());
+    let Ok(DiffSource::Raw(raw)) = get_diff_source(&input, repo.path()).await else {
+        panic!("expected a raw diff for the commit");
+    };
+    let finding = |line: u32, title: &str, fix: Option<Fix>| Finding {
+        file: "src/main.rs".to_string(),
+        line,
+        end_line: None,
+        severity: Severity::Warning,
+        title: title.to_string(),
+        message: "Details".to_string(),
+        suggestion: None,
+        agent: "test-agent".to_string(),
+        fix,
+        served_by: None,
+    };
+    let fix = Fix {
+        start_line: 3,
+        end_line: 3,
+        replacement: "    let y = a?;".to_string(),
+        original: None,
+    };
+    let provider = Arc::new(MockProvider::new(vec![
+        finding(2, "Unused variable", None),
+        finding(3, "Unchecked unwrap", Some(fix)),
+    ]));
+    let config = Config::default();
+    let progress = Arc::new(ProgressTracker::new(
+        &["src/main.rs".to_string()],
+        &["test-agent".to_string()],
+        false,
+    ));
+    let orchestrator = ReviewOrchestrator::new(
+        provider,
+        &config,
+        CacheEngine::new(false),
+        progress,
+        false,
+        None,
+        String::new(),
+    )
+    .with_content_source(ContentSource::for_input(&input));
+
+    let context = ReviewContext {
+        diffs: parse_unified_diff(&raw),
+        baseline: BaselineContext::default(),
+        repo_root: repo.path().display().to_string(),
+        is_path_scan: false,
+    };
+    let result = orchestrator
+        .run(&context, &[test_agent("test-agent")], 4, false, 10, 50)
+        .await
+        .expect("orchestrator should succeed");
+
+    assert_eq!(result.suppressed, 1);
+    assert_eq!(result.findings.len(), 1);
+    let fix = result.findings[0].fix.as_ref().expect("fix should be kept");
+    assert_eq!(fix.original.as_deref(), Some("    let y = a.unwrap();"));
+}
+
+#[tokio::test]
+async fn orchestrator_applies_path_overrides() {
+    use nitpik::config::PathOverride;
+    use std::sync::Mutex;
+
+    /// Records which agent reviewed which file.
+    struct AgentCapture {
+        calls: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl ReviewProvider for AgentCapture {
+        async fn review(
+            &self,
+            agent: &AgentDefinition,
+            prompt: &ReviewPrompt,
+            _agentic: bool,
+            _max_turns: usize,
+            _max_tool_calls: usize,
+        ) -> Result<ReviewOutput, ProviderError> {
+            let file = ["web/app.ts", "src/main.rs", "vendor/lib.rs"]
+                .into_iter()
+                .find(|f| prompt.body.contains(&format!("## Diff for: {f}")))
+                .unwrap_or("unknown");
+            self.calls
+                .lock()
+                .unwrap()
+                .push((file.to_string(), agent.profile.name.clone()));
+            Ok(Vec::new().into())
+        }
+
+        async fn triage(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+        ) -> Result<Vec<TriageVerdict>, ProviderError> {
+            Ok(Vec::new())
+        }
+    }
+
+    let provider = Arc::new(AgentCapture {
+        calls: Mutex::new(Vec::new()),
+    });
+    let mut config = Config::default();
+    config.review.overrides = vec![
+        PathOverride {
+            paths: vec!["web/**".to_string()],
+            profiles: vec!["frontend".to_string()],
+            ..Default::default()
+        },
+        PathOverride {
+            paths: vec!["vendor/**".to_string()],
+            ignore: true,
+            ..Default::default()
+        },
+    ];
+    let files = ["web/app.ts", "src/main.rs", "vendor/lib.rs"].map(String::from);
+    let progress = Arc::new(ProgressTracker::new(
+        &files,
+        &["general".to_string(), "frontend".to_string()],
+        false,
+    ));
+    let orchestrator = ReviewOrchestrator::new(
+        Arc::clone(&provider) as Arc<dyn ReviewProvider>,
+        &config,
+        CacheEngine::new(false),
+        progress,
+        false,
+        None,
+        String::new(),
+    )
+    .with_path_agents(vec![test_agent("frontend")]);
+
+    let context = ReviewContext {
+        diffs: files.iter().map(|f| test_diff(f, "let x = 1;")).collect(),
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+
+    orchestrator
+        .run(&context, &[test_agent("general")], 4, false, 10, 50)
+        .await
+        .expect("orchestrator should succeed");
+
+    let mut calls = provider.calls.lock().unwrap().clone();
+    calls.sort();
+    assert_eq!(
+        calls,
+        vec![
+            ("src/main.rs".to_string(), "general".to_string()),
+            ("web/app.ts".to_string(), "frontend".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn orchestrator_enforces_budget() {
+    use nitpik::config::loader::ModelPrice;
+    use nitpik::cost::{Budget, BudgetAction};
+    use nitpik::orchestrator::OrchestratorError;
+
+    // Every task costs exactly $1: input is free, output is estimated
+    // at a fixed token count.
+    let mut config = Config::default();
+    config.pricing.insert(
+        config.provider.resolved_model().to_string(),
+        ModelPrice {
+            input: 0.0,
+            output: 1_000_000.0 / nitpik::constants::ESTIMATED_OUTPUT_TOKENS as f64,
+            cached_input: None,
+        },
+    );
+    let agents = [test_agent("security"), test_agent("general")];
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 1;")],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+    let orchestrator = |action| {
+        let progress = Arc::new(ProgressTracker::new(
+            &["src/main.rs".to_string()],
+            &["security".to_string(), "general".to_string()],
+            false,
+        ));
+        ReviewOrchestrator::new(
+            Arc::new(MockProvider::new(test_findings("src/main.rs", "any"))),
+            &config,
+            CacheEngine::new(false),
+            progress,
+            false,
+            None,
+            String::new(),
+        )
+        .with_budget(Budget {
+            max_cost: 1.5,
+            action,
+        })
+    };
+
+    let err = orchestrator(BudgetAction::Abort)
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect_err("over-budget run should abort");
+    assert!(matches!(err, OrchestratorError::OverBudget { .. }));
+
+    let result = orchestrator(BudgetAction::Drop)
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("drop should run the tasks that fit");
+    assert_eq!(result.dropped_tasks, 1);
+    assert_eq!(result.findings.len(), 2, "only one agent should have run");
+    assert_eq!(
+        result.partly_reviewed,
+        vec!["src/main.rs".to_string()],
+        "a file that kept one of its tasks is partly reviewed, not skipped"
+    );
+}
+
+#[tokio::test]
+async fn budget_rejects_unpriced_models() {
+    use nitpik::cost::{Budget, BudgetAction};
+    use nitpik::orchestrator::OrchestratorError;
+
+    let mut config = Config::default();
+    config.provider.model = Some("llama3".to_string());
+    let agents = [test_agent("security")];
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 1;")],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+
+    for action in [BudgetAction::Abort, BudgetAction::Drop] {
+        let progress = Arc::new(ProgressTracker::new(
+            &["src/main.rs".to_string()],
+            &["security".to_string()],
+            false,
+        ));
+        let err = ReviewOrchestrator::new(
+            Arc::new(MockProvider::new(test_findings("src/main.rs", "any"))),
+            &config,
+            CacheEngine::new(false),
+            progress,
+            false,
+            None,
+            String::new(),
+        )
+        .with_budget(Budget {
+            max_cost: 100.0,
+            action,
+        })
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect_err("a budget can't cover a model with no price");
+        assert!(
+            matches!(&err, OrchestratorError::UnpricedModels(models) if models == &["llama3"]),
+            "unexpected error: {err}"
+        );
+        assert!(err.to_string().contains("llama3"));
+    }
+}
+
 #[tokio::test]
 async fn cache_prevents_duplicate_calls() {
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -425,13 +780,13 @@ async fn cache_prevents_duplicate_calls() {
         async fn review(
             &self,
             _agent: &AgentDefinition,
-            _prompt: &str,
+            _prompt: &ReviewPrompt,
             _agentic: bool,
             _max_turns: usize,
             _max_tool_calls: usize,
-        ) -> Result<Vec<Finding>, ProviderError> {
+        ) -> Result<ReviewOutput, ProviderError> {
             self.call_count.fetch_add(1, Ordering::SeqCst);
-            Ok(self.findings.clone())
+            Ok(self.findings.clone().into())
         }
 
         async fn triage(
@@ -494,6 +849,643 @@ async fn cache_prevents_duplicate_calls() {
     assert_eq!(provider.call_count.load(Ordering::SeqCst), 1);
 }
 
+#[tokio::test]
+async fn shared_cache_serves_other_runners() {
+    use nitpik::cache::store::FileStore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingProvider {
+        call_count: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ReviewProvider for CountingProvider {
+        async fn review(
+            &self,
+            _agent: &AgentDefinition,
+            _prompt: &ReviewPrompt,
+            _agentic: bool,
+            _max_turns: usize,
+            _max_tool_calls: usize,
+        ) -> Result<ReviewOutput, ProviderError> {
+            self.call_count.fetch_add(1, Ordering::SeqCst);
+            Ok(test_findings("src/main.rs", "cache-agent").into())
+        }
+
+        async fn triage(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+        ) -> Result<Vec<TriageVerdict>, ProviderError> {
+            Ok(Vec::new())
+        }
+    }
+
+    let provider = Arc::new(CountingProvider {
+        call_count: AtomicUsize::new(0),
+    });
+    let config = Config::default();
+    let dir = tempfile::tempdir().expect("failed to create temp cache dir");
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 1;")],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+    let agents = vec![test_agent("cache-agent")];
+
+    // Each runner has its own local cache; both share one backend.
+    for runner in ["runner-a", "runner-b"] {
+        let cache = CacheEngine::new_with_dir(dir.path().join(runner))
+            .with_shared(Box::new(FileStore::new_with_dir(dir.path().join("shared"))));
+        let progress = Arc::new(ProgressTracker::new(
+            &["src/main.rs".to_string()],
+            &["cache-agent".to_string()],
+            false,
+        ));
+        let orchestrator = ReviewOrchestrator::new(
+            Arc::clone(&provider) as Arc<dyn ReviewProvider>,
+            &config,
+            cache,
+            progress,
+            false,
+            None,
+            String::new(),
+        );
+        let result = orchestrator
+            .run(&context, &agents, 4, false, 10, 50)
+            .await
+            .expect("run should succeed");
+        assert_eq!(result.findings.len(), 2);
+    }
+
+    // Runner B was served from the shared cache.
+    assert_eq!(provider.call_count.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn usage_reported_per_agent_and_file() {
+    use nitpik::cost::TokenUsage;
+
+    /// Reports fixed token usage for every call.
+    struct MeteredProvider;
+
+    #[async_trait]
+    impl ReviewProvider for MeteredProvider {
+        async fn review(
+            &self,
+            _agent: &AgentDefinition,
+            _prompt: &ReviewPrompt,
+            _agentic: bool,
+            _max_turns: usize,
+            _max_tool_calls: usize,
+        ) -> Result<ReviewOutput, ProviderError> {
+            Ok(ReviewOutput {
+                findings: Vec::new(),
+                usage: TokenUsage {
+                    input_tokens: 1_000,
+                    output_tokens: 200,
+                    cached_input_tokens: 400,
+                    reasoning_tokens: 0,
+                },
+            })
+        }
+
+        async fn triage(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+        ) -> Result<Vec<TriageVerdict>, ProviderError> {
+            Ok(Vec::new())
+        }
+    }
+
+    let cache_dir = tempfile::tempdir().expect("failed to create temp cache dir");
+    let progress = Arc::new(ProgressTracker::new(
+        &["src/main.rs".to_string(), "src/lib.rs".to_string()],
+        &["security".to_string(), "general".to_string()],
+        false,
+    ));
+    let orchestrator = ReviewOrchestrator::new(
+        Arc::new(MeteredProvider),
+        &Config::default(),
+        CacheEngine::new_with_dir(cache_dir.path().to_path_buf()),
+        progress,
+        false,
+        None,
+        String::new(),
+    );
+    let context = ReviewContext {
+        diffs: vec![
+            test_diff("src/main.rs", "let x = 1;"),
+            test_diff("src/lib.rs", "let y = 2;"),
+        ],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+    let agents = [test_agent("security"), test_agent("general")];
+
+    let first = orchestrator
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("first run should succeed")
+        .usage;
+    assert_eq!(first.total.requests, 4);
+    assert_eq!(first.total.tokens.input_tokens, 4_000);
+    assert_eq!(first.total.tokens.output_tokens, 800);
+    assert_eq!(first.total.tokens.cached_input_tokens, 1_600);
+    assert!(first.total.cost.unwrap() > 0.0);
+    assert_eq!(first.by_agent["security"].requests, 2);
+    assert_eq!(first.by_file["src/lib.rs"].tokens.input_tokens, 2_000);
+
+    // Every task is cached now: no tokens, no cost, some savings
+    let second = orchestrator
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("second run should succeed")
+        .usage;
+    assert_eq!(second.total.requests, 0);
+    assert_eq!(second.total.cache_hits, 4);
+    assert_eq!(second.total.tokens, TokenUsage::default());
+    assert_eq!(second.total.cost, Some(0.0));
+    assert!(second.total.saved_cost.unwrap() > 0.0);
+}
+
+/// Submits a batch, collects it from a fresh orchestrator (as
+/// `--resume-batch` does), and checks that results reach the output
+/// pipeline through the cache while failed requests are reviewed live.
+#[tokio::test]
+async fn batch_results_flow_through_cache() {
+    use nitpik::cost::TokenUsage;
+    use nitpik::providers::{BatchRequest, BatchResult};
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Answers batches from memory; the `security` request fails.
+    struct BatchProvider {
+        submitted: Mutex<Vec<BatchRequest>>,
+        live_calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ReviewProvider for BatchProvider {
+        async fn review(
+            &self,
+            agent: &AgentDefinition,
+            _prompt: &ReviewPrompt,
+            _agentic: bool,
+            _max_turns: usize,
+            _max_tool_calls: usize,
+        ) -> Result<ReviewOutput, ProviderError> {
+            self.live_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(test_findings("src/main.rs", &agent.profile.name).into())
+        }
+
+        async fn triage(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+        ) -> Result<Vec<TriageVerdict>, ProviderError> {
+            Ok(Vec::new())
+        }
+
+        async fn submit_batch(&self, requests: &[BatchRequest]) -> Result<String, ProviderError> {
+            *self.submitted.lock().unwrap() = requests.to_vec();
+            Ok("batch_42".to_string())
+        }
+
+        async fn collect_batch(&self, batch_id: &str) -> Result<Vec<BatchResult>, ProviderError> {
+            assert_eq!(batch_id, "batch_42");
+            let submitted = self.submitted.lock().unwrap().clone();
+            Ok(submitted
+                .into_iter()
+                .map(|r| BatchResult {
+                    output: if r.prompt.contains("You are a security reviewer.") {
+                        Err("overloaded".to_string())
+                    } else {
+                        Ok(ReviewOutput {
+                            findings: test_findings("src/main.rs", "general"),
+                            usage: TokenUsage {
+                                input_tokens: 500,
+                                output_tokens: 50,
+                                ..TokenUsage::default()
+                            },
+                        })
+                    },
+                    custom_id: r.custom_id,
+                })
+                .collect())
+        }
+    }
+
+    let provider = Arc::new(BatchProvider {
+        submitted: Mutex::new(Vec::new()),
+        live_calls: AtomicUsize::new(0),
+    });
+    let cache_dir = tempfile::tempdir().expect("failed to create temp cache dir");
+    let orchestrator = || {
+        ReviewOrchestrator::new(
+            Arc::clone(&provider) as Arc<dyn ReviewProvider>,
+            &Config::default(),
+            CacheEngine::new_with_dir(cache_dir.path().to_path_buf()),
+            Arc::new(ProgressTracker::new(&[], &[], false)),
+            false,
+            None,
+            String::new(),
+        )
+    };
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 1;")],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+    let mut security = test_agent("security");
+    security.system_prompt = "You are a security reviewer.".to_string();
+    let agents = [security, test_agent("general")];
+
+    let id = orchestrator()
+        .submit_batch(&context, &agents)
+        .await
+        .expect("submit should succeed")
+        .expect("uncached tasks should be submitted");
+    assert_eq!(id, "batch_42");
+    assert_eq!(provider.submitted.lock().unwrap().len(), 2);
+
+    // A fresh orchestrator finds the batch through its on-disk record
+    let resumed = orchestrator();
+    let collection = resumed.collect_batch(&id).await.expect("collect");
+    assert_eq!(collection.collected, 1);
+    assert_eq!(collection.failed, 1);
+    assert_eq!(collection.usage.input_tokens, 500);
+    assert!(
+        resumed.collect_batch(&id).await.is_err(),
+        "record is consumed"
+    );
+
+    let result = resumed
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("run should succeed");
+    // The batch result is reported with the batch's tokens, not as a cache hit
+    assert_eq!(result.usage.total.cache_hits, 0);
+    assert_eq!(result.usage.total.requests, 2);
+    assert_eq!(result.usage.by_agent["general"].tokens.input_tokens, 500);
+    assert_eq!(
+        provider.live_calls.load(Ordering::SeqCst),
+        1,
+        "only the failed batch request is reviewed live"
+    );
+    assert_eq!(result.findings.len(), 2);
+
+    // Everything is cached now, so there is nothing left to batch
+    assert_eq!(
+        orchestrator()
+            .submit_batch(&context, &agents)
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+/// A resumed batch that completed every request is served entirely from
+/// the cache: the non-agentic run after `--resume-batch` makes no calls.
+#[tokio::test]
+async fn resumed_batch_is_served_from_cache() {
+    use nitpik::providers::{BatchRequest, BatchResult};
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct BatchProvider {
+        submitted: Mutex<Vec<BatchRequest>>,
+        live_calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ReviewProvider for BatchProvider {
+        async fn review(
+            &self,
+            agent: &AgentDefinition,
+            _prompt: &ReviewPrompt,
+            _agentic: bool,
+            _max_turns: usize,
+            _max_tool_calls: usize,
+        ) -> Result<ReviewOutput, ProviderError> {
+            self.live_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(test_findings("src/main.rs", &agent.profile.name).into())
+        }
+
+        async fn triage(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+        ) -> Result<Vec<TriageVerdict>, ProviderError> {
+            Ok(Vec::new())
+        }
+
+        async fn submit_batch(&self, requests: &[BatchRequest]) -> Result<String, ProviderError> {
+            *self.submitted.lock().unwrap() = requests.to_vec();
+            Ok("batch_7".to_string())
+        }
+
+        async fn collect_batch(&self, _batch_id: &str) -> Result<Vec<BatchResult>, ProviderError> {
+            let submitted = self.submitted.lock().unwrap().clone();
+            Ok(submitted
+                .into_iter()
+                .map(|r| BatchResult {
+                    output: Ok(test_findings("src/main.rs", "general").into()),
+                    custom_id: r.custom_id,
+                })
+                .collect())
+        }
+    }
+
+    let provider = Arc::new(BatchProvider {
+        submitted: Mutex::new(Vec::new()),
+        live_calls: AtomicUsize::new(0),
+    });
+    let cache_dir = tempfile::tempdir().expect("failed to create temp cache dir");
+    let mut config = Config::default();
+    config.review.agentic.enabled = true;
+    let orchestrator = || {
+        ReviewOrchestrator::new(
+            Arc::clone(&provider) as Arc<dyn ReviewProvider>,
+            &config,
+            CacheEngine::new_with_dir(cache_dir.path().to_path_buf()),
+            Arc::new(ProgressTracker::new(&[], &[], false)),
+            false,
+            None,
+            String::new(),
+        )
+    };
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 1;")],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+    let agents = [test_agent("security"), test_agent("general")];
+
+    let id = orchestrator()
+        .submit_batch(&context, &agents)
+        .await
+        .unwrap()
+        .expect("uncached tasks should be submitted");
+    let resumed = orchestrator();
+    assert_eq!(resumed.collect_batch(&id).await.unwrap().collected, 2);
+
+    // Batch runs review without tools even when agentic mode is configured
+    let result = resumed
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("run should succeed");
+    assert_eq!(result.usage.total.cache_hits, 0);
+    assert_eq!(result.usage.total.requests, 2);
+    assert_eq!(provider.live_calls.load(Ordering::SeqCst), 0);
+}
+
+/// Primary provider keeps reporting overload: the task moves to the
+/// fallback after `FALLBACK_AFTER_OVERLOADS` errors, findings record
+/// the fallback, and the result is cached under the fallback's model.
+#[tokio::test(start_paused = true)]
+async fn overloaded_provider_falls_back() {
+    use nitpik::models::ProviderName;
+    use nitpik::models::finding::ServedBy;
+    use nitpik::orchestrator::ChainedProvider;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct OverloadedProvider {
+        calls: AtomicUsize,
+        healthy: bool,
+    }
+
+    #[async_trait]
+    impl ReviewProvider for OverloadedProvider {
+        async fn review(
+            &self,
+            agent: &AgentDefinition,
+            _prompt: &ReviewPrompt,
+            _agentic: bool,
+            _max_turns: usize,
+            _max_tool_calls: usize,
+        ) -> Result<ReviewOutput, ProviderError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.healthy {
+                Ok(test_findings("src/main.rs", &agent.profile.name).into())
+            } else {
+                Err(ProviderError::ApiError(
+                    "529 overloaded_error: Overloaded".to_string(),
+                ))
+            }
+        }
+
+        async fn triage(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+        ) -> Result<Vec<TriageVerdict>, ProviderError> {
+            Ok(Vec::new())
+        }
+    }
+
+    let cache_dir = tempfile::tempdir().unwrap();
+    let config = Config::default();
+    let orchestrator = |primary: Arc<OverloadedProvider>| {
+        ReviewOrchestrator::new(
+            primary,
+            &config,
+            CacheEngine::new_with_dir(cache_dir.path().to_path_buf()),
+            Arc::new(ProgressTracker::new(&[], &[], false)),
+            false,
+            None,
+            String::new(),
+        )
+    };
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 1;")],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+    let agents = vec![test_agent("backend")];
+
+    let primary = Arc::new(OverloadedProvider {
+        calls: AtomicUsize::new(0),
+        healthy: false,
+    });
+    let fallback = Arc::new(OverloadedProvider {
+        calls: AtomicUsize::new(0),
+        healthy: true,
+    });
+    let result = orchestrator(Arc::clone(&primary))
+        .with_fallbacks(vec![ChainedProvider {
+            provider: Arc::clone(&fallback) as Arc<dyn ReviewProvider>,
+            name: ProviderName::OpenAI,
+            model: "gpt-4o".to_string(),
+        }])
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(result.failed_tasks, 0);
+    assert_eq!(
+        primary.calls.load(Ordering::SeqCst),
+        nitpik::constants::FALLBACK_AFTER_OVERLOADS as usize
+    );
+    assert_eq!(fallback.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(result.findings.len(), 2);
+    let served_by = ServedBy {
+        provider: ProviderName::OpenAI,
+        model: "gpt-4o".to_string(),
+    };
+    assert!(
+        result
+            .findings
+            .iter()
+            .all(|f| f.served_by.as_ref() == Some(&served_by))
+    );
+    assert_eq!(result.usage.by_agent["backend"].requests, 1);
+
+    // The fallback's result isn't reused as the primary model's review
+    let recovered = Arc::new(OverloadedProvider {
+        calls: AtomicUsize::new(0),
+        healthy: true,
+    });
+    let result = orchestrator(Arc::clone(&recovered))
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("run should succeed");
+    assert_eq!(recovered.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(result.usage.total.cache_hits, 0);
+    assert_eq!(
+        result.findings[0].served_by.as_ref().map(|s| s.provider),
+        Some(ProviderName::Anthropic)
+    );
+}
+
+/// Profiles that select a named provider are reviewed by it, with its
+/// model, while the rest stay on the default provider.
+#[tokio::test]
+async fn profiles_route_to_named_providers() {
+    use nitpik::config::ProviderConfig;
+    use nitpik::models::ProviderName;
+    use nitpik::orchestrator::{ChainedProvider, OrchestratorError};
+    use std::sync::Mutex;
+
+    /// Records the agent and model of every review call.
+    #[derive(Default)]
+    struct RecordingProvider {
+        calls: Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    #[async_trait]
+    impl ReviewProvider for RecordingProvider {
+        async fn review(
+            &self,
+            agent: &AgentDefinition,
+            _prompt: &ReviewPrompt,
+            _agentic: bool,
+            _max_turns: usize,
+            _max_tool_calls: usize,
+        ) -> Result<ReviewOutput, ProviderError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((agent.profile.name.clone(), agent.profile.model.clone()));
+            Ok(test_findings("src/main.rs", &agent.profile.name).into())
+        }
+
+        async fn triage(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+        ) -> Result<Vec<TriageVerdict>, ProviderError> {
+            Ok(Vec::new())
+        }
+    }
+
+    let mut config = Config::default();
+    config.providers.insert(
+        "local".to_string(),
+        ProviderConfig {
+            name: ProviderName::Ollama,
+            model: Some("llama3".to_string()),
+            ..Default::default()
+        },
+    );
+    let context = ReviewContext {
+        diffs: vec![test_diff("src/main.rs", "let x = 1;")],
+        baseline: BaselineContext::default(),
+        repo_root: "/tmp/test-repo".to_string(),
+        is_path_scan: false,
+    };
+    let mut frontend = test_agent("frontend");
+    frontend.profile.provider = Some("local".to_string());
+    let agents = vec![test_agent("security"), frontend];
+
+    let primary = Arc::new(RecordingProvider::default());
+    let local = Arc::new(RecordingProvider::default());
+    let orchestrator = |config: &Config| {
+        ReviewOrchestrator::new(
+            Arc::clone(&primary) as Arc<dyn ReviewProvider>,
+            config,
+            CacheEngine::new(false),
+            Arc::new(ProgressTracker::new(&[], &[], false)),
+            false,
+            None,
+            String::new(),
+        )
+    };
+
+    let result = orchestrator(&config)
+        .with_provider(
+            "local",
+            ChainedProvider {
+                provider: Arc::clone(&local) as Arc<dyn ReviewProvider>,
+                name: ProviderName::Ollama,
+                model: "llama3".to_string(),
+            },
+            Vec::new(),
+        )
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .expect("run should succeed");
+
+    assert_eq!(result.failed_tasks, 0);
+    assert_eq!(
+        *primary.calls.lock().unwrap(),
+        vec![(
+            "security".to_string(),
+            Some("claude-sonnet-4-20250514".to_string())
+        )]
+    );
+    assert_eq!(
+        *local.calls.lock().unwrap(),
+        vec![("frontend".to_string(), Some("llama3".to_string()))]
+    );
+    for finding in &result.findings {
+        let provider = finding.served_by.as_ref().map(|s| s.provider);
+        let expected = if finding.agent == "frontend" {
+            ProviderName::Ollama
+        } else {
+            ProviderName::Anthropic
+        };
+        assert_eq!(provider, Some(expected));
+    }
+
+    // A provider that was never registered fails the run up front
+    let err = orchestrator(&config)
+        .run(&context, &agents, 4, false, 10, 50)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        OrchestratorError::UnknownProvider { ref profile, ref provider }
+            if profile == "frontend" && provider == "local"
+    ));
+}
+
 /// Verifies the sidecar-based prior-findings injection flow:
 ///
 /// 1. First run: provider returns findings, cache + sidecar are written.
@@ -523,21 +1515,18 @@ async fn prior_findings_injected_on_cache_invalidation() {
         async fn review(
             &self,
             _agent: &AgentDefinition,
-            prompt: &str,
+            prompt: &ReviewPrompt,
             _agentic: bool,
             _max_turns: usize,
             _max_tool_calls: usize,
-        ) -> Result<Vec<Finding>, ProviderError> {
+        ) -> Result<ReviewOutput, ProviderError> {
             self.call_count.fetch_add(1, Ordering::SeqCst);
-            self.captured_prompts
-                .lock()
-                .unwrap()
-                .push(prompt.to_string());
+            self.captured_prompts.lock().unwrap().push(prompt.text());
 
-            if prompt.contains("Previous Review Findings") {
-                Ok(self.followup_findings.clone())
+            if prompt.body.contains("Previous Review Findings") {
+                Ok(self.followup_findings.clone().into())
             } else {
-                Ok(self.initial_findings.clone())
+                Ok(self.initial_findings.clone().into())
             }
         }
 
@@ -560,6 +1549,8 @@ async fn prior_findings_injected_on_cache_invalidation() {
         message: "Could panic at runtime.".to_string(),
         suggestion: Some("Add a None check.".to_string()),
         agent: "prior-agent".to_string(),
+        fix: None,
+        served_by: None,
     }];
 
     // Follow-up findings (the model's response when it sees prior context)
@@ -573,6 +1564,8 @@ async fn prior_findings_injected_on_cache_invalidation() {
         message: "The previous null deref was fixed but naming could improve.".to_string(),
         suggestion: None,
         agent: "prior-agent".to_string(),
+        fix: None,
+        served_by: None,
     }];
 
     let provider = Arc::new(PromptCapturingProvider {
@@ -615,8 +1608,10 @@ async fn prior_findings_injected_on_cache_invalidation() {
          ## Instructions\n\nReview the diff above for file `src/app.rs`. \
          You are the **prior-agent** reviewer: Test agent: prior-agent\n\n"
     );
-    let cache_key1 =
-        nitpik::cache::cache_key(&prompt1, "prior-agent", config.provider.resolved_model());
+    let cache_key1 = nitpik::cache::KeyComponents::new()
+        .with("diff", &prompt1)
+        .with("model", config.provider.resolved_model())
+        .key();
 
     // Store findings + sidecar for run 1
     store.put(&cache_key1, &initial_findings).await;
@@ -747,17 +1742,14 @@ async fn no_prior_context_flag_suppresses_injection() {
         async fn review(
             &self,
             _agent: &AgentDefinition,
-            prompt: &str,
+            prompt: &ReviewPrompt,
             _agentic: bool,
             _max_turns: usize,
             _max_tool_calls: usize,
-        ) -> Result<Vec<Finding>, ProviderError> {
+        ) -> Result<ReviewOutput, ProviderError> {
             self.call_count.fetch_add(1, Ordering::SeqCst);
-            self.captured_prompts
-                .lock()
-                .unwrap()
-                .push(prompt.to_string());
-            Ok(vec![])
+            self.captured_prompts.lock().unwrap().push(prompt.text());
+            Ok(vec![].into())
         }
 
         async fn triage(
@@ -778,6 +1770,8 @@ async fn no_prior_context_flag_suppresses_injection() {
         message: "User input concatenated into query.".to_string(),
         suggestion: None,
         agent: "sec-agent".to_string(),
+        fix: None,
+        served_by: None,
     }];
 
     let config = Config::default();
@@ -789,8 +1783,10 @@ async fn no_prior_context_flag_suppresses_injection() {
 
     let seed_content = format!("let seed_npc_{} = 1;", std::process::id());
     let seed_prompt = format!("seed-prompt-{seed_content}");
-    let seed_key =
-        nitpik::cache::cache_key(&seed_prompt, "sec-agent", config.provider.resolved_model());
+    let seed_key = nitpik::cache::KeyComponents::new()
+        .with("diff", &seed_prompt)
+        .with("model", config.provider.resolved_model())
+        .key();
     seeded_store.put(&seed_key, &initial_findings).await;
     seeded_store
         .put_sidecar(
@@ -870,6 +1866,7 @@ fn test_agent_with_tools(
             name: name.to_string(),
             description: format!("Test agent with tools: {name}"),
             model: None,
+            provider: None,
             tags: vec![],
             tools,
             agentic_instructions: None,
@@ -896,12 +1893,12 @@ async fn custom_tools_appear_in_agentic_prompt() {
         async fn review(
             &self,
             _agent: &AgentDefinition,
-            prompt: &str,
+            prompt: &ReviewPrompt,
             _agentic: bool,
             _max_turns: usize,
             _max_tool_calls: usize,
-        ) -> Result<Vec<Finding>, ProviderError> {
-            self.prompts.lock().unwrap().push(prompt.to_string());
+        ) -> Result<ReviewOutput, ProviderError> {
+            self.prompts.lock().unwrap().push(prompt.text());
             Ok(vec![Finding {
                 file: "src/app.rs".to_string(),
                 line: 2,
@@ -911,7 +1908,10 @@ async fn custom_tools_appear_in_agentic_prompt() {
                 message: "Minor style issue.".to_string(),
                 suggestion: None,
                 agent: "tool-agent".to_string(),
-            }])
+                fix: None,
+                served_by: None,
+            }]
+            .into())
         }
 
         async fn triage(
@@ -1018,12 +2018,12 @@ async fn custom_tools_absent_in_non_agentic_prompt() {
         async fn review(
             &self,
             _agent: &AgentDefinition,
-            prompt: &str,
+            prompt: &ReviewPrompt,
             _agentic: bool,
             _max_turns: usize,
             _max_tool_calls: usize,
-        ) -> Result<Vec<Finding>, ProviderError> {
-            self.prompts.lock().unwrap().push(prompt.to_string());
+        ) -> Result<ReviewOutput, ProviderError> {
+            self.prompts.lock().unwrap().push(prompt.text());
             Ok(vec![Finding {
                 file: "src/app.rs".to_string(),
                 line: 2,
@@ -1033,7 +2033,10 @@ async fn custom_tools_absent_in_non_agentic_prompt() {
                 message: "Minor.".to_string(),
                 suggestion: None,
                 agent: "tool-agent".to_string(),
-            }])
+                fix: None,
+                served_by: None,
+            }]
+            .into())
         }
 
         async fn triage(
@@ -1206,12 +2209,12 @@ impl ReviewProvider for TriageMockProvider {
     async fn review(
         &self,
         _agent: &AgentDefinition,
-        _prompt: &str,
+        _prompt: &ReviewPrompt,
         _agentic: bool,
         _max_turns: usize,
         _max_tool_calls: usize,
-    ) -> Result<Vec<Finding>, ProviderError> {
-        Ok(vec![])
+    ) -> Result<ReviewOutput, ProviderError> {
+        Ok(vec![].into())
     }
 
     async fn triage(
@@ -1247,10 +2250,7 @@ async fn threat_scanner_triage_dismisses_finding() {
     );
 
     // Now triage: dismiss finding #0, confirm finding #1
-    let provider = TriageMockProvider::from_canned(vec![
-        (0, "dismissed"),
-        (1, "confirmed"),
-    ]);
+    let provider = TriageMockProvider::from_canned(vec![(0, "dismissed"), (1, "confirmed")]);
 
     let triaged_findings = nitpik::threat::scan_for_threats(
         &[diff],
@@ -11,6 +11,7 @@ use nitpik::output::forgejo::ForgejoFormatter;
 use nitpik::output::github::GithubFormatter;
 use nitpik::output::gitlab::GitlabFormatter;
 use nitpik::output::json::JsonFormatter;
+use nitpik::output::sarif::SarifFormatter;
 
 /// Standard test findings used across all snapshot tests.
 fn test_findings() -> Vec<Finding> {
@@ -24,6 +25,8 @@ fn test_findings() -> Vec<Finding> {
             message: "Using .unwrap() can cause a panic at runtime. Use proper error handling with ? or .expect().".into(),
             suggestion: Some("Replace .unwrap() with .context(\"description\")? using anyhow".into()),
             agent: "backend".into(),
+            fix: None,
+            served_by: None,
         },
         Finding {
             file: "src/main.rs".into(),
@@ -34,6 +37,8 @@ fn test_findings() -> Vec<Finding> {
             message: "This error propagation loses context about what operation failed.".into(),
             suggestion: None,
             agent: "backend".into(),
+            fix: None,
+            served_by: None,
         },
         Finding {
             file: "src/utils.rs".into(),
@@ -44,6 +49,8 @@ fn test_findings() -> Vec<Finding> {
             message: "This block of logic is repeated in multiple places and could be extracted into a shared helper function.".into(),
             suggestion: Some("Create a `validate_input()` function in utils.rs".into()),
             agent: "architect".into(),
+            fix: None,
+            served_by: None,
         },
     ]
 }
@@ -229,3 +236,46 @@ fn generate_forgejo_fixture() {
     std::fs::write("tests/fixtures/expected_forgejo_output.json", pretty + "\n").unwrap();
     eprintln!("Wrote tests/fixtures/expected_forgejo_output.json");
 }
+
+/// Render SARIF with the tool version removed so the snapshot survives releases.
+fn render_sarif_without_version() -> serde_json::Value {
+    let output = SarifFormatter.format(&test_findings());
+    let mut parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    parsed["runs"][0]["tool"]["driver"]
+        .as_object_mut()
+        .unwrap()
+        .remove("semanticVersion");
+    parsed
+}
+
+#[test]
+fn snapshot_sarif_renderer() {
+    let actual = render_sarif_without_version();
+    let expected_str =
+        std::fs::read_to_string("tests/fixtures/expected_sarif_output.json").unwrap();
+    let expected: serde_json::Value = serde_json::from_str(&expected_str).unwrap();
+
+    assert_eq!(
+        actual, expected,
+        "SARIF renderer output does not match snapshot.\nActual:\n{actual:#}"
+    );
+}
+
+#[test]
+fn sarif_renderer_empty_findings() {
+    let output = SarifFormatter.format(&[]);
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(parsed["version"], "2.1.0");
+    assert!(parsed["runs"][0]["results"].as_array().unwrap().is_empty());
+}
+
+/// Generate the SARIF fixture file from current renderer output.
+///
+/// Run with: `cargo test generate_sarif_fixture -- --ignored`
+#[test]
+#[ignore]
+fn generate_sarif_fixture() {
+    let pretty = serde_json::to_string_pretty(&render_sarif_without_version()).unwrap();
+    std::fs::write("tests/fixtures/expected_sarif_output.json", pretty + "\n").unwrap();
+    eprintln!("Wrote tests/fixtures/expected_sarif_output.json");
+}